@@ -0,0 +1,209 @@
+use std::{error::Error, time::Duration};
+
+use strum::Display;
+
+use crate::can::{can_id::CANID, dlc::DLC, frame::CANFrame};
+
+// Every bit of a base data frame except the data field, including the inter frame spacing.
+const BASE_FRAME_OVERHEAD: usize = 47;
+const EXTENDED_FRAME_OVERHEAD: usize = 67;
+// The bits from the start of frame to the end of the cyclic redundancy check, which are the only
+// ones that are stuffed, except the data field.
+const BASE_STUFFED_OVERHEAD: usize = 34;
+const EXTENDED_STUFFED_OVERHEAD: usize = 54;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BusLoadError {
+    BitrateMustBeNonZero,
+    PeriodMustBeNonZero,
+    DataFieldTooLong,
+}
+
+impl Error for BusLoadError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PeriodicMessage {
+    identifier: CANID,
+    data_field_length: usize,
+    period: Duration,
+}
+
+impl PeriodicMessage {
+    pub fn new(
+        identifier: CANID,
+        data_field_length: usize,
+        period: Duration,
+    ) -> Result<Self, BusLoadError> {
+        if DLC::from_len_classic(data_field_length).is_none() {
+            return Err(BusLoadError::DataFieldTooLong);
+        }
+
+        if period.is_zero() {
+            return Err(BusLoadError::PeriodMustBeNonZero);
+        }
+
+        Ok(Self {
+            identifier,
+            data_field_length,
+            period,
+        })
+    }
+
+    pub fn identifier(&self) -> CANID {
+        self.identifier
+    }
+
+    pub fn data_field_length(&self) -> usize {
+        self.data_field_length
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    pub fn worst_case_bit_count(&self) -> usize {
+        // The data field length was checked on construction.
+        worst_case_bit_count(self.identifier, self.data_field_length).unwrap()
+    }
+}
+
+// The on-wire length of the frame, with its actual stuff bits and the inter frame spacing.
+pub fn bit_count<F: Into<CANFrame>>(can_frame: F) -> usize {
+    can_frame.into().to_stuffed_bits().len()
+}
+
+// The on-wire length of a classic data frame with the most stuff bits its length allows.
+pub fn worst_case_bit_count(
+    identifier: CANID,
+    data_field_length: usize,
+) -> Result<usize, BusLoadError> {
+    if DLC::from_len_classic(data_field_length).is_none() {
+        return Err(BusLoadError::DataFieldTooLong);
+    }
+
+    let (frame_overhead, stuffed_overhead) = match identifier {
+        CANID::Standard(_) => (BASE_FRAME_OVERHEAD, BASE_STUFFED_OVERHEAD),
+        CANID::Extended(_) => (EXTENDED_FRAME_OVERHEAD, EXTENDED_STUFFED_OVERHEAD),
+    };
+
+    // After the first stuff bit, each further one can be forced by only four more bits.
+    let stuffed_length = stuffed_overhead + 8 * data_field_length;
+    let worst_case_stuff_bit_count = (stuffed_length - 1) / 4;
+
+    Ok(frame_overhead + 8 * data_field_length + worst_case_stuff_bit_count)
+}
+
+// Every bit is assumed to be sent at the same bitrate, so this does not hold for CAN FD frames
+// that switch to a faster data phase.
+pub fn transmission_time(bit_count: usize, bitrate: u32) -> Result<Duration, BusLoadError> {
+    if bitrate == 0 {
+        return Err(BusLoadError::BitrateMustBeNonZero);
+    }
+
+    Ok(Duration::from_secs_f64(bit_count as f64 / f64::from(bitrate)))
+}
+
+// The share of the bus that the messages take up in the worst case, in percent.
+pub fn bus_load(periodic_messages: &[PeriodicMessage], bitrate: u32) -> Result<f64, BusLoadError> {
+    if bitrate == 0 {
+        return Err(BusLoadError::BitrateMustBeNonZero);
+    }
+
+    let bits_per_second: f64 = periodic_messages
+        .iter()
+        .map(|periodic_message| {
+            periodic_message.worst_case_bit_count() as f64
+                / periodic_message.period().as_secs_f64()
+        })
+        .sum();
+
+    Ok(100.0 * bits_per_second / f64::from(bitrate))
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::{u3, u11, u29};
+
+    use crate::{
+        can::frame::data::{base::BaseDataFrame, extended::ExtendedDataFrame},
+        j1939::{j1939_id::J1939ID, pdu::PDU, pgn::PGN},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_bit_count() {
+        let base_data_frame = assert_ok!(BaseDataFrame::new(u11::new(0x7FF), vec![0x00; 8]));
+        let base_data_frame_bit_count = bit_count(base_data_frame.clone());
+
+        assert_eq!(base_data_frame_bit_count, base_data_frame.to_stuffed_bits().len());
+        // 111 bits and 15 stuff bits, against a worst case of 135.
+        assert_eq!(base_data_frame_bit_count, 126);
+
+        let extended_data_frame =
+            assert_ok!(ExtendedDataFrame::new(u29::new(0x1FFFFFFF), vec![0xFF; 8]));
+
+        // 131 bits and 18 stuff bits, against a worst case of 160.
+        assert_eq!(bit_count(extended_data_frame), 149);
+    }
+
+    #[test]
+    fn test_worst_case_bit_count() {
+        assert_eq!(worst_case_bit_count(CANID::Standard(u11::new(0x000)), 0), Ok(55));
+        assert_eq!(worst_case_bit_count(CANID::Standard(u11::new(0x000)), 8), Ok(135));
+        assert_eq!(worst_case_bit_count(CANID::Extended(u29::new(0x00000000)), 0), Ok(80));
+        assert_eq!(worst_case_bit_count(CANID::Extended(u29::new(0x00000000)), 8), Ok(160));
+
+        assert_err_eq!(
+            worst_case_bit_count(CANID::Standard(u11::new(0x000)), 9),
+            BusLoadError::DataFieldTooLong
+        );
+    }
+
+    #[test]
+    fn test_transmission_time() {
+        assert_eq!(transmission_time(135, 500_000), Ok(Duration::from_micros(270)));
+        assert_eq!(transmission_time(160, 250_000), Ok(Duration::from_micros(640)));
+
+        assert_err_eq!(transmission_time(135, 0), BusLoadError::BitrateMustBeNonZero);
+    }
+
+    #[test]
+    fn test_bus_load() {
+        let electronic_engine_controller_1 =
+            J1939ID::new(u3::new(3), PGN::new(PDU::new(0xF0, 0x04)), 0x00);
+
+        let periodic_messages = [
+            assert_ok!(PeriodicMessage::new(
+                CANID::from(electronic_engine_controller_1),
+                8,
+                Duration::from_millis(10)
+            )),
+            assert_ok!(PeriodicMessage::new(
+                CANID::Standard(u11::new(0x100)),
+                8,
+                Duration::from_millis(20)
+            )),
+        ];
+
+        // 160 bits every 10 ms and 135 bits every 20 ms on a 250 kbit/s bus.
+        let load = assert_ok!(bus_load(&periodic_messages, 250_000));
+        assert!((load - 9.1).abs() < 1e-9);
+
+        assert_eq!(bus_load(&[], 250_000), Ok(0.0));
+        assert_err_eq!(bus_load(&periodic_messages, 0), BusLoadError::BitrateMustBeNonZero);
+    }
+
+    #[test]
+    fn test_periodic_message_errors() {
+        assert_err_eq!(
+            PeriodicMessage::new(CANID::Standard(u11::new(0x000)), 9, Duration::from_millis(10)),
+            BusLoadError::DataFieldTooLong
+        );
+        assert_err_eq!(
+            PeriodicMessage::new(CANID::Standard(u11::new(0x000)), 8, Duration::ZERO),
+            BusLoadError::PeriodMustBeNonZero
+        );
+    }
+}
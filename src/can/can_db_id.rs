@@ -2,7 +2,7 @@ use ux::u11;
 
 use crate::can::can_id::CANID;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CANDBID {
     Standard(u11),
     Extended(u32),
@@ -1,13 +1,43 @@
+use std::cmp::Ordering;
+
 use ux::{u11, u29};
 
 use crate::{can::can_db_id::CANDBID, j1939::j1939_id::J1939ID};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum CANID {
     Standard(u11),
     Extended(u29),
 }
 
+impl CANID {
+    // The identifier bits as they are sent in the arbitration field, followed by the substitute
+    // remote request and identifier extension bits, which a base data frame sends dominant.
+    fn arbitration_key(&self) -> (u32, bool, u32) {
+        match self {
+            Self::Standard(identifier) => (u32::from(*identifier), false, 0),
+            Self::Extended(identifier) => {
+                let identifier = u32::from(*identifier);
+                (identifier >> 18, true, identifier & 0x3FFFF)
+            }
+        }
+    }
+}
+
+// Lower identifiers win the arbitration, and a base frame wins against an extended frame with the
+// same base identifier.
+impl Ord for CANID {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.arbitration_key().cmp(&other.arbitration_key())
+    }
+}
+
+impl PartialOrd for CANID {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl From<J1939ID> for CANID {
     fn from(value: J1939ID) -> Self {
         Self::Extended(value.raw())
@@ -43,6 +73,34 @@ mod tests {
         assert_eq!(extended_can_db_id, CANDBID::Extended(0b10000000000000000000000000000001));
     }
 
+    #[test]
+    fn test_can_id_ordering() {
+        assert!(CANID::Standard(u11::new(0x000)) < CANID::Standard(u11::new(0x001)));
+        assert!(CANID::Extended(u29::new(0x00000000)) < CANID::Extended(u29::new(0x00000001)));
+        assert!(CANID::Standard(u11::new(0x000)) < CANID::Extended(u29::new(0x00000000)));
+        assert!(CANID::Standard(u11::new(0x001)) < CANID::Extended(u29::new(0x0007FFFF)));
+        assert!(CANID::Extended(u29::new(0x00000000)) < CANID::Standard(u11::new(0x001)));
+        assert!(CANID::Extended(u29::new(0x0003FFFF)) < CANID::Standard(u11::new(0x001)));
+
+        let mut can_ids = [
+            CANID::Extended(u29::new(0x18FEF100)),
+            CANID::Standard(u11::new(0x7FF)),
+            CANID::Standard(u11::new(0x63C)),
+            CANID::Extended(u29::new(0x0CF00400)),
+        ];
+        can_ids.sort();
+
+        assert_eq!(
+            can_ids,
+            [
+                CANID::Extended(u29::new(0x0CF00400)),
+                CANID::Standard(u11::new(0x63C)),
+                CANID::Extended(u29::new(0x18FEF100)),
+                CANID::Standard(u11::new(0x7FF)),
+            ]
+        );
+    }
+
     #[test]
     pub fn test_can_id_to_j1939_id() {
         let standard_can_id = CANID::Standard(u11::new(0b00000000000));
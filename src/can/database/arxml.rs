@@ -0,0 +1,628 @@
+use std::collections::BTreeMap;
+
+use roxmltree::Node as XMLNode;
+
+use crate::can::{
+    database::{
+        Database, ImportError, ImportErrorKind, Message, Multiplexing, Node, Signal,
+        ValueDescriptions, can_db_id,
+        xml::{
+            child, children, descendant, error_at, parse_document, parse_number, required_child,
+            text,
+        },
+    },
+    signal::{ByteOrder, Scaling, SignalSpec, ValueType},
+};
+
+// Elements are referred to by the path of short names of the packages and elements down to them.
+type Elements<'a, 'input> = BTreeMap<String, XMLNode<'a, 'input>>;
+
+// The scaling, range, labels and unit a computation method gives a signal.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Computation {
+    factor: Option<f64>,
+    offset: f64,
+    minimum: f64,
+    maximum: f64,
+    value_descriptions: ValueDescriptions,
+    unit: Option<String>,
+}
+
+// Parses the CAN frames of an AUTOSAR system description, from the triggerings that give their
+// identifiers down to the signals of their PDUs. Frames on every cluster are merged into the one
+// database.
+pub fn parse(text: &str) -> Result<Database, ImportError> {
+    let document = parse_document(text)?;
+    let autosar = document.root_element();
+
+    if autosar.tag_name().name() != "AUTOSAR" {
+        return Err(error_at(autosar, ImportErrorKind::MissingElement));
+    }
+
+    let mut elements = Elements::new();
+    collect_elements(autosar, "", &mut elements);
+
+    let mut database = Database::default();
+
+    for ecu_instance in autosar
+        .descendants()
+        .filter(|node| node.tag_name().name() == "ECU-INSTANCE")
+    {
+        database
+            .add_node(Node::new(short_name(ecu_instance)?))
+            .map_err(|error| error_at(ecu_instance, ImportErrorKind::InvalidDatabase(error)))?;
+    }
+
+    for frame_triggering in autosar
+        .descendants()
+        .filter(|node| node.tag_name().name() == "CAN-FRAME-TRIGGERING")
+    {
+        database
+            .add_message(parse_frame_triggering(frame_triggering, &elements)?)
+            .map_err(|error| error_at(frame_triggering, ImportErrorKind::InvalidDatabase(error)))?;
+    }
+
+    Ok(database)
+}
+
+fn collect_elements<'a, 'input>(
+    node: XMLNode<'a, 'input>,
+    path: &str,
+    elements: &mut Elements<'a, 'input>,
+) {
+    for element in node.children().filter(XMLNode::is_element) {
+        match child(element, "SHORT-NAME") {
+            Some(short_name) => {
+                let path = format!("{path}/{}", text(short_name));
+                elements.insert(path.clone(), element);
+                collect_elements(element, &path, elements);
+            }
+            None => collect_elements(element, path, elements),
+        }
+    }
+}
+
+fn parse_frame_triggering(
+    frame_triggering: XMLNode,
+    elements: &Elements,
+) -> Result<Message, ImportError> {
+    let identifier_element = required_child(frame_triggering, "IDENTIFIER")?;
+    let identifier = parse_number(identifier_element, text(identifier_element))?;
+    let extended = child(frame_triggering, "CAN-ADDRESSING-MODE")
+        .is_some_and(|addressing_mode| text(addressing_mode) == "EXTENDED");
+    let identifier = can_db_id(identifier, extended).ok_or(error_at(
+        identifier_element,
+        ImportErrorKind::IdentifierOutOfRange,
+    ))?;
+
+    let frame = resolve(required_child(frame_triggering, "FRAME-REF")?, elements)?;
+    let frame_length = required_child(frame, "FRAME-LENGTH")?;
+
+    let mut message = Message::new(
+        identifier,
+        short_name(frame)?,
+        parse_number(frame_length, text(frame_length))?,
+        None,
+    );
+    message.set_comment(description(frame));
+
+    for pdu_to_frame_mapping in frame
+        .descendants()
+        .filter(|node| node.tag_name().name() == "PDU-TO-FRAME-MAPPING")
+    {
+        let pdu = resolve(required_child(pdu_to_frame_mapping, "PDU-REF")?, elements)?;
+        let pdu_start_position: usize = child(pdu_to_frame_mapping, "START-POSITION")
+            .map(|start_position| parse_number(start_position, text(start_position)))
+            .transpose()?
+            .unwrap_or_default();
+
+        // Only the signals of plain signal PDUs are imported.
+        if pdu.tag_name().name() != "I-SIGNAL-I-PDU" {
+            continue;
+        }
+
+        for signal_to_pdu_mapping in pdu
+            .descendants()
+            .filter(|node| node.tag_name().name() == "I-SIGNAL-TO-I-PDU-MAPPING")
+        {
+            // Mappings of signal groups have no signal of their own.
+            let Some(signal_reference) = child(signal_to_pdu_mapping, "I-SIGNAL-REF") else {
+                continue;
+            };
+
+            let signal = parse_signal(
+                signal_to_pdu_mapping,
+                resolve(signal_reference, elements)?,
+                pdu_start_position,
+                elements,
+            )?;
+
+            message.add_signal(signal).map_err(|error| {
+                error_at(
+                    signal_to_pdu_mapping,
+                    ImportErrorKind::InvalidDatabase(error),
+                )
+            })?;
+        }
+    }
+
+    Ok(message)
+}
+
+// Signals are placed by their least significant bit, or for big endian signals by their most
+// significant bit, as in DBC files.
+fn parse_signal(
+    signal_to_pdu_mapping: XMLNode,
+    signal: XMLNode,
+    pdu_start_position: usize,
+    elements: &Elements,
+) -> Result<Signal, ImportError> {
+    let start_position = required_child(signal_to_pdu_mapping, "START-POSITION")?;
+    let start_bit =
+        pdu_start_position + parse_number::<usize>(start_position, text(start_position))?;
+
+    let byte_order = match child(signal_to_pdu_mapping, "PACKING-BYTE-ORDER").map(text) {
+        Some("MOST-SIGNIFICANT-BYTE-LAST") | Some("OPAQUE") | None => ByteOrder::LittleEndian,
+        Some("MOST-SIGNIFICANT-BYTE-FIRST") => ByteOrder::BigEndian,
+        Some(_) => {
+            return Err(error_at(
+                signal_to_pdu_mapping,
+                ImportErrorKind::InvalidByteOrder,
+            ));
+        }
+    };
+
+    let length_element = required_child(signal, "LENGTH")?;
+    let length = parse_number(length_element, text(length_element))?;
+
+    let system_signal = child(signal, "SYSTEM-SIGNAL-REF")
+        .map(|system_signal_reference| resolve(system_signal_reference, elements))
+        .transpose()?;
+
+    // The network representation of the signal takes precedence over the physical properties of
+    // its system signal.
+    let reference = |name: &str| {
+        descendant(signal, name)
+            .or_else(|| system_signal.and_then(|system_signal| descendant(system_signal, name)))
+            .map(|reference| resolve(reference, elements))
+            .transpose()
+    };
+
+    let value_type = match reference("BASE-TYPE-REF")?
+        .and_then(|base_type| child(base_type, "BASE-TYPE-ENCODING"))
+        .map(text)
+    {
+        Some("2C") => ValueType::Signed,
+        Some("IEEE754") if length == 32 => ValueType::Float32,
+        Some("IEEE754") => ValueType::Float64,
+        _ => ValueType::Unsigned,
+    };
+
+    let computation = reference("COMPU-METHOD-REF")?
+        .map(|compu_method| parse_compu_method(compu_method, elements))
+        .transpose()?
+        .unwrap_or_default();
+
+    let unit = match reference("UNIT-REF")? {
+        Some(unit) => Some(unit_name(unit)?),
+        None => computation.unit.clone(),
+    };
+
+    let spec = SignalSpec::new(
+        start_bit,
+        length,
+        byte_order,
+        value_type,
+        Scaling::new(computation.factor.unwrap_or(1.0), computation.offset),
+        computation.minimum..=computation.maximum,
+    )
+    .map_err(|error| error_at(signal, ImportErrorKind::InvalidSignal(error)))?;
+
+    let mut imported_signal = Signal::new(
+        short_name(signal)?,
+        spec,
+        unit.unwrap_or_default(),
+        Vec::new(),
+        Multiplexing::None,
+    );
+    imported_signal.set_value_descriptions(computation.value_descriptions);
+    imported_signal
+        .set_comment(description(signal).or_else(|| system_signal.and_then(description)));
+
+    Ok(imported_signal)
+}
+
+// Linear scales give the factor and offset, and the raw range they apply over. Scales of a
+// single value with a text give its label.
+fn parse_compu_method(
+    compu_method: XMLNode,
+    elements: &Elements,
+) -> Result<Computation, ImportError> {
+    let mut computation = Computation::default();
+
+    for compu_scale in compu_method
+        .descendants()
+        .filter(|node| node.tag_name().name() == "COMPU-SCALE")
+    {
+        let limit = |name: &str| {
+            child(compu_scale, name)
+                .map(|limit| parse_number::<f64>(limit, text(limit)))
+                .transpose()
+        };
+        let lower_limit = limit("LOWER-LIMIT")?;
+        let upper_limit = limit("UPPER-LIMIT")?;
+
+        if let Some(rational_coefficients) = child(compu_scale, "COMPU-RATIONAL-COEFFS") {
+            let coefficients = |name: &str| -> Result<Vec<f64>, ImportError> {
+                child(rational_coefficients, name)
+                    .into_iter()
+                    .flat_map(|coefficients| children(coefficients, "V"))
+                    .map(|coefficient| parse_number(coefficient, text(coefficient)))
+                    .collect()
+            };
+            let numerator = coefficients("COMPU-NUMERATOR")?;
+            let denominator = coefficients("COMPU-DENOMINATOR")?
+                .first()
+                .copied()
+                .unwrap_or(1.0);
+
+            let offset = numerator.first().copied().unwrap_or_default() / denominator;
+            let factor = numerator.get(1).copied().unwrap_or(1.0) / denominator;
+
+            computation.factor = Some(factor);
+            computation.offset = offset;
+
+            if let (Some(lower_limit), Some(upper_limit)) = (lower_limit, upper_limit) {
+                computation.minimum = lower_limit * factor + offset;
+                computation.maximum = upper_limit * factor + offset;
+            }
+        } else if let Some(label) =
+            child(compu_scale, "COMPU-CONST").and_then(|compu_const| child(compu_const, "VT"))
+            && let Some(lower_limit) = lower_limit
+        {
+            computation
+                .value_descriptions
+                .insert(lower_limit as i64, String::from(text(label)));
+        }
+    }
+
+    computation.unit = child(compu_method, "UNIT-REF")
+        .map(|unit_reference| unit_name(resolve(unit_reference, elements)?))
+        .transpose()?;
+
+    Ok(computation)
+}
+
+fn resolve<'a, 'input>(
+    reference: XMLNode,
+    elements: &Elements<'a, 'input>,
+) -> Result<XMLNode<'a, 'input>, ImportError> {
+    elements
+        .get(text(reference))
+        .copied()
+        .ok_or(error_at(reference, ImportErrorKind::UnknownReference))
+}
+
+fn short_name(element: XMLNode) -> Result<String, ImportError> {
+    Ok(String::from(text(required_child(element, "SHORT-NAME")?)))
+}
+
+fn unit_name(unit: XMLNode) -> Result<String, ImportError> {
+    match child(unit, "DISPLAY-NAME") {
+        Some(display_name) => Ok(String::from(text(display_name))),
+        None => short_name(unit),
+    }
+}
+
+fn description(element: XMLNode) -> Option<String> {
+    child(element, "DESC")
+        .and_then(|description| child(description, "L-2"))
+        .map(|description| String::from(text(description)))
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok, assert_some, assert_some_eq};
+    use ux::{u11, u29};
+
+    use crate::can::{can_db_id::CANDBID, can_id::CANID};
+
+    use super::*;
+
+    const ARXML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AUTOSAR xmlns="http://autosar.org/schema/r4.0">
+  <AR-PACKAGES>
+    <AR-PACKAGE>
+      <SHORT-NAME>System</SHORT-NAME>
+      <ELEMENTS>
+        <ECU-INSTANCE>
+          <SHORT-NAME>Engine</SHORT-NAME>
+        </ECU-INSTANCE>
+        <CAN-CLUSTER>
+          <SHORT-NAME>Powertrain</SHORT-NAME>
+          <CAN-CLUSTER-VARIANTS>
+            <CAN-CLUSTER-CONDITIONAL>
+              <PHYSICAL-CHANNELS>
+                <CAN-PHYSICAL-CHANNEL>
+                  <SHORT-NAME>Channel</SHORT-NAME>
+                  <FRAME-TRIGGERINGS>
+                    <CAN-FRAME-TRIGGERING>
+                      <SHORT-NAME>EngineStatusTriggering</SHORT-NAME>
+                      <FRAME-REF DEST="CAN-FRAME">/Frames/EngineStatus</FRAME-REF>
+                      <CAN-ADDRESSING-MODE>STANDARD</CAN-ADDRESSING-MODE>
+                      <IDENTIFIER>256</IDENTIFIER>
+                    </CAN-FRAME-TRIGGERING>
+                    <CAN-FRAME-TRIGGERING>
+                      <SHORT-NAME>DiagnosticsTriggering</SHORT-NAME>
+                      <FRAME-REF DEST="CAN-FRAME">/Frames/Diagnostics</FRAME-REF>
+                      <CAN-ADDRESSING-MODE>EXTENDED</CAN-ADDRESSING-MODE>
+                      <IDENTIFIER>512</IDENTIFIER>
+                    </CAN-FRAME-TRIGGERING>
+                  </FRAME-TRIGGERINGS>
+                </CAN-PHYSICAL-CHANNEL>
+              </PHYSICAL-CHANNELS>
+            </CAN-CLUSTER-CONDITIONAL>
+          </CAN-CLUSTER-VARIANTS>
+        </CAN-CLUSTER>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Frames</SHORT-NAME>
+      <ELEMENTS>
+        <CAN-FRAME>
+          <SHORT-NAME>EngineStatus</SHORT-NAME>
+          <DESC><L-2 L="EN">Engine state</L-2></DESC>
+          <FRAME-LENGTH>4</FRAME-LENGTH>
+          <PDU-TO-FRAME-MAPPINGS>
+            <PDU-TO-FRAME-MAPPING>
+              <SHORT-NAME>EngineStatusMapping</SHORT-NAME>
+              <PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-LAST</PACKING-BYTE-ORDER>
+              <PDU-REF DEST="I-SIGNAL-I-PDU">/Pdus/EngineStatusPdu</PDU-REF>
+              <START-POSITION>0</START-POSITION>
+            </PDU-TO-FRAME-MAPPING>
+          </PDU-TO-FRAME-MAPPINGS>
+        </CAN-FRAME>
+        <CAN-FRAME>
+          <SHORT-NAME>Diagnostics</SHORT-NAME>
+          <FRAME-LENGTH>8</FRAME-LENGTH>
+        </CAN-FRAME>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Pdus</SHORT-NAME>
+      <ELEMENTS>
+        <I-SIGNAL-I-PDU>
+          <SHORT-NAME>EngineStatusPdu</SHORT-NAME>
+          <LENGTH>4</LENGTH>
+          <I-SIGNAL-TO-PDU-MAPPINGS>
+            <I-SIGNAL-TO-I-PDU-MAPPING>
+              <SHORT-NAME>SpeedMapping</SHORT-NAME>
+              <I-SIGNAL-REF DEST="I-SIGNAL">/Signals/Speed</I-SIGNAL-REF>
+              <PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-LAST</PACKING-BYTE-ORDER>
+              <START-POSITION>0</START-POSITION>
+            </I-SIGNAL-TO-I-PDU-MAPPING>
+            <I-SIGNAL-TO-I-PDU-MAPPING>
+              <SHORT-NAME>TemperatureMapping</SHORT-NAME>
+              <I-SIGNAL-REF DEST="I-SIGNAL">/Signals/Temperature</I-SIGNAL-REF>
+              <PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-FIRST</PACKING-BYTE-ORDER>
+              <START-POSITION>23</START-POSITION>
+            </I-SIGNAL-TO-I-PDU-MAPPING>
+            <I-SIGNAL-TO-I-PDU-MAPPING>
+              <SHORT-NAME>GearMapping</SHORT-NAME>
+              <I-SIGNAL-REF DEST="I-SIGNAL">/Signals/Gear</I-SIGNAL-REF>
+              <PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-LAST</PACKING-BYTE-ORDER>
+              <START-POSITION>24</START-POSITION>
+            </I-SIGNAL-TO-I-PDU-MAPPING>
+          </I-SIGNAL-TO-PDU-MAPPINGS>
+        </I-SIGNAL-I-PDU>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Signals</SHORT-NAME>
+      <ELEMENTS>
+        <I-SIGNAL>
+          <SHORT-NAME>Speed</SHORT-NAME>
+          <LENGTH>16</LENGTH>
+          <NETWORK-REPRESENTATION-PROPS>
+            <SW-DATA-DEF-PROPS-VARIANTS>
+              <SW-DATA-DEF-PROPS-CONDITIONAL>
+                <COMPU-METHOD-REF DEST="COMPU-METHOD">/Methods/Speed</COMPU-METHOD-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL>
+            </SW-DATA-DEF-PROPS-VARIANTS>
+          </NETWORK-REPRESENTATION-PROPS>
+          <SYSTEM-SIGNAL-REF DEST="SYSTEM-SIGNAL">/Signals/SpeedSystemSignal</SYSTEM-SIGNAL-REF>
+        </I-SIGNAL>
+        <SYSTEM-SIGNAL>
+          <SHORT-NAME>SpeedSystemSignal</SHORT-NAME>
+          <DESC><L-2 L="EN">Crankshaft speed</L-2></DESC>
+        </SYSTEM-SIGNAL>
+        <I-SIGNAL>
+          <SHORT-NAME>Temperature</SHORT-NAME>
+          <LENGTH>8</LENGTH>
+          <NETWORK-REPRESENTATION-PROPS>
+            <SW-DATA-DEF-PROPS-VARIANTS>
+              <SW-DATA-DEF-PROPS-CONDITIONAL>
+                <BASE-TYPE-REF DEST="SW-BASE-TYPE">/Types/Sint8</BASE-TYPE-REF>
+                <COMPU-METHOD-REF DEST="COMPU-METHOD">/Methods/Temperature</COMPU-METHOD-REF>
+                <UNIT-REF DEST="UNIT">/Units/DegreesCelsius</UNIT-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL>
+            </SW-DATA-DEF-PROPS-VARIANTS>
+          </NETWORK-REPRESENTATION-PROPS>
+        </I-SIGNAL>
+        <I-SIGNAL>
+          <SHORT-NAME>Gear</SHORT-NAME>
+          <LENGTH>2</LENGTH>
+          <NETWORK-REPRESENTATION-PROPS>
+            <SW-DATA-DEF-PROPS-VARIANTS>
+              <SW-DATA-DEF-PROPS-CONDITIONAL>
+                <COMPU-METHOD-REF DEST="COMPU-METHOD">/Methods/Gear</COMPU-METHOD-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL>
+            </SW-DATA-DEF-PROPS-VARIANTS>
+          </NETWORK-REPRESENTATION-PROPS>
+        </I-SIGNAL>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Methods</SHORT-NAME>
+      <ELEMENTS>
+        <COMPU-METHOD>
+          <SHORT-NAME>Speed</SHORT-NAME>
+          <CATEGORY>LINEAR</CATEGORY>
+          <UNIT-REF DEST="UNIT">/Units/Rpm</UNIT-REF>
+          <COMPU-INTERNAL-TO-PHYS>
+            <COMPU-SCALES>
+              <COMPU-SCALE>
+                <LOWER-LIMIT>0</LOWER-LIMIT>
+                <UPPER-LIMIT>65535</UPPER-LIMIT>
+                <COMPU-RATIONAL-COEFFS>
+                  <COMPU-NUMERATOR><V>0</V><V>1</V></COMPU-NUMERATOR>
+                  <COMPU-DENOMINATOR><V>4</V></COMPU-DENOMINATOR>
+                </COMPU-RATIONAL-COEFFS>
+              </COMPU-SCALE>
+            </COMPU-SCALES>
+          </COMPU-INTERNAL-TO-PHYS>
+        </COMPU-METHOD>
+        <COMPU-METHOD>
+          <SHORT-NAME>Temperature</SHORT-NAME>
+          <CATEGORY>SCALE_LINEAR_AND_TEXTTABLE</CATEGORY>
+          <COMPU-INTERNAL-TO-PHYS>
+            <COMPU-SCALES>
+              <COMPU-SCALE>
+                <LOWER-LIMIT>0</LOWER-LIMIT>
+                <UPPER-LIMIT>127</UPPER-LIMIT>
+                <COMPU-RATIONAL-COEFFS>
+                  <COMPU-NUMERATOR><V>-40</V><V>1</V></COMPU-NUMERATOR>
+                  <COMPU-DENOMINATOR><V>1</V></COMPU-DENOMINATOR>
+                </COMPU-RATIONAL-COEFFS>
+              </COMPU-SCALE>
+              <COMPU-SCALE>
+                <LOWER-LIMIT>-1</LOWER-LIMIT>
+                <UPPER-LIMIT>-1</UPPER-LIMIT>
+                <COMPU-CONST><VT>Error</VT></COMPU-CONST>
+              </COMPU-SCALE>
+            </COMPU-SCALES>
+          </COMPU-INTERNAL-TO-PHYS>
+        </COMPU-METHOD>
+        <COMPU-METHOD>
+          <SHORT-NAME>Gear</SHORT-NAME>
+          <CATEGORY>TEXTTABLE</CATEGORY>
+          <COMPU-INTERNAL-TO-PHYS>
+            <COMPU-SCALES>
+              <COMPU-SCALE>
+                <LOWER-LIMIT>0</LOWER-LIMIT>
+                <UPPER-LIMIT>0</UPPER-LIMIT>
+                <COMPU-CONST><VT>Park</VT></COMPU-CONST>
+              </COMPU-SCALE>
+              <COMPU-SCALE>
+                <LOWER-LIMIT>3</LOWER-LIMIT>
+                <UPPER-LIMIT>3</UPPER-LIMIT>
+                <COMPU-CONST><VT>Drive</VT></COMPU-CONST>
+              </COMPU-SCALE>
+            </COMPU-SCALES>
+          </COMPU-INTERNAL-TO-PHYS>
+        </COMPU-METHOD>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Types</SHORT-NAME>
+      <ELEMENTS>
+        <SW-BASE-TYPE>
+          <SHORT-NAME>Sint8</SHORT-NAME>
+          <BASE-TYPE-SIZE>8</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>2C</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Units</SHORT-NAME>
+      <ELEMENTS>
+        <UNIT>
+          <SHORT-NAME>Rpm</SHORT-NAME>
+          <DISPLAY-NAME>rpm</DISPLAY-NAME>
+        </UNIT>
+        <UNIT>
+          <SHORT-NAME>DegreesCelsius</SHORT-NAME>
+          <DISPLAY-NAME>degC</DISPLAY-NAME>
+        </UNIT>
+      </ELEMENTS>
+    </AR-PACKAGE>
+  </AR-PACKAGES>
+</AUTOSAR>
+"#;
+
+    #[test]
+    fn test_parse() {
+        let database = assert_ok!(parse(ARXML));
+
+        let node_names: Vec<&str> = database.nodes().iter().map(Node::name).collect();
+        assert_eq!(node_names, vec!["Engine"]);
+
+        let engine_status = assert_some!(database.message(&CANDBID::Standard(u11::new(0x100))));
+        assert_eq!(engine_status.name(), "EngineStatus");
+        assert_eq!(engine_status.size(), 4);
+        assert_eq!(engine_status.comment(), Some("Engine state"));
+
+        let speed = assert_some!(engine_status.signal("Speed"));
+        assert_eq!(speed.spec().factor(), 0.25);
+        assert_eq!(speed.spec().maximum(), 16383.75);
+        assert_eq!(speed.unit(), "rpm");
+        assert_eq!(speed.comment(), Some("Crankshaft speed"));
+
+        let temperature = assert_some!(engine_status.signal("Temperature"));
+        assert_eq!(temperature.spec().start_bit(), 23);
+        assert_eq!(temperature.spec().byte_order(), ByteOrder::BigEndian);
+        assert_eq!(temperature.spec().value_type(), ValueType::Signed);
+        assert_eq!(temperature.spec().offset(), -40.0);
+        assert_eq!(temperature.unit(), "degC");
+        assert_some_eq!(temperature.value_descriptions().get(&-1), "Error");
+
+        let gear = assert_some!(engine_status.signal("Gear"));
+        assert_eq!(gear.spec().factor(), 1.0);
+        assert_some_eq!(gear.value_descriptions().get(&3), "Drive");
+
+        let diagnostics = assert_some!(database.message_by_name("Diagnostics"));
+        assert_eq!(diagnostics.identifier(), CANDBID::Extended(0x80000200));
+        assert!(diagnostics.signals().is_empty());
+
+        let decoded_message = assert_ok!(
+            database.decode(CANID::Standard(u11::new(0x100)), &[0x40, 0x1F, 0x5A, 0x03])
+        );
+        let decoded_values: Vec<f64> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.value())
+            .collect();
+        assert_eq!(decoded_values, vec![2000.0, 50.0, 3.0]);
+
+        assert_ok!(database.decode(CANID::Extended(u29::new(0x200)), &[0x00; 8]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_err_eq!(
+            parse("<ARXML/>"),
+            ImportError::new(1, 1, ImportErrorKind::MissingElement)
+        );
+
+        let arxml = r#"<AUTOSAR>
+  <CAN-FRAME-TRIGGERING>
+    <FRAME-REF>/Frames/Missing</FRAME-REF>
+    <IDENTIFIER>1</IDENTIFIER>
+  </CAN-FRAME-TRIGGERING>
+</AUTOSAR>"#;
+        assert_err_eq!(
+            parse(arxml),
+            ImportError::new(3, 5, ImportErrorKind::UnknownReference)
+        );
+
+        let arxml = r#"<AUTOSAR>
+  <CAN-FRAME-TRIGGERING>
+    <IDENTIFIER>2048</IDENTIFIER>
+  </CAN-FRAME-TRIGGERING>
+</AUTOSAR>"#;
+        assert_err_eq!(
+            parse(arxml),
+            ImportError::new(3, 5, ImportErrorKind::IdentifierOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::can::database::dbc::{DBCError, DBCErrorKind};
+
+const PUNCTUATION: &str = ":;|@+-(),[]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    Identifier(String),
+    // Numbers keep their text, as whether they are parsed as integers or floats depends on where
+    // they are.
+    Number(String),
+    String(String),
+    Punctuation(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+struct Lexer<'a> {
+    characters: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.characters.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.characters.next()?;
+
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(character)
+    }
+
+    fn next_if(&mut self, predicate: impl Fn(char) -> bool) -> Option<char> {
+        self.peek().filter(|character| predicate(*character))?;
+        self.next()
+    }
+
+    // A sign only starts a number when a digit or a decimal point follows it.
+    fn is_number_start(&self, character: char) -> bool {
+        if character.is_ascii_digit() || character == '.' {
+            return true;
+        }
+
+        if character == '-' || character == '+' {
+            let mut characters = self.characters.clone();
+            characters.next();
+            return characters
+                .next()
+                .is_some_and(|next| next.is_ascii_digit() || next == '.');
+        }
+
+        false
+    }
+
+    fn number(&mut self) -> String {
+        let mut number = String::new();
+
+        if let Some(sign) = self.next_if(|character| character == '-' || character == '+') {
+            number.push(sign);
+        }
+
+        while let Some(character) =
+            self.next_if(|character| character.is_ascii_digit() || character == '.')
+        {
+            number.push(character);
+        }
+
+        if let Some(exponent) = self.next_if(|character| character == 'e' || character == 'E') {
+            number.push(exponent);
+
+            if let Some(sign) = self.next_if(|character| character == '-' || character == '+') {
+                number.push(sign);
+            }
+
+            while let Some(character) = self.next_if(|character| character.is_ascii_digit()) {
+                number.push(character);
+            }
+        }
+
+        number
+    }
+
+    fn string(&mut self, line: usize, column: usize) -> Result<String, DBCError> {
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some(character) => string.push(character),
+                    None => break,
+                },
+                Some(character) => string.push(character),
+                None => break,
+            }
+        }
+
+        Err(DBCError::new(
+            line,
+            column,
+            DBCErrorKind::UnterminatedString,
+        ))
+    }
+}
+
+pub(crate) fn tokenize(text: &str) -> Result<Vec<Token>, DBCError> {
+    let mut lexer = Lexer {
+        characters: text.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    let mut tokens = Vec::new();
+
+    while let Some(character) = lexer.peek() {
+        if character.is_whitespace() {
+            lexer.next();
+            continue;
+        }
+
+        let (line, column) = (lexer.line, lexer.column);
+
+        let kind = if character == '"' {
+            lexer.next();
+            TokenKind::String(lexer.string(line, column)?)
+        } else if lexer.is_number_start(character) {
+            TokenKind::Number(lexer.number())
+        } else if character.is_ascii_alphabetic() || character == '_' {
+            let mut identifier = String::new();
+            while let Some(character) =
+                lexer.next_if(|character| character.is_ascii_alphanumeric() || character == '_')
+            {
+                identifier.push(character);
+            }
+            TokenKind::Identifier(identifier)
+        } else if PUNCTUATION.contains(character) {
+            lexer.next();
+            TokenKind::Punctuation(character)
+        } else {
+            return Err(DBCError::new(
+                line,
+                column,
+                DBCErrorKind::UnexpectedCharacter,
+            ));
+        };
+
+        tokens.push(Token { kind, line, column });
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = assert_ok!(tokenize("SG_ Speed : 7|16@0- (0.01,-40) \"km/h\"\n  ECU"));
+
+        let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind.clone()).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Identifier(String::from("SG_")),
+                TokenKind::Identifier(String::from("Speed")),
+                TokenKind::Punctuation(':'),
+                TokenKind::Number(String::from("7")),
+                TokenKind::Punctuation('|'),
+                TokenKind::Number(String::from("16")),
+                TokenKind::Punctuation('@'),
+                TokenKind::Number(String::from("0")),
+                TokenKind::Punctuation('-'),
+                TokenKind::Punctuation('('),
+                TokenKind::Number(String::from("0.01")),
+                TokenKind::Punctuation(','),
+                TokenKind::Number(String::from("-40")),
+                TokenKind::Punctuation(')'),
+                TokenKind::String(String::from("km/h")),
+                TokenKind::Identifier(String::from("ECU")),
+            ]
+        );
+
+        assert_eq!((tokens[1].line, tokens[1].column), (1, 5));
+        assert_eq!((tokens[15].line, tokens[15].column), (2, 3));
+    }
+
+    #[test]
+    fn test_tokenize_number_and_string() {
+        let tokens = assert_ok!(tokenize("1e-05 \"say \\\"hi\\\"\""));
+
+        assert_eq!(tokens[0].kind, TokenKind::Number(String::from("1e-05")));
+        assert_eq!(
+            tokens[1].kind,
+            TokenKind::String(String::from("say \"hi\""))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_err_eq!(
+            tokenize("CM_ \"unterminated"),
+            DBCError::new(1, 5, DBCErrorKind::UnterminatedString)
+        );
+        assert_err_eq!(
+            tokenize("BO_ 1 A:\n  8 # B"),
+            DBCError::new(2, 5, DBCErrorKind::UnexpectedCharacter)
+        );
+    }
+}
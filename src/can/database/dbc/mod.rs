@@ -0,0 +1,1940 @@
+use std::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use strum::Display;
+use ux::u11;
+
+use crate::can::{
+    can_db_id::CANDBID,
+    database::{
+        AttributeDefinition, AttributeObjectType, AttributeValue, AttributeValueType, Database,
+        DatabaseError, EXTENDED_IDENTIFIER_FLAG, ExtendedMultiplexing, Message, Multiplexing, Node,
+        Signal, ValueDescriptions,
+    },
+    signal::{ByteOrder, Scaling, SignalError, SignalSpec, ValueType},
+};
+
+use lexer::{Token, TokenKind, tokenize};
+
+mod lexer;
+
+// Vector tools write this in place of a missing transmitter or receiver.
+const NO_NODE: &str = "Vector__XXX";
+const MAXIMUM_STANDARD_IDENTIFIER: u32 = 0x7FF;
+// The symbols Vector tools declare, which are written out so that they load the file unchanged.
+const NEW_SYMBOLS: [&str; 27] = [
+    "NS_DESC_",
+    "CM_",
+    "BA_DEF_",
+    "BA_",
+    "VAL_",
+    "CAT_DEF_",
+    "CAT_",
+    "FILTER",
+    "BA_DEF_DEF_",
+    "EV_DATA_",
+    "ENVVAR_DATA_",
+    "SGTYPE_",
+    "SGTYPE_VAL_",
+    "BA_DEF_SGTYPE_",
+    "BA_SGTYPE_",
+    "SIG_TYPE_REF_",
+    "VAL_TABLE_",
+    "SIG_GROUP_",
+    "SIG_VALTYPE_",
+    "SIGTYPE_VALTYPE_",
+    "BO_TX_BU_",
+    "BA_DEF_REL_",
+    "BA_REL_",
+    "BA_DEF_DEF_REL_",
+    "BU_SG_REL_",
+    "BU_EV_REL_",
+    "BU_BO_REL_",
+];
+// The statements that can follow a node list, which has no terminator of its own.
+const KEYWORDS: [&str; 30] = [
+    "VERSION",
+    "NS_",
+    "BS_",
+    "BU_",
+    "VAL_TABLE_",
+    "BO_",
+    "SG_",
+    "BO_TX_BU_",
+    "EV_",
+    "ENVVAR_DATA_",
+    "SGTYPE_",
+    "SGTYPE_VAL_",
+    "SIG_TYPE_REF_",
+    "SIG_GROUP_",
+    "SIG_VALTYPE_",
+    "SIGTYPE_VALTYPE_",
+    "SG_MUL_VAL_",
+    "CM_",
+    "BA_DEF_",
+    "BA_DEF_SGTYPE_",
+    "BA_DEF_REL_",
+    "BA_DEF_DEF_",
+    "BA_DEF_DEF_REL_",
+    "BA_",
+    "BA_SGTYPE_",
+    "BA_REL_",
+    "VAL_",
+    "CAT_DEF_",
+    "CAT_",
+    "FILTER",
+];
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DBCErrorKind {
+    UnexpectedCharacter,
+    UnterminatedString,
+    UnexpectedEndOfFile,
+    UnexpectedToken,
+    InvalidNumber,
+    IdentifierOutOfRange,
+    InvalidByteOrder,
+    InvalidMultiplexing,
+    InvalidValueType,
+    InvalidSignal(SignalError),
+    InvalidAttributeValue,
+    DuplicateNode,
+    DuplicateValueTable,
+    DuplicateMessage,
+    DuplicateSignal,
+    DuplicateAttributeDefinition,
+    UnknownMessage,
+    UnknownSignal,
+    UnknownNode,
+    UnknownAttribute,
+    AttributeObjectTypeMismatch,
+}
+
+impl From<DatabaseError> for DBCErrorKind {
+    fn from(value: DatabaseError) -> Self {
+        match value {
+            DatabaseError::IdentifierInvalid => Self::IdentifierOutOfRange,
+            DatabaseError::DuplicateNode => Self::DuplicateNode,
+            DatabaseError::DuplicateValueTable => Self::DuplicateValueTable,
+            DatabaseError::DuplicateMessage => Self::DuplicateMessage,
+            DatabaseError::DuplicateSignal => Self::DuplicateSignal,
+            DatabaseError::DuplicateAttributeDefinition => Self::DuplicateAttributeDefinition,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DBCError {
+    line: usize,
+    column: usize,
+    kind: DBCErrorKind,
+}
+
+impl DBCError {
+    pub(crate) fn new(line: usize, column: usize, kind: DBCErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn kind(&self) -> DBCErrorKind {
+        self.kind
+    }
+}
+
+impl Display for DBCError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl Error for DBCError {}
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DBCWriteError {
+    UnknownAttribute,
+    AttributeObjectTypeMismatch,
+}
+
+impl Error for DBCWriteError {}
+
+pub fn parse(text: &str) -> Result<Database, DBCError> {
+    let tokens = tokenize(text)?;
+
+    // Running out of tokens is reported at the end of the text.
+    let last_line = text.rsplit('\n').next().unwrap_or_default();
+    let end = (
+        text.matches('\n').count() + 1,
+        last_line.chars().count() + 1,
+    );
+
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end,
+        database: Database::default(),
+    };
+
+    parser.parse()?;
+
+    Ok(parser.database)
+}
+
+pub fn write(database: &Database) -> Result<String, DBCWriteError> {
+    check_attribute_definitions(database)?;
+
+    let mut lines = vec![
+        format!("VERSION {}", quote(database.version())),
+        String::new(),
+        String::from("NS_ :"),
+    ];
+
+    lines.extend(NEW_SYMBOLS.iter().map(|symbol| format!("\t{symbol}")));
+    lines.push(String::new());
+    lines.push(String::from("BS_:"));
+    lines.push(String::new());
+
+    let nodes: Vec<&str> = database.nodes().iter().map(Node::name).collect();
+    lines.push(format!("BU_: {}", nodes.join(" ")).trim_end().to_string());
+    lines.push(String::new());
+
+    for (name, value_descriptions) in database.value_tables() {
+        lines.push(format!(
+            "VAL_TABLE_ {name}{} ;",
+            write_value_descriptions(value_descriptions)
+        ));
+    }
+
+    for message in database.messages() {
+        lines.push(String::new());
+        lines.push(format!(
+            "BO_ {} {}: {} {}",
+            write_identifier(message.identifier()),
+            message.name(),
+            message.size(),
+            message.transmitter().unwrap_or(NO_NODE)
+        ));
+
+        for signal in message.signals() {
+            lines.push(write_signal(signal));
+        }
+    }
+
+    lines.push(String::new());
+
+    if let Some(comment) = database.comment() {
+        lines.push(format!("CM_ {};", quote(comment)));
+    }
+
+    for node in database.nodes() {
+        if let Some(comment) = node.comment() {
+            lines.push(format!("CM_ BU_ {} {};", node.name(), quote(comment)));
+        }
+    }
+
+    for message in database.messages() {
+        let identifier = write_identifier(message.identifier());
+
+        if let Some(comment) = message.comment() {
+            lines.push(format!("CM_ BO_ {identifier} {};", quote(comment)));
+        }
+
+        for signal in message.signals() {
+            if let Some(comment) = signal.comment() {
+                lines.push(format!(
+                    "CM_ SG_ {identifier} {} {};",
+                    signal.name(),
+                    quote(comment)
+                ));
+            }
+        }
+    }
+
+    for attribute_definition in database.attribute_definitions() {
+        lines.push(write_attribute_definition(attribute_definition));
+    }
+
+    for attribute_definition in database.attribute_definitions() {
+        if let Some(default) = attribute_definition.default() {
+            lines.push(format!(
+                "BA_DEF_DEF_ {} {};",
+                quote(attribute_definition.name()),
+                write_attribute_value(default)
+            ));
+        }
+    }
+
+    for (name, value) in database.attributes() {
+        lines.push(format!(
+            "BA_ {} {};",
+            quote(name),
+            write_attribute_value(value)
+        ));
+    }
+
+    for node in database.nodes() {
+        for (name, value) in node.attributes() {
+            lines.push(format!(
+                "BA_ {} BU_ {} {};",
+                quote(name),
+                node.name(),
+                write_attribute_value(value)
+            ));
+        }
+    }
+
+    for message in database.messages() {
+        let identifier = write_identifier(message.identifier());
+
+        for (name, value) in message.attributes() {
+            lines.push(format!(
+                "BA_ {} BO_ {identifier} {};",
+                quote(name),
+                write_attribute_value(value)
+            ));
+        }
+
+        for signal in message.signals() {
+            for (name, value) in signal.attributes() {
+                lines.push(format!(
+                    "BA_ {} SG_ {identifier} {} {};",
+                    quote(name),
+                    signal.name(),
+                    write_attribute_value(value)
+                ));
+            }
+        }
+    }
+
+    for message in database.messages() {
+        let identifier = write_identifier(message.identifier());
+
+        for signal in message.signals() {
+            if !signal.value_descriptions().is_empty() {
+                lines.push(format!(
+                    "VAL_ {identifier} {}{} ;",
+                    signal.name(),
+                    write_value_descriptions(signal.value_descriptions())
+                ));
+            }
+        }
+    }
+
+    for message in database.messages() {
+        let identifier = write_identifier(message.identifier());
+
+        for signal in message.signals() {
+            let value_type = match signal.spec().value_type() {
+                ValueType::Float32 => 1,
+                ValueType::Float64 => 2,
+                ValueType::Unsigned | ValueType::Signed => continue,
+            };
+
+            lines.push(format!(
+                "SIG_VALTYPE_ {identifier} {} : {value_type};",
+                signal.name()
+            ));
+        }
+    }
+
+    for message in database.messages() {
+        let identifier = write_identifier(message.identifier());
+
+        for signal in message.signals() {
+            let Some(extended_multiplexing) = signal.extended_multiplexing() else {
+                continue;
+            };
+
+            let values: Vec<String> = extended_multiplexing
+                .values()
+                .iter()
+                .map(|values| format!("{}-{}", values.start(), values.end()))
+                .collect();
+
+            lines.push(format!(
+                "SG_MUL_VAL_ {identifier} {} {} {};",
+                signal.name(),
+                extended_multiplexing.multiplexor(),
+                values.join(", ")
+            ));
+        }
+    }
+
+    lines.push(String::new());
+
+    Ok(lines.join("\n"))
+}
+
+// A file with an attribute value that has no definition for its object type does not load.
+fn check_attribute_definitions(database: &Database) -> Result<(), DBCWriteError> {
+    let mut attributes: Vec<(&String, AttributeObjectType)> = database
+        .attributes()
+        .keys()
+        .map(|name| (name, AttributeObjectType::Network))
+        .collect();
+
+    for node in database.nodes() {
+        attributes.extend(node.attributes().keys().map(|name| (name, AttributeObjectType::Node)));
+    }
+
+    for message in database.messages() {
+        attributes.extend(
+            message
+                .attributes()
+                .keys()
+                .map(|name| (name, AttributeObjectType::Message)),
+        );
+
+        for signal in message.signals() {
+            attributes.extend(
+                signal
+                    .attributes()
+                    .keys()
+                    .map(|name| (name, AttributeObjectType::Signal)),
+            );
+        }
+    }
+
+    for (name, object_type) in attributes {
+        let attribute_definition = database
+            .attribute_definition(name)
+            .ok_or(DBCWriteError::UnknownAttribute)?;
+
+        if attribute_definition.object_type() != object_type {
+            return Err(DBCWriteError::AttributeObjectTypeMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+// The object a comment or an attribute value belongs to.
+enum ObjectReference {
+    Network,
+    Node(String, Token),
+    Message(CANDBID, Token),
+    Signal(CANDBID, Token, String, Token),
+    EnvironmentVariable,
+}
+
+impl ObjectReference {
+    fn object_type(&self) -> AttributeObjectType {
+        match self {
+            Self::Network => AttributeObjectType::Network,
+            Self::Node(..) => AttributeObjectType::Node,
+            Self::Message(..) => AttributeObjectType::Message,
+            Self::Signal(..) => AttributeObjectType::Signal,
+            Self::EnvironmentVariable => AttributeObjectType::EnvironmentVariable,
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: (usize, usize),
+    database: Database,
+}
+
+impl Parser {
+    fn parse(&mut self) -> Result<(), DBCError> {
+        while let Some(token) = self.next_token_if_any() {
+            let TokenKind::Identifier(keyword) = &token.kind else {
+                return Err(error_at(&token, DBCErrorKind::UnexpectedToken));
+            };
+
+            match keyword.as_str() {
+                "VERSION" => self.database.version = self.string()?,
+                "NS_" => self.new_symbols()?,
+                "BS_" => self.bit_timing(&token)?,
+                "BU_" => self.nodes()?,
+                "VAL_TABLE_" => self.value_table()?,
+                "BO_" => self.message()?,
+                "CM_" => self.comment()?,
+                "BA_DEF_" => self.attribute_definition()?,
+                "BA_DEF_DEF_" => self.attribute_default()?,
+                "BA_" => self.attribute()?,
+                "VAL_" => self.value_descriptions()?,
+                "SIG_VALTYPE_" => self.signal_value_type()?,
+                "SG_MUL_VAL_" => self.extended_multiplexing()?,
+                _ => self.skip_statement()?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn new_symbols(&mut self) -> Result<(), DBCError> {
+        self.punctuation(':')?;
+
+        // The new symbols are a list of keywords, which ends at the bit timing section.
+        while !self.peek_is_identifier("BS_") {
+            self.next_token()?;
+        }
+
+        Ok(())
+    }
+
+    fn bit_timing(&mut self, keyword_token: &Token) -> Result<(), DBCError> {
+        self.punctuation(':')?;
+
+        // The baudrate and register values are obsolete, and only ever on the same line.
+        while self
+            .peek()
+            .is_some_and(|token| token.line == keyword_token.line)
+        {
+            self.next_token()?;
+        }
+
+        Ok(())
+    }
+
+    fn nodes(&mut self) -> Result<(), DBCError> {
+        self.punctuation(':')?;
+
+        while let Some(Token {
+            kind: TokenKind::Identifier(name),
+            ..
+        }) = self.peek()
+        {
+            if KEYWORDS.contains(&name.as_str()) {
+                break;
+            }
+
+            let (name, name_token) = self.identifier()?;
+            self.database
+                .add_node(Node::new(name))
+                .map_err(|database_error| error_at(&name_token, database_error.into()))?;
+        }
+
+        Ok(())
+    }
+
+    fn value_table(&mut self) -> Result<(), DBCError> {
+        let (name, name_token) = self.identifier()?;
+        let value_descriptions = self.value_description_list()?;
+
+        self.database
+            .add_value_table(name, value_descriptions)
+            .map_err(|database_error| error_at(&name_token, database_error.into()))?;
+
+        Ok(())
+    }
+
+    fn message(&mut self) -> Result<(), DBCError> {
+        let (identifier, identifier_token) = self.message_identifier()?;
+        let (name, _) = self.identifier()?;
+        self.punctuation(':')?;
+        let size = self.unsigned()? as usize;
+        let (transmitter, _) = self.identifier()?;
+
+        let mut message = Message::new(identifier, name, size, node_name(transmitter));
+
+        while self.peek_is_identifier("SG_") {
+            let signal_token = self.next_token()?;
+            let signal = self.signal()?;
+
+            message
+                .add_signal(signal)
+                .map_err(|database_error| error_at(&signal_token, database_error.into()))?;
+        }
+
+        self.database
+            .add_message(message)
+            .map_err(|database_error| error_at(&identifier_token, database_error.into()))?;
+
+        Ok(())
+    }
+
+    fn signal(&mut self) -> Result<Signal, DBCError> {
+        let (name, name_token) = self.identifier()?;
+
+        let multiplexing = if self.peek_is_punctuation(':') {
+            Multiplexing::None
+        } else {
+            let (multiplexing, multiplexing_token) = self.identifier()?;
+            parse_multiplexing(&multiplexing).ok_or(error_at(
+                &multiplexing_token,
+                DBCErrorKind::InvalidMultiplexing,
+            ))?
+        };
+
+        self.punctuation(':')?;
+        let start_bit = self.unsigned()? as usize;
+        self.punctuation('|')?;
+        let length = self.unsigned()? as usize;
+        self.punctuation('@')?;
+
+        let byte_order_token = self.next_token()?;
+        let byte_order = match &byte_order_token.kind {
+            TokenKind::Number(number) if number == "0" => ByteOrder::BigEndian,
+            TokenKind::Number(number) if number == "1" => ByteOrder::LittleEndian,
+            _ => return Err(error_at(&byte_order_token, DBCErrorKind::InvalidByteOrder)),
+        };
+
+        let value_type_token = self.next_token()?;
+        let value_type = match &value_type_token.kind {
+            TokenKind::Punctuation('+') => ValueType::Unsigned,
+            TokenKind::Punctuation('-') => ValueType::Signed,
+            _ => return Err(error_at(&value_type_token, DBCErrorKind::InvalidValueType)),
+        };
+
+        self.punctuation('(')?;
+        let factor = self.float()?;
+        self.punctuation(',')?;
+        let offset = self.float()?;
+        self.punctuation(')')?;
+        self.punctuation('[')?;
+        let minimum = self.float()?;
+        self.punctuation('|')?;
+        let maximum = self.float()?;
+        self.punctuation(']')?;
+        let unit = self.string()?;
+
+        let mut receivers = Vec::new();
+        let (receiver, _) = self.identifier()?;
+        receivers.extend(node_name(receiver));
+        while self.peek_is_punctuation(',') {
+            self.next_token()?;
+            let (receiver, _) = self.identifier()?;
+            receivers.extend(node_name(receiver));
+        }
+
+        let spec = SignalSpec::new(
+            start_bit,
+            length,
+            byte_order,
+            value_type,
+            Scaling::new(factor, offset),
+            minimum..=maximum,
+        )
+        .map_err(|signal_error| error_at(&name_token, DBCErrorKind::InvalidSignal(signal_error)))?;
+
+        Ok(Signal::new(name, spec, unit, receivers, multiplexing))
+    }
+
+    fn comment(&mut self) -> Result<(), DBCError> {
+        let object = self.object_reference()?;
+        let comment = Some(self.string()?);
+        self.punctuation(';')?;
+
+        match object {
+            ObjectReference::Network => self.database.comment = comment,
+            ObjectReference::Node(name, name_token) => {
+                self.node_mut(&name, &name_token)?.comment = comment
+            }
+            ObjectReference::Message(identifier, identifier_token) => {
+                self.message_mut(&identifier, &identifier_token)?.comment = comment
+            }
+            ObjectReference::Signal(identifier, identifier_token, name, name_token) => {
+                self.signal_mut(&identifier, &identifier_token, &name, &name_token)?
+                    .comment = comment
+            }
+            // Environment variables are not modelled.
+            ObjectReference::EnvironmentVariable => {}
+        }
+
+        Ok(())
+    }
+
+    fn attribute_definition(&mut self) -> Result<(), DBCError> {
+        let object_type = if self.peek_is_identifier("BU_") {
+            AttributeObjectType::Node
+        } else if self.peek_is_identifier("BO_") {
+            AttributeObjectType::Message
+        } else if self.peek_is_identifier("SG_") {
+            AttributeObjectType::Signal
+        } else if self.peek_is_identifier("EV_") {
+            AttributeObjectType::EnvironmentVariable
+        } else {
+            AttributeObjectType::Network
+        };
+
+        if object_type != AttributeObjectType::Network {
+            self.next_token()?;
+        }
+
+        let name_token = self.peek_token()?;
+        let name = self.string()?;
+        let (value_type, value_type_token) = self.identifier()?;
+
+        let value_type = match value_type.as_str() {
+            "INT" => AttributeValueType::Integer {
+                minimum: self.integer()?,
+                maximum: self.integer()?,
+            },
+            "HEX" => AttributeValueType::Hex {
+                minimum: self.integer()?,
+                maximum: self.integer()?,
+            },
+            "FLOAT" => AttributeValueType::Float {
+                minimum: self.float()?,
+                maximum: self.float()?,
+            },
+            "STRING" => AttributeValueType::String,
+            "ENUM" => {
+                let mut values = Vec::new();
+                while !self.peek_is_punctuation(';') {
+                    if !values.is_empty() {
+                        self.punctuation(',')?;
+                    }
+                    values.push(self.string()?);
+                }
+                AttributeValueType::Enumeration { values }
+            }
+            _ => return Err(error_at(&value_type_token, DBCErrorKind::InvalidValueType)),
+        };
+
+        self.punctuation(';')?;
+
+        self.database
+            .add_attribute_definition(AttributeDefinition::new(
+                name,
+                object_type,
+                value_type,
+                None,
+            ))
+            .map_err(|database_error| error_at(&name_token, database_error.into()))?;
+
+        Ok(())
+    }
+
+    fn attribute_default(&mut self) -> Result<(), DBCError> {
+        let name_token = self.peek_token()?;
+        let name = self.string()?;
+
+        let value_type = self
+            .database
+            .attribute_definition(&name)
+            .map(|attribute_definition| attribute_definition.value_type.clone())
+            .ok_or(error_at(&name_token, DBCErrorKind::UnknownAttribute))?;
+
+        let value = self.attribute_value(&value_type)?;
+        self.punctuation(';')?;
+
+        if let Some(attribute_definition) = self
+            .database
+            .attribute_definitions
+            .iter_mut()
+            .find(|attribute_definition| attribute_definition.name == name)
+        {
+            attribute_definition.default = Some(value);
+        }
+
+        Ok(())
+    }
+
+    fn attribute(&mut self) -> Result<(), DBCError> {
+        let name_token = self.peek_token()?;
+        let name = self.string()?;
+
+        let (object_type, value_type) = self
+            .database
+            .attribute_definition(&name)
+            .map(|attribute_definition| {
+                (
+                    attribute_definition.object_type,
+                    attribute_definition.value_type.clone(),
+                )
+            })
+            .ok_or(error_at(&name_token, DBCErrorKind::UnknownAttribute))?;
+
+        let object_token = self.peek_token()?;
+        let object = self.object_reference()?;
+
+        if object.object_type() != object_type {
+            return Err(error_at(
+                &object_token,
+                DBCErrorKind::AttributeObjectTypeMismatch,
+            ));
+        }
+
+        let value = self.attribute_value(&value_type)?;
+        self.punctuation(';')?;
+
+        let attributes = match object {
+            ObjectReference::Network => &mut self.database.attributes,
+            ObjectReference::Node(name, name_token) => {
+                &mut self.node_mut(&name, &name_token)?.attributes
+            }
+            ObjectReference::Message(identifier, identifier_token) => {
+                &mut self.message_mut(&identifier, &identifier_token)?.attributes
+            }
+            ObjectReference::Signal(identifier, identifier_token, name, name_token) => {
+                &mut self
+                    .signal_mut(&identifier, &identifier_token, &name, &name_token)?
+                    .attributes
+            }
+            ObjectReference::EnvironmentVariable => return Ok(()),
+        };
+
+        attributes.insert(name, value);
+
+        Ok(())
+    }
+
+    fn value_descriptions(&mut self) -> Result<(), DBCError> {
+        // Value descriptions for environment variables start with a name instead.
+        if matches!(self.peek_token()?.kind, TokenKind::Identifier(_)) {
+            return self.skip_statement();
+        }
+
+        let (identifier, identifier_token) = self.message_identifier()?;
+        let (signal_name, signal_token) = self.identifier()?;
+        let value_descriptions = self.value_description_list()?;
+
+        let signal =
+            self.signal_mut(&identifier, &identifier_token, &signal_name, &signal_token)?;
+
+        signal.value_descriptions = value_descriptions;
+
+        Ok(())
+    }
+
+    fn signal_value_type(&mut self) -> Result<(), DBCError> {
+        let (identifier, identifier_token) = self.message_identifier()?;
+        let (signal_name, signal_token) = self.identifier()?;
+        self.punctuation(':')?;
+        let value_type_token = self.peek_token()?;
+        let value_type = match self.unsigned()? {
+            0 => None,
+            1 => Some(ValueType::Float32),
+            2 => Some(ValueType::Float64),
+            _ => return Err(error_at(&value_type_token, DBCErrorKind::InvalidValueType)),
+        };
+        self.punctuation(';')?;
+
+        let signal =
+            self.signal_mut(&identifier, &identifier_token, &signal_name, &signal_token)?;
+
+        if let Some(value_type) = value_type {
+            let spec = signal.spec;
+            signal.spec = SignalSpec::new(
+                spec.start_bit(),
+                spec.length(),
+                spec.byte_order(),
+                value_type,
+                spec.scaling(),
+                spec.minimum()..=spec.maximum(),
+            )
+            .map_err(|signal_error| {
+                error_at(&value_type_token, DBCErrorKind::InvalidSignal(signal_error))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn value_description_list(&mut self) -> Result<ValueDescriptions, DBCError> {
+        let mut value_descriptions = ValueDescriptions::new();
+
+        while !self.peek_is_punctuation(';') {
+            let value = self.integer()?;
+            let description = self.string()?;
+            value_descriptions.insert(value, description);
+        }
+
+        self.punctuation(';')?;
+
+        Ok(value_descriptions)
+    }
+
+    fn attribute_value(
+        &mut self,
+        value_type: &AttributeValueType,
+    ) -> Result<AttributeValue, DBCError> {
+        let token = self.next_token()?;
+
+        match (&token.kind, value_type) {
+            (TokenKind::String(string), AttributeValueType::String)
+            | (TokenKind::String(string), AttributeValueType::Enumeration { .. }) => {
+                Ok(AttributeValue::String(string.clone()))
+            }
+            (TokenKind::Number(number), AttributeValueType::Float { .. }) => number
+                .parse()
+                .map(AttributeValue::Float)
+                .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber)),
+            (TokenKind::Number(number), AttributeValueType::Integer { .. })
+            | (TokenKind::Number(number), AttributeValueType::Hex { .. })
+            | (TokenKind::Number(number), AttributeValueType::Enumeration { .. }) => number
+                .parse()
+                .map(AttributeValue::Integer)
+                .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber)),
+            _ => Err(error_at(&token, DBCErrorKind::InvalidAttributeValue)),
+        }
+    }
+
+    fn object_reference(&mut self) -> Result<ObjectReference, DBCError> {
+        let object = if self.peek_is_identifier("BU_") {
+            self.next_token()?;
+            let (name, name_token) = self.identifier()?;
+            ObjectReference::Node(name, name_token)
+        } else if self.peek_is_identifier("BO_") {
+            self.next_token()?;
+            let (identifier, identifier_token) = self.message_identifier()?;
+            ObjectReference::Message(identifier, identifier_token)
+        } else if self.peek_is_identifier("SG_") {
+            self.next_token()?;
+            let (identifier, identifier_token) = self.message_identifier()?;
+            let (name, name_token) = self.identifier()?;
+            ObjectReference::Signal(identifier, identifier_token, name, name_token)
+        } else if self.peek_is_identifier("EV_") {
+            self.next_token()?;
+            self.identifier()?;
+            ObjectReference::EnvironmentVariable
+        } else {
+            ObjectReference::Network
+        };
+
+        Ok(object)
+    }
+
+    fn node_mut(&mut self, name: &str, name_token: &Token) -> Result<&mut Node, DBCError> {
+        self.database
+            .nodes
+            .iter_mut()
+            .find(|node| node.name == name)
+            .ok_or(error_at(name_token, DBCErrorKind::UnknownNode))
+    }
+
+    fn message_mut(
+        &mut self,
+        identifier: &CANDBID,
+        identifier_token: &Token,
+    ) -> Result<&mut Message, DBCError> {
+        self.database
+            .messages
+            .get_mut(identifier)
+            .ok_or(error_at(identifier_token, DBCErrorKind::UnknownMessage))
+    }
+
+    fn extended_multiplexing(&mut self) -> Result<(), DBCError> {
+        let (identifier, identifier_token) = self.message_identifier()?;
+        let (signal_name, signal_token) = self.identifier()?;
+        let (multiplexor_name, multiplexor_token) = self.identifier()?;
+
+        let mut values = vec![self.multiplexor_values()?];
+        while self.peek_is_punctuation(',') {
+            self.next_token()?;
+            values.push(self.multiplexor_values()?);
+        }
+        self.punctuation(';')?;
+
+        let multiplexor = self
+            .message_mut(&identifier, &identifier_token)?
+            .signal(&multiplexor_name)
+            .ok_or(error_at(&multiplexor_token, DBCErrorKind::UnknownSignal))?;
+
+        if !matches!(
+            multiplexor.multiplexing,
+            Multiplexing::Multiplexor | Multiplexing::MultiplexedMultiplexor(_)
+        ) {
+            return Err(error_at(
+                &multiplexor_token,
+                DBCErrorKind::InvalidMultiplexing,
+            ));
+        }
+
+        let signal =
+            self.signal_mut(&identifier, &identifier_token, &signal_name, &signal_token)?;
+
+        if !matches!(
+            signal.multiplexing,
+            Multiplexing::Multiplexed(_) | Multiplexing::MultiplexedMultiplexor(_)
+        ) {
+            return Err(error_at(&signal_token, DBCErrorKind::InvalidMultiplexing));
+        }
+
+        signal.extended_multiplexing = Some(ExtendedMultiplexing::new(multiplexor_name, values));
+
+        Ok(())
+    }
+
+    // Ranges are written as 3-5, which the lexer reads as the numbers 3 and -5.
+    fn multiplexor_values(&mut self) -> Result<RangeInclusive<u64>, DBCError> {
+        let minimum = self.unsigned()?;
+
+        let separated = self.peek_is_punctuation('-');
+        if separated {
+            self.next_token()?;
+        }
+
+        let (number, token) = self.number()?;
+        let maximum = match number.strip_prefix('-') {
+            Some(maximum) if !separated => maximum,
+            None if separated => &number,
+            _ => return Err(error_at(&token, DBCErrorKind::UnexpectedToken)),
+        };
+
+        let maximum = maximum
+            .parse()
+            .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber))?;
+
+        Ok(minimum..=maximum)
+    }
+
+    fn signal_mut(
+        &mut self,
+        identifier: &CANDBID,
+        identifier_token: &Token,
+        name: &str,
+        name_token: &Token,
+    ) -> Result<&mut Signal, DBCError> {
+        self.message_mut(identifier, identifier_token)?
+            .signals
+            .iter_mut()
+            .find(|signal| signal.name == name)
+            .ok_or(error_at(name_token, DBCErrorKind::UnknownSignal))
+    }
+
+    fn message_identifier(&mut self) -> Result<(CANDBID, Token), DBCError> {
+        let identifier_token = self.peek_token()?;
+        let identifier = u32::try_from(self.unsigned()?)
+            .map_err(|_| error_at(&identifier_token, DBCErrorKind::IdentifierOutOfRange))?;
+
+        let can_db_id = if identifier & EXTENDED_IDENTIFIER_FLAG != 0 {
+            CANDBID::Extended(identifier)
+        } else if identifier <= MAXIMUM_STANDARD_IDENTIFIER {
+            CANDBID::Standard(u11::new(identifier as u16))
+        } else {
+            return Err(error_at(
+                &identifier_token,
+                DBCErrorKind::IdentifierOutOfRange,
+            ));
+        };
+
+        Ok((can_db_id, identifier_token))
+    }
+
+    fn skip_statement(&mut self) -> Result<(), DBCError> {
+        while self.next_token()?.kind != TokenKind::Punctuation(';') {}
+
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_token(&self) -> Result<Token, DBCError> {
+        self.peek().cloned().ok_or(self.end_of_file())
+    }
+
+    fn peek_is_identifier(&self, identifier: &str) -> bool {
+        self.peek()
+            .is_some_and(|token| token.kind == TokenKind::Identifier(identifier.to_string()))
+    }
+
+    fn peek_is_punctuation(&self, punctuation: char) -> bool {
+        self.peek()
+            .is_some_and(|token| token.kind == TokenKind::Punctuation(punctuation))
+    }
+
+    fn next_token_if_any(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned()?;
+        self.index += 1;
+        Some(token)
+    }
+
+    fn next_token(&mut self) -> Result<Token, DBCError> {
+        self.next_token_if_any().ok_or(self.end_of_file())
+    }
+
+    fn end_of_file(&self) -> DBCError {
+        DBCError::new(self.end.0, self.end.1, DBCErrorKind::UnexpectedEndOfFile)
+    }
+
+    fn punctuation(&mut self, punctuation: char) -> Result<(), DBCError> {
+        let token = self.next_token()?;
+
+        match token.kind {
+            TokenKind::Punctuation(character) if character == punctuation => Ok(()),
+            _ => Err(error_at(&token, DBCErrorKind::UnexpectedToken)),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<(String, Token), DBCError> {
+        let token = self.next_token()?;
+
+        match &token.kind {
+            TokenKind::Identifier(identifier) => Ok((identifier.clone(), token)),
+            _ => Err(error_at(&token, DBCErrorKind::UnexpectedToken)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, DBCError> {
+        let token = self.next_token()?;
+
+        match token.kind {
+            TokenKind::String(string) => Ok(string),
+            _ => Err(error_at(&token, DBCErrorKind::UnexpectedToken)),
+        }
+    }
+
+    fn number(&mut self) -> Result<(String, Token), DBCError> {
+        let token = self.next_token()?;
+
+        match &token.kind {
+            TokenKind::Number(number) => Ok((number.clone(), token)),
+            _ => Err(error_at(&token, DBCErrorKind::UnexpectedToken)),
+        }
+    }
+
+    fn unsigned(&mut self) -> Result<u64, DBCError> {
+        let (number, token) = self.number()?;
+        number
+            .parse()
+            .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber))
+    }
+
+    fn integer(&mut self) -> Result<i64, DBCError> {
+        let (number, token) = self.number()?;
+        number
+            .parse()
+            .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber))
+    }
+
+    fn float(&mut self) -> Result<f64, DBCError> {
+        let (number, token) = self.number()?;
+        number
+            .parse()
+            .map_err(|_| error_at(&token, DBCErrorKind::InvalidNumber))
+    }
+}
+
+fn error_at(token: &Token, kind: DBCErrorKind) -> DBCError {
+    DBCError::new(token.line, token.column, kind)
+}
+
+fn node_name(name: String) -> Option<String> {
+    (name != NO_NODE).then_some(name)
+}
+
+fn write_identifier(identifier: CANDBID) -> u32 {
+    match identifier {
+        CANDBID::Standard(identifier) => u32::from(identifier),
+        CANDBID::Extended(identifier) => identifier,
+    }
+}
+
+fn write_signal(signal: &Signal) -> String {
+    let spec = signal.spec();
+
+    let multiplexing = match signal.multiplexing() {
+        Multiplexing::None => String::new(),
+        Multiplexing::Multiplexor => String::from(" M"),
+        Multiplexing::Multiplexed(value) => format!(" m{value}"),
+        Multiplexing::MultiplexedMultiplexor(value) => format!(" m{value}M"),
+    };
+
+    let byte_order = match spec.byte_order() {
+        ByteOrder::LittleEndian => 1,
+        ByteOrder::BigEndian => 0,
+    };
+
+    // Floating point signals are marked as signed, their type is given by SIG_VALTYPE_.
+    let value_type = match spec.value_type() {
+        ValueType::Unsigned => '+',
+        ValueType::Signed | ValueType::Float32 | ValueType::Float64 => '-',
+    };
+
+    let receivers = if signal.receivers().is_empty() {
+        String::from(NO_NODE)
+    } else {
+        signal.receivers().join(",")
+    };
+
+    format!(
+        " SG_ {}{multiplexing} : {}|{}@{byte_order}{value_type} ({},{}) [{}|{}] {} {receivers}",
+        signal.name(),
+        spec.start_bit(),
+        spec.length(),
+        spec.factor(),
+        spec.offset(),
+        spec.minimum(),
+        spec.maximum(),
+        quote(signal.unit())
+    )
+}
+
+fn write_attribute_definition(attribute_definition: &AttributeDefinition) -> String {
+    let object_type = match attribute_definition.object_type() {
+        AttributeObjectType::Network => "",
+        AttributeObjectType::Node => "BU_ ",
+        AttributeObjectType::Message => "BO_ ",
+        AttributeObjectType::Signal => "SG_ ",
+        AttributeObjectType::EnvironmentVariable => "EV_ ",
+    };
+
+    let value_type = match attribute_definition.value_type() {
+        AttributeValueType::Integer { minimum, maximum } => format!("INT {minimum} {maximum}"),
+        AttributeValueType::Hex { minimum, maximum } => format!("HEX {minimum} {maximum}"),
+        AttributeValueType::Float { minimum, maximum } => format!("FLOAT {minimum} {maximum}"),
+        AttributeValueType::String => String::from("STRING"),
+        AttributeValueType::Enumeration { values } => {
+            let values: Vec<String> = values.iter().map(|value| quote(value)).collect();
+            format!("ENUM {}", values.join(","))
+        }
+    };
+
+    format!(
+        "BA_DEF_ {object_type}{} {value_type};",
+        quote(attribute_definition.name())
+    )
+}
+
+fn write_attribute_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::Integer(value) => value.to_string(),
+        AttributeValue::Float(value) => value.to_string(),
+        AttributeValue::String(value) => quote(value),
+    }
+}
+
+fn write_value_descriptions(value_descriptions: &ValueDescriptions) -> String {
+    value_descriptions
+        .iter()
+        .map(|(value, description)| format!(" {value} {}", quote(description)))
+        .collect()
+}
+
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_multiplexing(multiplexing: &str) -> Option<Multiplexing> {
+    if multiplexing == "M" {
+        return Some(Multiplexing::Multiplexor);
+    }
+
+    let value = multiplexing.strip_prefix('m')?;
+
+    match value.strip_suffix('M') {
+        Some(value) => value.parse().ok().map(Multiplexing::MultiplexedMultiplexor),
+        None => value.parse().ok().map(Multiplexing::Multiplexed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_err_eq, assert_ok, assert_some, assert_some_eq};
+    use proptest::{collection, option, prelude::*};
+
+    use super::*;
+
+    const DBC: &str = r#"VERSION "1.0"
+
+NS_ :
+    NS_DESC_
+    CM_
+    BA_DEF_
+    BA_
+    VAL_
+    SIG_VALTYPE_
+
+BS_:
+
+BU_: Engine Dashboard Gateway
+
+VAL_TABLE_ GearTable 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+
+BO_ 256 EngineStatus: 8 Engine
+ SG_ Speed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dashboard,Gateway
+ SG_ Temperature : 23|8@0- (1,-40) [-40|87] "degC" Dashboard
+ SG_ Gear : 24|2@1+ (1,0) [0|3] "" Vector__XXX
+
+BO_ 2147484160 Diagnostics: 8 Vector__XXX
+ SG_ Mode M : 0|8@1+ (1,0) [0|0] "" Gateway
+ SG_ Voltage m0 : 8|32@1+ (1,0) [0|0] "V" Gateway
+ SG_ Current m1 : 8|16@1- (0.1,0) [-100|100] "A" Gateway
+
+BO_TX_BU_ 256 : Engine,Gateway;
+
+CM_ "Example network";
+CM_ BU_ Engine "Engine control unit";
+CM_ BO_ 256 "Engine state";
+CM_ SG_ 256 Speed "Crankshaft speed";
+
+BA_DEF_ "BusType" STRING ;
+BA_DEF_ BU_ "NodeLayerModules" STRING ;
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+BA_DEF_ BO_ "GenMsgSendType" ENUM "Cyclic","Event";
+BA_DEF_ SG_ "GenSigStartValue" FLOAT -3.4E+038 3.4E+038;
+BA_DEF_DEF_ "BusType" "CAN";
+BA_DEF_DEF_ "GenMsgCycleTime" 100;
+BA_ "BusType" "CAN FD";
+BA_ "NodeLayerModules" BU_ Engine "CANoeILNLVector.dll";
+BA_ "GenMsgCycleTime" BO_ 256 10;
+BA_ "GenMsgSendType" BO_ 256 1;
+BA_ "GenSigStartValue" SG_ 256 Speed 800;
+
+VAL_ 256 Gear 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+SIG_VALTYPE_ 2147484160 Voltage : 1;
+"#;
+
+    #[test]
+    fn test_parse() {
+        let database = assert_ok!(parse(DBC));
+
+        assert_eq!(database.version(), "1.0");
+        assert_eq!(database.comment(), Some("Example network"));
+
+        let node_names: Vec<&str> = database.nodes().iter().map(Node::name).collect();
+        assert_eq!(node_names, vec!["Engine", "Dashboard", "Gateway"]);
+
+        let engine = assert_some!(database.node("Engine"));
+        assert_eq!(engine.comment(), Some("Engine control unit"));
+        assert_some_eq!(
+            engine.attributes().get("NodeLayerModules"),
+            &AttributeValue::String(String::from("CANoeILNLVector.dll"))
+        );
+
+        let gear_table = assert_some!(database.value_tables().get("GearTable"));
+        assert_some_eq!(gear_table.get(&3), "Drive");
+
+        assert_eq!(database.messages().count(), 2);
+
+        let engine_status = assert_some!(database.message(&CANDBID::Standard(u11::new(256))));
+        assert_eq!(engine_status.name(), "EngineStatus");
+        assert_eq!(engine_status.size(), 8);
+        assert_eq!(engine_status.transmitter(), Some("Engine"));
+        assert_eq!(engine_status.comment(), Some("Engine state"));
+        assert_some_eq!(
+            engine_status.attributes().get("GenMsgCycleTime"),
+            &AttributeValue::Integer(10)
+        );
+        assert_some_eq!(
+            engine_status.attributes().get("GenMsgSendType"),
+            &AttributeValue::Integer(1)
+        );
+
+        let speed = assert_some!(engine_status.signal("Speed"));
+        assert_eq!(speed.spec().start_bit(), 0);
+        assert_eq!(speed.spec().length(), 16);
+        assert_eq!(speed.spec().byte_order(), ByteOrder::LittleEndian);
+        assert_eq!(speed.spec().value_type(), ValueType::Unsigned);
+        assert_eq!(speed.spec().factor(), 0.25);
+        assert_eq!(speed.spec().maximum(), 16383.75);
+        assert_eq!(speed.unit(), "rpm");
+        assert_eq!(speed.receivers(), ["Dashboard", "Gateway"]);
+        assert_eq!(speed.multiplexing(), Multiplexing::None);
+        assert_eq!(speed.comment(), Some("Crankshaft speed"));
+        assert_some_eq!(
+            speed.attributes().get("GenSigStartValue"),
+            &AttributeValue::Float(800.0)
+        );
+
+        let temperature = assert_some!(engine_status.signal("Temperature"));
+        assert_eq!(temperature.spec().byte_order(), ByteOrder::BigEndian);
+        assert_eq!(temperature.spec().value_type(), ValueType::Signed);
+        assert_eq!(temperature.spec().offset(), -40.0);
+
+        let gear = assert_some!(engine_status.signal("Gear"));
+        assert!(gear.receivers().is_empty());
+        assert_some_eq!(gear.value_descriptions().get(&1), "Reverse");
+
+        let diagnostics = assert_some!(database.message_by_name("Diagnostics"));
+        assert_eq!(diagnostics.identifier(), CANDBID::Extended(0x80000200));
+        assert_eq!(diagnostics.transmitter(), None);
+        assert_eq!(assert_some!(diagnostics.multiplexor()).name(), "Mode");
+
+        let voltage = assert_some!(diagnostics.signal("Voltage"));
+        assert_eq!(voltage.multiplexing(), Multiplexing::Multiplexed(0));
+        assert_eq!(voltage.spec().value_type(), ValueType::Float32);
+
+        let current = assert_some!(diagnostics.signal("Current"));
+        assert_eq!(current.multiplexing(), Multiplexing::Multiplexed(1));
+
+        assert_some_eq!(
+            database.attributes().get("BusType"),
+            &AttributeValue::String(String::from("CAN FD"))
+        );
+
+        let cycle_time = assert_some!(database.attribute_definition("GenMsgCycleTime"));
+        assert_eq!(cycle_time.object_type(), AttributeObjectType::Message);
+        assert_eq!(
+            cycle_time.value_type(),
+            &AttributeValueType::Integer {
+                minimum: 0,
+                maximum: 65535
+            }
+        );
+        assert_eq!(cycle_time.default(), Some(&AttributeValue::Integer(100)));
+
+        let send_type = assert_some!(database.attribute_definition("GenMsgSendType"));
+        assert_eq!(
+            send_type.value_type(),
+            &AttributeValueType::Enumeration {
+                values: vec![String::from("Cyclic"), String::from("Event")]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_multiplexing() {
+        assert_eq!(parse_multiplexing("M"), Some(Multiplexing::Multiplexor));
+        assert_eq!(parse_multiplexing("m3"), Some(Multiplexing::Multiplexed(3)));
+        assert_eq!(
+            parse_multiplexing("m12M"),
+            Some(Multiplexing::MultiplexedMultiplexor(12))
+        );
+        assert_eq!(parse_multiplexing("x"), None);
+        assert_eq!(parse_multiplexing("m"), None);
+    }
+
+    #[test]
+    fn test_parse_extended_multiplexing() {
+        let dbc = r#"BO_ 1024 Diagnostics: 4 Tester
+ SG_ Service M : 0|8@1+ (1,0) [0|0] "" Engine
+ SG_ Session m1M : 8|8@1+ (1,0) [0|0] "" Engine
+ SG_ Voltage m2 : 16|16@1+ (0.01,0) [0|0] "V" Engine
+
+SG_MUL_VAL_ 1024 Session Service 1-1;
+SG_MUL_VAL_ 1024 Voltage Session 2-3, 6 - 7;
+"#;
+        let database = assert_ok!(parse(dbc));
+        let message = assert_some!(database.message_by_name("Diagnostics"));
+
+        assert_eq!(
+            assert_some!(message.signal("Session")).extended_multiplexing(),
+            Some(&ExtendedMultiplexing::new(
+                String::from("Service"),
+                vec![1..=1]
+            ))
+        );
+        assert_eq!(
+            assert_some!(message.signal("Voltage")).extended_multiplexing(),
+            Some(&ExtendedMultiplexing::new(
+                String::from("Session"),
+                vec![2..=3, 6..=7]
+            ))
+        );
+        assert_eq!(assert_some!(message.signal("Service")).extended_multiplexing(), None);
+
+        assert_eq!(assert_ok!(parse(&assert_ok!(write(&database)))), database);
+
+        let dbc = r#"BO_ 1 A: 2 X
+ SG_ M1 M : 0|8@1+ (1,0) [0|0] "" X
+ SG_ B m1 : 8|8@1+ (1,0) [0|0] "" X"#;
+        assert_eq!(
+            parse(&format!("{dbc}\nSG_MUL_VAL_ 1 B M2 1-1;")),
+            Err(DBCError::new(4, 17, DBCErrorKind::UnknownSignal))
+        );
+        assert_eq!(
+            parse(&format!("{dbc}\nSG_MUL_VAL_ 1 M1 M1 1-1;")),
+            Err(DBCError::new(4, 15, DBCErrorKind::InvalidMultiplexing))
+        );
+        assert_eq!(
+            parse(&format!("{dbc}\nSG_MUL_VAL_ 1 B M1 1 2;")),
+            Err(DBCError::new(4, 22, DBCErrorKind::UnexpectedToken))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = assert_err!(parse("BO_ 2048 Message: 8 Vector__XXX"));
+        assert_eq!(
+            error,
+            DBCError::new(1, 5, DBCErrorKind::IdentifierOutOfRange)
+        );
+        assert_eq!(
+            error.to_string(),
+            "IdentifierOutOfRange at line 1, column 5"
+        );
+
+        assert_eq!(
+            parse("BO_ 1 Message: 8 Vector__XXX\n SG_ Signal : 0|8@2+ (1,0) [0|0] \"\" X"),
+            Err(DBCError::new(2, 19, DBCErrorKind::InvalidByteOrder))
+        );
+        assert_eq!(
+            parse("BO_ 1 Message: 8 Vector__XXX\n SG_ Signal : 0|0@1+ (1,0) [0|0] \"\" X"),
+            Err(DBCError::new(
+                2,
+                6,
+                DBCErrorKind::InvalidSignal(SignalError::LengthMustBeNonZero)
+            ))
+        );
+        assert_eq!(
+            parse("BO_ 1 A: 8 X\nBO_ 1 B: 8 X"),
+            Err(DBCError::new(2, 5, DBCErrorKind::DuplicateMessage))
+        );
+        assert_eq!(
+            parse("BU_: A A"),
+            Err(DBCError::new(1, 8, DBCErrorKind::DuplicateNode))
+        );
+        assert_eq!(
+            parse("VAL_TABLE_ A 0 \"Off\" ;\nVAL_TABLE_ A 1 \"On\" ;"),
+            Err(DBCError::new(2, 12, DBCErrorKind::DuplicateValueTable))
+        );
+        assert_eq!(
+            parse("BA_DEF_ BO_ \"A\" INT 0 1;\nBA_DEF_ SG_ \"A\" INT 0 1;"),
+            Err(DBCError::new(2, 13, DBCErrorKind::DuplicateAttributeDefinition))
+        );
+        assert_eq!(
+            parse("CM_ BO_ 1 \"Missing\";"),
+            Err(DBCError::new(1, 9, DBCErrorKind::UnknownMessage))
+        );
+        assert_eq!(
+            parse("BA_ \"Missing\" 1;"),
+            Err(DBCError::new(1, 5, DBCErrorKind::UnknownAttribute))
+        );
+        assert_eq!(
+            parse("BA_DEF_ BO_ \"A\" INT 0 1;\nBA_ \"A\" 1;"),
+            Err(DBCError::new(
+                2,
+                9,
+                DBCErrorKind::AttributeObjectTypeMismatch
+            ))
+        );
+        assert_eq!(
+            parse("BO_ 1 Message: 8"),
+            Err(DBCError::new(1, 17, DBCErrorKind::UnexpectedEndOfFile))
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let mut database = Database::new(String::from("2.0"));
+        assert_ok!(database.add_node(Node::new(String::from("Engine"))));
+        assert_ok!(database.add_node(Node::new(String::from("Dashboard"))));
+        database.set_comment(Some(String::from("Say \"hi\"")));
+
+        let mut extended = Message::new(CANDBID::Extended(0x80000001), String::from("B"), 8, None);
+        let spec = assert_ok!(SignalSpec::new(
+            0,
+            32,
+            ByteOrder::LittleEndian,
+            ValueType::Float32,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+        assert_ok!(extended.add_signal(Signal::new(
+            String::from("Value"),
+            spec,
+            String::new(),
+            Vec::new(),
+            Multiplexing::None
+        )));
+        assert_ok!(database.add_message(extended));
+
+        let mut standard = Message::new(
+            CANDBID::Standard(u11::new(0x100)),
+            String::from("A"),
+            2,
+            Some(String::from("Engine")),
+        );
+        let spec = assert_ok!(SignalSpec::new(
+            7,
+            16,
+            ByteOrder::BigEndian,
+            ValueType::Signed,
+            Scaling::new(0.5, -40.0),
+            -100.0..=100.0
+        ));
+        let mut temperature = Signal::new(
+            String::from("Temperature"),
+            spec,
+            String::from("degC"),
+            vec![String::from("Dashboard")],
+            Multiplexing::None,
+        );
+        temperature.set_value_descriptions(ValueDescriptions::from([(-1, String::from("Error"))]));
+        assert_ok!(standard.add_signal(temperature));
+        assert_ok!(database.add_message(standard));
+
+        assert_ok!(database.add_attribute_definition(AttributeDefinition::new(
+            String::from("GenMsgCycleTime"),
+            AttributeObjectType::Message,
+            AttributeValueType::Integer {
+                minimum: 0,
+                maximum: 1000
+            },
+            Some(AttributeValue::Integer(100)),
+        )));
+
+        assert_eq!(
+            assert_ok!(write(&database)),
+            ["VERSION \"2.0\"", "", "NS_ :",]
+                .into_iter()
+                .map(String::from)
+                .chain(NEW_SYMBOLS.iter().map(|symbol| format!("\t{symbol}")))
+                .chain(
+                    [
+                        "",
+                        "BS_:",
+                        "",
+                        "BU_: Engine Dashboard",
+                        "",
+                        "",
+                        "BO_ 256 A: 2 Engine",
+                        " SG_ Temperature : 7|16@0- (0.5,-40) [-100|100] \"degC\" Dashboard",
+                        "",
+                        "BO_ 2147483649 B: 8 Vector__XXX",
+                        " SG_ Value : 0|32@1- (1,0) [0|0] \"\" Vector__XXX",
+                        "",
+                        "CM_ \"Say \\\"hi\\\"\";",
+                        "BA_DEF_ BO_ \"GenMsgCycleTime\" INT 0 1000;",
+                        "BA_DEF_DEF_ \"GenMsgCycleTime\" 100;",
+                        "VAL_ 256 Temperature -1 \"Error\" ;",
+                        "SIG_VALTYPE_ 2147483649 Value : 1;",
+                        "",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                )
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_write_parse() {
+        let database = assert_ok!(parse(DBC));
+
+        assert_eq!(assert_ok!(parse(&assert_ok!(write(&database)))), database);
+    }
+
+    #[test]
+    fn test_write_errors() {
+        let mut database = Database::new(String::new());
+        let mut message = Message::new(CANDBID::Standard(u11::new(1)), String::from("A"), 8, None);
+        message.set_attribute(String::from("GenMsgCycleTime"), AttributeValue::Integer(100));
+        assert_ok!(database.add_message(message));
+
+        assert_err_eq!(write(&database), DBCWriteError::UnknownAttribute);
+
+        assert_ok!(database.add_attribute_definition(AttributeDefinition::new(
+            String::from("GenMsgCycleTime"),
+            AttributeObjectType::Signal,
+            AttributeValueType::Integer {
+                minimum: 0,
+                maximum: 1000
+            },
+            None,
+        )));
+
+        assert_err_eq!(write(&database), DBCWriteError::AttributeObjectTypeMismatch);
+    }
+
+    fn name() -> impl Strategy<Value = String> {
+        "[A-Z][a-z0-9_]{0,7}"
+    }
+
+    fn text() -> impl Strategy<Value = String> {
+        "[ -~\n]{0,12}"
+    }
+
+    fn number() -> impl Strategy<Value = f64> {
+        -1e9..1e9
+    }
+
+    fn identifier() -> impl Strategy<Value = CANDBID> {
+        prop_oneof![
+            (0..=0x7FFu16).prop_map(|identifier| CANDBID::Standard(u11::new(identifier))),
+            (0..(1u32 << 29))
+                .prop_map(|identifier| CANDBID::Extended(identifier | EXTENDED_IDENTIFIER_FLAG)),
+        ]
+    }
+
+    fn multiplexing() -> impl Strategy<Value = Multiplexing> {
+        prop_oneof![
+            Just(Multiplexing::None),
+            Just(Multiplexing::Multiplexor),
+            any::<u16>().prop_map(|value| Multiplexing::Multiplexed(u64::from(value))),
+            any::<u16>().prop_map(|value| Multiplexing::MultiplexedMultiplexor(u64::from(value))),
+        ]
+    }
+
+    fn spec() -> impl Strategy<Value = SignalSpec> {
+        let value_type = prop_oneof![
+            (Just(ValueType::Unsigned), 1..=64usize),
+            (Just(ValueType::Signed), 1..=64usize),
+            Just((ValueType::Float32, 32)),
+            Just((ValueType::Float64, 64)),
+        ];
+        let byte_order = prop_oneof![Just(ByteOrder::LittleEndian), Just(ByteOrder::BigEndian)];
+        let factor = number().prop_filter("factor must be non-zero", |factor| *factor != 0.0);
+
+        (
+            0..64usize,
+            value_type,
+            byte_order,
+            factor,
+            number(),
+            number(),
+            number(),
+        )
+            .prop_map(
+                |(
+                    start_bit,
+                    (value_type, length),
+                    byte_order,
+                    factor,
+                    offset,
+                    minimum,
+                    maximum,
+                )| {
+                    SignalSpec::new(
+                        start_bit,
+                        length,
+                        byte_order,
+                        value_type,
+                        Scaling::new(factor, offset),
+                        minimum..=maximum,
+                    )
+                    .unwrap()
+                },
+            )
+    }
+
+    fn signal(name: String) -> impl Strategy<Value = Signal> {
+        (
+            spec(),
+            text(),
+            collection::vec(self::name(), 0..3),
+            multiplexing(),
+            collection::btree_map(any::<i64>(), text(), 0..3),
+            option::of(text()),
+            option::of(number()),
+        )
+            .prop_map(
+                move |(spec, unit, receivers, multiplexing, value_descriptions, comment, start)| {
+                    let mut signal = Signal::new(name.clone(), spec, unit, receivers, multiplexing);
+                    signal.set_value_descriptions(value_descriptions);
+                    signal.set_comment(comment);
+                    if let Some(start) = start {
+                        signal.set_attribute(
+                            String::from("GenSigStartValue"),
+                            AttributeValue::Float(start),
+                        );
+                    }
+                    signal
+                },
+            )
+    }
+
+    fn message(identifier: CANDBID) -> impl Strategy<Value = Message> {
+        let signals = collection::btree_set(name(), 0..4)
+            .prop_flat_map(|names| names.into_iter().map(signal).collect::<Vec<_>>());
+
+        (
+            name(),
+            0..=64usize,
+            option::of(name()),
+            signals,
+            option::of(text()),
+            option::of(0..=65535i64),
+            option::of(prop_oneof![
+                (0..2i64).prop_map(AttributeValue::Integer),
+                prop_oneof![Just("Cyclic"), Just("Event")]
+                    .prop_map(|value| AttributeValue::String(String::from(value))),
+            ]),
+            collection::vec(option::of(extended_multiplexing()), 3),
+        )
+            .prop_map(
+                move |(
+                    name,
+                    size,
+                    transmitter,
+                    mut signals,
+                    comment,
+                    cycle_time,
+                    send_type,
+                    extended_multiplexings,
+                )| {
+                    set_extended_multiplexings(&mut signals, extended_multiplexings);
+
+                    let mut message = Message::new(identifier, name, size, transmitter);
+                    for signal in signals {
+                        message.add_signal(signal).unwrap();
+                    }
+                    message.set_comment(comment);
+                    if let Some(cycle_time) = cycle_time {
+                        message.set_attribute(
+                            String::from("GenMsgCycleTime"),
+                            AttributeValue::Integer(cycle_time),
+                        );
+                    }
+                    if let Some(send_type) = send_type {
+                        message.set_attribute(String::from("GenMsgSendType"), send_type);
+                    }
+                    message
+                },
+            )
+    }
+
+    // The multiplexor is picked once the other signals of the message are known.
+    type ExtendedMultiplexingChoice = (prop::sample::Index, Vec<RangeInclusive<u64>>);
+
+    fn extended_multiplexing() -> impl Strategy<Value = ExtendedMultiplexingChoice> {
+        let values = any::<(u16, u16)>()
+            .prop_map(|(first, last)| u64::from(first.min(last))..=u64::from(first.max(last)));
+
+        (any::<prop::sample::Index>(), collection::vec(values, 1..3))
+    }
+
+    // Extended multiplexing is only valid on a multiplexed signal, naming another signal of the
+    // message that is a multiplexor.
+    fn set_extended_multiplexings(
+        signals: &mut [Signal],
+        extended_multiplexings: Vec<Option<ExtendedMultiplexingChoice>>,
+    ) {
+        let multiplexors: Vec<String> = signals
+            .iter()
+            .filter(|signal| {
+                matches!(
+                    signal.multiplexing(),
+                    Multiplexing::Multiplexor | Multiplexing::MultiplexedMultiplexor(_)
+                )
+            })
+            .map(|signal| String::from(signal.name()))
+            .collect();
+
+        for (signal, extended_multiplexing) in signals.iter_mut().zip(extended_multiplexings) {
+            let multiplexors: Vec<&String> = multiplexors
+                .iter()
+                .filter(|multiplexor| multiplexor.as_str() != signal.name())
+                .collect();
+
+            if let Some((multiplexor_index, values)) = extended_multiplexing
+                && matches!(
+                    signal.multiplexing(),
+                    Multiplexing::Multiplexed(_) | Multiplexing::MultiplexedMultiplexor(_)
+                )
+                && !multiplexors.is_empty()
+            {
+                let multiplexor = multiplexors[multiplexor_index.index(multiplexors.len())];
+
+                signal.set_extended_multiplexing(Some(ExtendedMultiplexing::new(
+                    multiplexor.clone(),
+                    values,
+                )));
+            }
+        }
+    }
+
+    fn node() -> impl Strategy<Value = (String, Option<String>, Option<String>)> {
+        (name(), option::of(text()), option::of(text()))
+    }
+
+    fn attribute_definitions() -> Vec<AttributeDefinition> {
+        vec![
+            AttributeDefinition::new(
+                String::from("BusType"),
+                AttributeObjectType::Network,
+                AttributeValueType::String,
+                Some(AttributeValue::String(String::from("CAN"))),
+            ),
+            AttributeDefinition::new(
+                String::from("NodeLayerModules"),
+                AttributeObjectType::Node,
+                AttributeValueType::String,
+                None,
+            ),
+            AttributeDefinition::new(
+                String::from("GenMsgCycleTime"),
+                AttributeObjectType::Message,
+                AttributeValueType::Integer {
+                    minimum: 0,
+                    maximum: 65535,
+                },
+                Some(AttributeValue::Integer(0)),
+            ),
+            AttributeDefinition::new(
+                String::from("GenMsgSendType"),
+                AttributeObjectType::Message,
+                AttributeValueType::Enumeration {
+                    values: vec![String::from("Cyclic"), String::from("Event")],
+                },
+                None,
+            ),
+            AttributeDefinition::new(
+                String::from("GenSigStartValue"),
+                AttributeObjectType::Signal,
+                AttributeValueType::Float {
+                    minimum: -3.4e38,
+                    maximum: 3.4e38,
+                },
+                Some(AttributeValue::Float(0.0)),
+            ),
+            AttributeDefinition::new(
+                String::from("Mask"),
+                AttributeObjectType::EnvironmentVariable,
+                AttributeValueType::Hex {
+                    minimum: 0,
+                    maximum: 0xFF,
+                },
+                None,
+            ),
+        ]
+    }
+
+    fn database() -> impl Strategy<Value = Database> {
+        let messages = collection::btree_set(identifier(), 0..4)
+            .prop_flat_map(|identifiers| identifiers.into_iter().map(message).collect::<Vec<_>>());
+
+        (
+            text(),
+            collection::btree_map(name(), node(), 0..4),
+            collection::btree_map(
+                name(),
+                collection::btree_map(any::<i64>(), text(), 0..3),
+                0..3,
+            ),
+            messages,
+            option::of(text()),
+            option::of(text()),
+        )
+            .prop_map(
+                |(version, nodes, value_tables, messages, comment, bus_type)| {
+                    let mut database = Database::new(version);
+                    for (name, (_, comment, modules)) in nodes {
+                        let mut node = Node::new(name);
+                        node.set_comment(comment);
+                        if let Some(modules) = modules {
+                            node.set_attribute(
+                                String::from("NodeLayerModules"),
+                                AttributeValue::String(modules),
+                            );
+                        }
+                        database.add_node(node).unwrap();
+                    }
+                    for (name, value_descriptions) in value_tables {
+                        database.add_value_table(name, value_descriptions).unwrap();
+                    }
+                    for message in messages {
+                        database.add_message(message).unwrap();
+                    }
+                    for attribute_definition in attribute_definitions() {
+                        database
+                            .add_attribute_definition(attribute_definition)
+                            .unwrap();
+                    }
+                    database.set_comment(comment);
+                    if let Some(bus_type) = bus_type {
+                        database.set_attribute(
+                            String::from("BusType"),
+                            AttributeValue::String(bus_type),
+                        );
+                    }
+                    database
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_write_parse_round_trip(database in database()) {
+            prop_assert_eq!(parse(&write(&database).unwrap()), Ok(database));
+        }
+    }
+}
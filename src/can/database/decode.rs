@@ -0,0 +1,290 @@
+use std::error::Error;
+
+use strum::Display;
+
+use crate::can::{
+    can_db_id::CANDBID,
+    can_id::CANID,
+    database::{Database, Message, MultiplexingError, Signal},
+    signal::{SignalError, ValueType},
+};
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    UnknownMessage,
+    MultiplexorMissing,
+    MultiplexorAmbiguous,
+    InvalidSignal(SignalError),
+}
+
+impl Error for DecodeError {}
+
+impl From<MultiplexingError> for DecodeError {
+    fn from(value: MultiplexingError) -> Self {
+        match value {
+            MultiplexingError::MultiplexorMissing => Self::MultiplexorMissing,
+            MultiplexingError::MultiplexorAmbiguous => Self::MultiplexorAmbiguous,
+            MultiplexingError::InvalidSignal(error) => Self::InvalidSignal(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage<'a> {
+    message: &'a Message,
+    signals: Vec<DecodedSignal<'a>>,
+}
+
+impl<'a> DecodedMessage<'a> {
+    pub fn message(&self) -> &'a Message {
+        self.message
+    }
+
+    pub fn signals(&self) -> &[DecodedSignal<'a>] {
+        &self.signals
+    }
+
+    pub fn signal(&self, name: &str) -> Option<&DecodedSignal<'a>> {
+        self.signals.iter().find(|signal| signal.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSignal<'a> {
+    signal: &'a Signal,
+    raw_value: u64,
+    value: f64,
+}
+
+impl<'a> DecodedSignal<'a> {
+    pub fn signal(&self) -> &'a Signal {
+        self.signal
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.signal.name()
+    }
+
+    pub fn unit(&self) -> &'a str {
+        self.signal.unit()
+    }
+
+    pub fn raw_value(&self) -> u64 {
+        self.raw_value
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    // The value table entry for the raw value, which floating point signals never have.
+    pub fn label(&self) -> Option<&'a str> {
+        let spec = self.signal.spec();
+
+        let raw_value = match spec.value_type() {
+            ValueType::Unsigned => i64::try_from(self.raw_value).ok()?,
+            ValueType::Signed => spec.sign_extend(self.raw_value),
+            ValueType::Float32 | ValueType::Float64 => return None,
+        };
+
+        self.signal
+            .value_descriptions()
+            .get(&raw_value)
+            .map(String::as_str)
+    }
+}
+
+impl Database {
+    pub fn decode(
+        &self,
+        identifier: CANID,
+        data_field: &[u8],
+    ) -> Result<DecodedMessage<'_>, DecodeError> {
+        self.message(&CANDBID::from(identifier))
+            .ok_or(DecodeError::UnknownMessage)?
+            .decode(data_field)
+    }
+}
+
+impl Message {
+    // Multiplexed signals are only present when their multiplexor, and each multiplexor above it,
+    // has one of the values that select them.
+    pub fn decode(&self, data_field: &[u8]) -> Result<DecodedMessage<'_>, DecodeError> {
+        let mut signals = Vec::new();
+
+        for signal in &self.signals {
+            if !self.is_selected(signal, data_field)? {
+                continue;
+            }
+
+            signals.push(DecodedSignal {
+                signal,
+                raw_value: signal
+                    .spec()
+                    .decode_raw(data_field)
+                    .map_err(DecodeError::InvalidSignal)?,
+                value: signal
+                    .spec()
+                    .decode(data_field)
+                    .map_err(DecodeError::InvalidSignal)?,
+            });
+        }
+
+        Ok(DecodedMessage {
+            message: self,
+            signals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_none, assert_ok, assert_some, assert_some_eq};
+    use ux::{u11, u29};
+
+    use crate::can::database::{
+        dbc::parse,
+        test_fixtures::{DBC, NESTED_DBC, ambiguous_nested_dbc},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let database = assert_ok!(parse(DBC));
+
+        let decoded_message =
+            assert_ok!(database.decode(CANID::Standard(u11::new(256)), &[0x40, 0x1F, 0x5A, 0x03]));
+
+        assert_eq!(decoded_message.message().name(), "EngineStatus");
+        assert_eq!(decoded_message.signals().len(), 3);
+
+        let speed = assert_some!(decoded_message.signal("Speed"));
+        assert_eq!(speed.raw_value(), 0x1F40);
+        assert_eq!(speed.value(), 2000.0);
+        assert_eq!(speed.unit(), "rpm");
+        assert_none!(speed.label());
+
+        let temperature = assert_some!(decoded_message.signal("Temperature"));
+        assert_eq!(temperature.value(), 50.0);
+        assert_none!(temperature.label());
+
+        let gear = assert_some!(decoded_message.signal("Gear"));
+        assert_eq!(gear.value(), 3.0);
+        assert_some_eq!(gear.label(), "Drive");
+
+        // A raw temperature of all ones is -1 once sign extended, which has a label.
+        let decoded_message =
+            assert_ok!(database.decode(CANID::Standard(u11::new(256)), &[0x00, 0x00, 0xFF, 0x00]));
+        let temperature = assert_some!(decoded_message.signal("Temperature"));
+        assert_eq!(temperature.value(), -41.0);
+        assert_some_eq!(temperature.label(), "Error");
+    }
+
+    #[test]
+    fn test_decode_multiplexed() {
+        let database = assert_ok!(parse(DBC));
+
+        let decoded_message = assert_ok!(database.decode(
+            CANID::Extended(u29::new(0x200)),
+            &[0x00, 0xB0, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00]
+        ));
+        let names: Vec<&str> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.name())
+            .collect();
+        assert_eq!(names, vec!["Mode", "Voltage"]);
+        assert_eq!(
+            assert_some!(decoded_message.signal("Voltage")).value(),
+            12.0
+        );
+
+        let decoded_message = assert_ok!(database.decode(
+            CANID::Extended(u29::new(0x200)),
+            &[0x01, 0x9C, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00]
+        ));
+        let names: Vec<&str> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.name())
+            .collect();
+        assert_eq!(names, vec!["Mode", "Current"]);
+        assert_eq!(
+            assert_some!(decoded_message.signal("Current")).value(),
+            -10.0
+        );
+
+        // No signal is multiplexed by this value, so only the multiplexor is present.
+        let decoded_message = assert_ok!(database.decode(
+            CANID::Extended(u29::new(0x200)),
+            &[0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        ));
+        assert_eq!(decoded_message.signals().len(), 1);
+    }
+
+    #[test]
+    fn test_decode_nested_multiplexed() {
+        let database = assert_ok!(parse(NESTED_DBC));
+
+        let decode = |data_field: &[u8]| {
+            let decoded_message =
+                assert_ok!(database.decode(CANID::Standard(u11::new(1024)), data_field));
+            decoded_message
+                .signals()
+                .iter()
+                .map(|signal| (String::from(signal.name()), signal.value()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            decode(&[0x01, 0x03, 0xB0, 0x04]),
+            vec![
+                (String::from("Service"), 1.0),
+                (String::from("Session"), 3.0),
+                (String::from("Voltage"), 12.0)
+            ]
+        );
+        assert_eq!(
+            decode(&[0x01, 0x05, 0x9C, 0xFF]),
+            vec![
+                (String::from("Service"), 1.0),
+                (String::from("Session"), 5.0),
+                (String::from("Current"), -10.0)
+            ]
+        );
+
+        // The voltage is selected by the session, not by the service having its value.
+        assert_eq!(
+            decode(&[0x02, 0x07, 0xB0, 0x04]),
+            vec![(String::from("Service"), 2.0), (String::from("Counter"), 7.0)]
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let database = assert_ok!(parse(DBC));
+
+        assert_err_eq!(
+            database.decode(CANID::Standard(u11::new(257)), &[0x00; 4]),
+            DecodeError::UnknownMessage
+        );
+        assert_err_eq!(
+            database.decode(CANID::Standard(u11::new(256)), &[0x00; 3]),
+            DecodeError::InvalidSignal(SignalError::DataFieldTooShort)
+        );
+
+        // A multiplexed signal without a multiplexor to select it.
+        let database = assert_ok!(parse("BO_ 1 A: 1 X\n SG_ B m1 : 0|8@1+ (1,0) [0|0] \"\" X"));
+        assert_err_eq!(
+            database.decode(CANID::Standard(u11::new(1)), &[0x01]),
+            DecodeError::MultiplexorMissing
+        );
+
+        let database = assert_ok!(parse(ambiguous_nested_dbc()));
+        assert_err_eq!(
+            database.decode(CANID::Standard(u11::new(1024)), &[0x01, 0x03, 0xB0, 0x04]),
+            DecodeError::MultiplexorAmbiguous
+        );
+    }
+}
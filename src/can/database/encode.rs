@@ -0,0 +1,302 @@
+use std::{collections::BTreeMap, error::Error};
+
+use strum::Display;
+
+use crate::can::{
+    can_db_id::CANDBID,
+    can_id::CANID,
+    database::{AttributeValue, Database, Message, MultiplexingError, Signal},
+    signal::SignalError,
+};
+
+// The attribute Vector tools give the initial raw value of a signal in.
+const START_VALUE_ATTRIBUTE: &str = "GenSigStartValue";
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    UnknownMessage,
+    UnknownSignal,
+    MultiplexorMissing,
+    MultiplexorAmbiguous,
+    SignalNotSelected,
+    InvalidSignal(SignalError),
+}
+
+impl Error for EncodeError {}
+
+impl From<MultiplexingError> for EncodeError {
+    fn from(value: MultiplexingError) -> Self {
+        match value {
+            MultiplexingError::MultiplexorMissing => Self::MultiplexorMissing,
+            MultiplexingError::MultiplexorAmbiguous => Self::MultiplexorAmbiguous,
+            MultiplexingError::InvalidSignal(error) => Self::InvalidSignal(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedMessage {
+    identifier: CANID,
+    data_field: Vec<u8>,
+}
+
+impl EncodedMessage {
+    pub fn identifier(&self) -> CANID {
+        self.identifier
+    }
+
+    pub fn data_field(&self) -> &[u8] {
+        &self.data_field
+    }
+}
+
+impl Database {
+    pub fn encode(
+        &self,
+        identifier: CANDBID,
+        values: &BTreeMap<String, f64>,
+    ) -> Result<EncodedMessage, EncodeError> {
+        let message = self
+            .message(&identifier)
+            .ok_or(EncodeError::UnknownMessage)?;
+
+        self.encode_message(message, values)
+    }
+
+    pub fn encode_by_name(
+        &self,
+        name: &str,
+        values: &BTreeMap<String, f64>,
+    ) -> Result<EncodedMessage, EncodeError> {
+        let message = self
+            .message_by_name(name)
+            .ok_or(EncodeError::UnknownMessage)?;
+
+        self.encode_message(message, values)
+    }
+
+    // Multiplexors are encoded before the signals below them, as their raw values select which
+    // multiplexed signals are encoded. Values given for signals they do not select are rejected.
+    fn encode_message(
+        &self,
+        message: &Message,
+        values: &BTreeMap<String, f64>,
+    ) -> Result<EncodedMessage, EncodeError> {
+        if values.keys().any(|name| message.signal(name).is_none()) {
+            return Err(EncodeError::UnknownSignal);
+        }
+
+        let mut signals = message
+            .signals()
+            .iter()
+            .map(|signal| Ok((message.multiplexing_depth(signal)?, signal)))
+            .collect::<Result<Vec<_>, MultiplexingError>>()?;
+        signals.sort_by_key(|(depth, _)| *depth);
+
+        let mut data_field = vec![0; message.size()];
+
+        for (_, signal) in signals {
+            if message.is_selected(signal, &data_field)? {
+                self.encode_signal(signal, values, &mut data_field)?;
+            } else if values.contains_key(signal.name()) {
+                return Err(EncodeError::SignalNotSelected);
+            }
+        }
+
+        Ok(EncodedMessage {
+            identifier: CANID::from(message.identifier()),
+            data_field,
+        })
+    }
+
+    // Signals without a value take their start value, which is in raw units and so is not held
+    // to the signal's physical range.
+    fn encode_signal(
+        &self,
+        signal: &Signal,
+        values: &BTreeMap<String, f64>,
+        data_field: &mut [u8],
+    ) -> Result<(), EncodeError> {
+        match values.get(signal.name()) {
+            Some(value) => signal.spec().encode(*value, data_field),
+            None => signal
+                .spec()
+                .encode_unscaled(self.start_value(signal), data_field),
+        }
+        .map_err(EncodeError::InvalidSignal)
+    }
+
+    fn start_value(&self, signal: &Signal) -> f64 {
+        let start_value = signal.attributes().get(START_VALUE_ATTRIBUTE).or_else(|| {
+            self.attribute_definition(START_VALUE_ATTRIBUTE)
+                .and_then(|attribute_definition| attribute_definition.default())
+        });
+
+        match start_value {
+            Some(AttributeValue::Integer(value)) => *value as f64,
+            Some(AttributeValue::Float(value)) => *value,
+            Some(AttributeValue::String(_)) | None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::{u11, u29};
+
+    use crate::can::database::{
+        dbc::parse,
+        test_fixtures::{DBC, NESTED_DBC, ambiguous_nested_dbc},
+    };
+
+    use super::*;
+
+    const START_VALUE_ATTRIBUTES: &str = r#"
+BA_DEF_ SG_ "GenSigStartValue" FLOAT -3.4E+038 3.4E+038;
+BA_DEF_DEF_ "GenSigStartValue" 0;
+BA_ "GenSigStartValue" SG_ 256 Temperature 60;
+BA_ "GenSigStartValue" SG_ 256 Gear 2;
+BA_ "GenSigStartValue" SG_ 2147484160 Mode 1;
+"#;
+
+    fn dbc() -> String {
+        format!("{DBC}{START_VALUE_ATTRIBUTES}")
+    }
+
+    fn values(values: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        values
+            .iter()
+            .map(|(name, value)| (String::from(*name), *value))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode() {
+        let database = assert_ok!(parse(&dbc()));
+
+        let encoded_message = assert_ok!(database.encode(
+            CANDBID::Standard(u11::new(256)),
+            &values(&[("Speed", 2000.0), ("Temperature", 50.0), ("Gear", 3.0)])
+        ));
+
+        assert_eq!(encoded_message.identifier(), CANID::Standard(u11::new(256)));
+        assert_eq!(encoded_message.data_field(), [0x40, 0x1F, 0x5A, 0x03]);
+
+        let decoded_message =
+            assert_ok!(database.decode(encoded_message.identifier(), encoded_message.data_field()));
+        let decoded_values: Vec<f64> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.value())
+            .collect();
+        assert_eq!(decoded_values, vec![2000.0, 50.0, 3.0]);
+    }
+
+    #[test]
+    fn test_encode_start_values() {
+        let database = assert_ok!(parse(&dbc()));
+
+        // Start values are raw, so the temperature is 60 - 40 = 20 degC, and the speed takes the
+        // default of the attribute.
+        let encoded_message = assert_ok!(database.encode_by_name("EngineStatus", &values(&[])));
+
+        assert_eq!(encoded_message.data_field(), [0x00, 0x00, 0x3C, 0x02]);
+    }
+
+    #[test]
+    fn test_encode_multiplexed() {
+        let database = assert_ok!(parse(&dbc()));
+
+        let encoded_message = assert_ok!(
+            database.encode_by_name("Diagnostics", &values(&[("Mode", 0.0), ("Voltage", 12.0)]))
+        );
+
+        assert_eq!(
+            encoded_message.identifier(),
+            CANID::Extended(u29::new(0x200))
+        );
+        assert_eq!(
+            encoded_message.data_field(),
+            [0x00, 0xB0, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+
+        // The multiplexor starts at 1, which selects the current.
+        let encoded_message =
+            assert_ok!(database.encode_by_name("Diagnostics", &values(&[("Current", -10.0)])));
+
+        assert_eq!(
+            encoded_message.data_field(),
+            [0x01, 0x9C, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+
+        assert_err_eq!(
+            database.encode_by_name("Diagnostics", &values(&[("Voltage", 12.0)])),
+            EncodeError::SignalNotSelected
+        );
+    }
+
+    #[test]
+    fn test_encode_nested_multiplexed() {
+        let database = assert_ok!(parse(NESTED_DBC));
+
+        let encoded_message = assert_ok!(database.encode_by_name(
+            "Diagnostics",
+            &values(&[("Service", 1.0), ("Session", 3.0), ("Voltage", 12.0)])
+        ));
+        assert_eq!(encoded_message.data_field(), [0x01, 0x03, 0xB0, 0x04]);
+
+        let encoded_message = assert_ok!(
+            database.encode_by_name("Diagnostics", &values(&[("Service", 2.0), ("Counter", 7.0)]))
+        );
+        assert_eq!(encoded_message.data_field(), [0x02, 0x07, 0x00, 0x00]);
+
+        assert_err_eq!(
+            database.encode_by_name(
+                "Diagnostics",
+                &values(&[("Service", 1.0), ("Session", 5.0), ("Voltage", 12.0)])
+            ),
+            EncodeError::SignalNotSelected
+        );
+
+        // A service of 2 selects the counter, and no session is present to select the voltage.
+        assert_err_eq!(
+            database.encode_by_name("Diagnostics", &values(&[("Service", 2.0), ("Voltage", 12.0)])),
+            EncodeError::SignalNotSelected
+        );
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let database = assert_ok!(parse(&dbc()));
+
+        assert_err_eq!(
+            database.encode(CANDBID::Standard(u11::new(257)), &values(&[])),
+            EncodeError::UnknownMessage
+        );
+        assert_err_eq!(
+            database.encode_by_name("EngineStatus", &values(&[("Torque", 0.0)])),
+            EncodeError::UnknownSignal
+        );
+        assert_err_eq!(
+            database.encode_by_name("EngineStatus", &values(&[("Temperature", 90.0)])),
+            EncodeError::InvalidSignal(SignalError::ValueOutOfRange)
+        );
+        assert_err_eq!(
+            database.encode_by_name("Diagnostics", &values(&[("Current", -200.0)])),
+            EncodeError::InvalidSignal(SignalError::ValueOutOfRange)
+        );
+
+        let database = assert_ok!(parse("BO_ 1 A: 1 X\n SG_ B m1 : 0|8@1+ (1,0) [0|0] \"\" X"));
+        assert_err_eq!(
+            database.encode_by_name("A", &values(&[])),
+            EncodeError::MultiplexorMissing
+        );
+
+        let database = assert_ok!(parse(ambiguous_nested_dbc()));
+        assert_err_eq!(
+            database.encode_by_name("Diagnostics", &values(&[])),
+            EncodeError::MultiplexorAmbiguous
+        );
+    }
+}
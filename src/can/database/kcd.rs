@@ -0,0 +1,386 @@
+use std::collections::BTreeMap;
+
+use roxmltree::Node as XMLNode;
+
+use crate::can::{
+    database::{
+        Database, ImportError, ImportErrorKind, Message, Multiplexing, Node, Signal,
+        ValueDescriptions, can_db_id,
+        xml::{
+            attribute, child, children, error_at, parse_document, parse_identifier, parse_number,
+            text,
+        },
+    },
+    signal::{ByteOrder, Scaling, SignalSpec, ValueType, msb0_to_dbc_start_bit},
+};
+
+// Parses a Kayak network definition. Messages from every bus are merged into the one database.
+pub fn parse(text: &str) -> Result<Database, ImportError> {
+    let document = parse_document(text)?;
+    let network_definition = document.root_element();
+
+    if network_definition.tag_name().name() != "NetworkDefinition" {
+        return Err(error_at(
+            network_definition,
+            ImportErrorKind::MissingElement,
+        ));
+    }
+
+    let version = child(network_definition, "Document")
+        .and_then(|document| document.attribute("version"))
+        .unwrap_or_default();
+
+    let mut database = Database::new(String::from(version));
+
+    // Nodes are referred to by their id, which is not part of the model.
+    let mut node_names = BTreeMap::new();
+
+    for node in children(network_definition, "Node") {
+        let name = attribute(node, "name")?;
+        node_names.insert(attribute(node, "id")?, String::from(name));
+
+        database
+            .add_node(Node::new(String::from(name)))
+            .map_err(|error| error_at(node, ImportErrorKind::InvalidDatabase(error)))?;
+    }
+
+    for bus in children(network_definition, "Bus") {
+        for message in children(bus, "Message") {
+            database
+                .add_message(parse_message(message, &node_names)?)
+                .map_err(|error| error_at(message, ImportErrorKind::InvalidDatabase(error)))?;
+        }
+    }
+
+    Ok(database)
+}
+
+fn parse_message(
+    element: XMLNode,
+    node_names: &BTreeMap<&str, String>,
+) -> Result<Message, ImportError> {
+    let identifier = parse_identifier(element, attribute(element, "id")?)?;
+    let extended = element.attribute("format") == Some("extended");
+    let identifier = can_db_id(identifier, extended)
+        .ok_or(error_at(element, ImportErrorKind::IdentifierOutOfRange))?;
+
+    let transmitter = child(element, "Producer")
+        .map(|producer| parse_node_references(producer, node_names))
+        .transpose()?
+        .and_then(|transmitters| transmitters.into_iter().next());
+
+    let mut signals = Vec::new();
+
+    for signal in children(element, "Signal") {
+        signals.push(parse_signal(signal, node_names, Multiplexing::None)?);
+    }
+
+    for multiplex in children(element, "Multiplex") {
+        signals.push(parse_signal(
+            multiplex,
+            node_names,
+            Multiplexing::Multiplexor,
+        )?);
+
+        for mux_group in children(multiplex, "MuxGroup") {
+            let value = parse_number(mux_group, attribute(mux_group, "count")?)?;
+
+            for signal in children(mux_group, "Signal") {
+                signals.push(parse_signal(
+                    signal,
+                    node_names,
+                    Multiplexing::Multiplexed(value),
+                )?);
+            }
+        }
+    }
+
+    // An automatic length is just long enough for the signals.
+    let size = match element.attribute("length") {
+        Some(length) if length != "auto" => parse_number(element, length)?,
+        _ => signals
+            .iter()
+            .map(|signal| (bit_offset(signal.spec()) + signal.spec().length()).div_ceil(8))
+            .max()
+            .unwrap_or_default(),
+    };
+
+    let mut message = Message::new(
+        identifier,
+        String::from(attribute(element, "name")?),
+        size,
+        transmitter,
+    );
+
+    for signal in signals {
+        message
+            .add_signal(signal)
+            .map_err(|error| error_at(element, ImportErrorKind::InvalidDatabase(error)))?;
+    }
+
+    message.set_comment(child(element, "Notes").map(|notes| String::from(text(notes))));
+
+    Ok(message)
+}
+
+// Signals are placed by the offset of their least significant bit, counted from the least
+// significant bit of the first byte, or for big endian signals, of their most significant bit,
+// counted from the most significant bit of the first byte.
+fn parse_signal(
+    element: XMLNode,
+    node_names: &BTreeMap<&str, String>,
+    multiplexing: Multiplexing,
+) -> Result<Signal, ImportError> {
+    let offset: usize = parse_number(element, attribute(element, "offset")?)?;
+    let length = element
+        .attribute("length")
+        .map(|length| parse_number(element, length))
+        .transpose()?
+        .unwrap_or(1);
+
+    let (byte_order, start_bit) = match element.attribute("endianess").unwrap_or("little") {
+        "little" => (ByteOrder::LittleEndian, offset),
+        "big" => (ByteOrder::BigEndian, msb0_to_dbc_start_bit(offset)),
+        _ => return Err(error_at(element, ImportErrorKind::InvalidByteOrder)),
+    };
+
+    let value = child(element, "Value");
+    let value_attribute = |name: &str| value.and_then(|value| value.attribute(name));
+    let value_number = |name: &str, default: f64| {
+        value_attribute(name)
+            .map(|number| parse_number(value.unwrap_or(element), number))
+            .transpose()
+            .map(|number| number.unwrap_or(default))
+    };
+
+    let value_type = match value_attribute("type").unwrap_or("unsigned") {
+        "unsigned" => ValueType::Unsigned,
+        "signed" => ValueType::Signed,
+        "single" => ValueType::Float32,
+        "double" => ValueType::Float64,
+        _ => return Err(error_at(element, ImportErrorKind::InvalidValueType)),
+    };
+
+    let spec = SignalSpec::new(
+        start_bit,
+        length,
+        byte_order,
+        value_type,
+        Scaling::new(value_number("slope", 1.0)?, value_number("intercept", 0.0)?),
+        value_number("min", 0.0)?..=value_number("max", 0.0)?,
+    )
+    .map_err(|error| error_at(element, ImportErrorKind::InvalidSignal(error)))?;
+
+    let receivers = child(element, "Consumer")
+        .map(|consumer| parse_node_references(consumer, node_names))
+        .transpose()?
+        .unwrap_or_default();
+
+    let mut signal = Signal::new(
+        String::from(attribute(element, "name")?),
+        spec,
+        String::from(value_attribute("unit").unwrap_or_default()),
+        receivers,
+        multiplexing,
+    );
+
+    if let Some(label_set) = child(element, "LabelSet") {
+        let mut value_descriptions = ValueDescriptions::new();
+
+        for label in children(label_set, "Label") {
+            value_descriptions.insert(
+                parse_number(label, attribute(label, "value")?)?,
+                String::from(attribute(label, "name")?),
+            );
+        }
+
+        signal.set_value_descriptions(value_descriptions);
+    }
+
+    signal.set_comment(child(element, "Notes").map(|notes| String::from(text(notes))));
+
+    Ok(signal)
+}
+
+fn parse_node_references(
+    element: XMLNode,
+    node_names: &BTreeMap<&str, String>,
+) -> Result<Vec<String>, ImportError> {
+    children(element, "NodeRef")
+        .map(|node_reference| {
+            node_names
+                .get(attribute(node_reference, "id")?)
+                .cloned()
+                .ok_or(error_at(node_reference, ImportErrorKind::UnknownReference))
+        })
+        .collect()
+}
+
+// The offset of the first bit of the signal in the order it is numbered in.
+fn bit_offset(spec: &SignalSpec) -> usize {
+    match spec.byte_order() {
+        ByteOrder::LittleEndian => spec.start_bit(),
+        ByteOrder::BigEndian => msb0_to_dbc_start_bit(spec.start_bit()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok, assert_some, assert_some_eq};
+    use ux::{u11, u29};
+
+    use crate::can::{can_db_id::CANDBID, can_id::CANID};
+
+    use super::*;
+
+    const KCD: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<NetworkDefinition xmlns="http://kayak.2codeornot2code.org/1.0">
+  <Document name="Example" version="1.2" author="" company="" date=""/>
+  <Node id="1" name="Engine"/>
+  <Node id="2" name="Dashboard"/>
+  <Bus name="Powertrain" baudrate="500000">
+    <Message id="0x100" name="EngineStatus" length="4" interval="10">
+      <Notes>Engine state</Notes>
+      <Producer>
+        <NodeRef id="1"/>
+      </Producer>
+      <Signal name="Speed" offset="0" length="16">
+        <Consumer>
+          <NodeRef id="2"/>
+        </Consumer>
+        <Value slope="0.25" unit="rpm" min="0" max="16383.75"/>
+      </Signal>
+      <Signal name="Temperature" offset="16" length="8" endianess="big">
+        <Value type="signed" intercept="-40" unit="degC" min="-40" max="87"/>
+      </Signal>
+      <Signal name="Gear" offset="24" length="2">
+        <LabelSet>
+          <Label name="Park" value="0"/>
+          <Label name="Drive" value="3"/>
+        </LabelSet>
+      </Signal>
+    </Message>
+  </Bus>
+  <Bus name="Diagnostics">
+    <Message id="0x200" name="Diagnostics" format="extended">
+      <Multiplex name="Mode" offset="0" length="8">
+        <MuxGroup count="0">
+          <Signal name="Voltage" offset="8" length="32">
+            <Value type="single" unit="V"/>
+          </Signal>
+        </MuxGroup>
+        <MuxGroup count="1">
+          <Signal name="Current" offset="8" length="16">
+            <Value type="signed" slope="0.1" unit="A"/>
+          </Signal>
+        </MuxGroup>
+      </Multiplex>
+    </Message>
+  </Bus>
+</NetworkDefinition>
+"#;
+
+    #[test]
+    fn test_parse() {
+        let database = assert_ok!(parse(KCD));
+
+        assert_eq!(database.version(), "1.2");
+        let node_names: Vec<&str> = database.nodes().iter().map(Node::name).collect();
+        assert_eq!(node_names, vec!["Engine", "Dashboard"]);
+
+        let engine_status = assert_some!(database.message(&CANDBID::Standard(u11::new(0x100))));
+        assert_eq!(engine_status.name(), "EngineStatus");
+        assert_eq!(engine_status.size(), 4);
+        assert_eq!(engine_status.transmitter(), Some("Engine"));
+        assert_eq!(engine_status.comment(), Some("Engine state"));
+
+        let speed = assert_some!(engine_status.signal("Speed"));
+        assert_eq!(speed.spec().start_bit(), 0);
+        assert_eq!(speed.spec().factor(), 0.25);
+        assert_eq!(speed.unit(), "rpm");
+        assert_eq!(speed.receivers(), ["Dashboard"]);
+
+        // The most significant bit of the third byte, which DBC files number 23.
+        let temperature = assert_some!(engine_status.signal("Temperature"));
+        assert_eq!(temperature.spec().start_bit(), 23);
+        assert_eq!(temperature.spec().byte_order(), ByteOrder::BigEndian);
+        assert_eq!(temperature.spec().value_type(), ValueType::Signed);
+
+        let gear = assert_some!(engine_status.signal("Gear"));
+        assert_eq!(gear.spec().length(), 2);
+        assert_some_eq!(gear.value_descriptions().get(&3), "Drive");
+
+        let diagnostics = assert_some!(database.message_by_name("Diagnostics"));
+        assert_eq!(diagnostics.identifier(), CANDBID::Extended(0x80000200));
+        assert_eq!(diagnostics.size(), 5);
+        assert_eq!(assert_some!(diagnostics.multiplexor()).name(), "Mode");
+
+        let voltage = assert_some!(diagnostics.signal("Voltage"));
+        assert_eq!(voltage.multiplexing(), Multiplexing::Multiplexed(0));
+        assert_eq!(voltage.spec().value_type(), ValueType::Float32);
+
+        let current = assert_some!(diagnostics.signal("Current"));
+        assert_eq!(current.multiplexing(), Multiplexing::Multiplexed(1));
+
+        let decoded_message = assert_ok!(
+            database.decode(CANID::Standard(u11::new(0x100)), &[0x40, 0x1F, 0x5A, 0x03])
+        );
+        let decoded_values: Vec<f64> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.value())
+            .collect();
+        assert_eq!(decoded_values, vec![2000.0, 50.0, 3.0]);
+
+        let decoded_message = assert_ok!(database.decode(
+            CANID::Extended(u29::new(0x200)),
+            &[0x01, 0x9C, 0xFF, 0x00, 0x00]
+        ));
+        assert_eq!(
+            assert_some!(decoded_message.signal("Current")).value(),
+            -10.0
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_err_eq!(
+            parse("<NetworkDefinition>\n  <Bus>\n</NetworkDefinition>"),
+            ImportError::new(3, 1, ImportErrorKind::InvalidSyntax)
+        );
+
+        let kcd = r#"<NetworkDefinition>
+  <Bus>
+    <Message id="0x800" name="A"/>
+  </Bus>
+</NetworkDefinition>"#;
+        assert_err_eq!(
+            parse(kcd),
+            ImportError::new(3, 5, ImportErrorKind::IdentifierOutOfRange)
+        );
+
+        let kcd = r#"<NetworkDefinition>
+  <Bus>
+    <Message id="1" name="A">
+      <Signal name="B"/>
+    </Message>
+  </Bus>
+</NetworkDefinition>"#;
+        assert_err_eq!(
+            parse(kcd),
+            ImportError::new(4, 7, ImportErrorKind::MissingAttribute)
+        );
+
+        let kcd = r#"<NetworkDefinition>
+  <Bus>
+    <Message id="1" name="A">
+      <Producer><NodeRef id="9"/></Producer>
+    </Message>
+  </Bus>
+</NetworkDefinition>"#;
+        assert_err_eq!(
+            parse(kcd),
+            ImportError::new(4, 17, ImportErrorKind::UnknownReference)
+        );
+    }
+}
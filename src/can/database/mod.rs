@@ -0,0 +1,671 @@
+use std::{collections::BTreeMap, error::Error, fmt::Display, ops::RangeInclusive};
+
+use strum::Display;
+
+use ux::{u11, u29};
+
+use crate::can::{
+    can_db_id::CANDBID,
+    can_id::CANID,
+    signal::{SignalError, SignalSpec},
+};
+
+pub mod arxml;
+pub mod dbc;
+pub mod decode;
+pub mod encode;
+pub mod kcd;
+pub mod sym;
+#[cfg(test)]
+mod test_fixtures;
+mod xml;
+
+pub type ValueDescriptions = BTreeMap<i64, String>;
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+// DBC files mark extended identifiers by setting bit 31.
+pub(crate) const EXTENDED_IDENTIFIER_FLAG: u32 = 1 << 31;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DatabaseError {
+    IdentifierInvalid,
+    DuplicateNode,
+    DuplicateValueTable,
+    DuplicateMessage,
+    DuplicateSignal,
+    DuplicateAttributeDefinition,
+}
+
+impl Error for DatabaseError {}
+
+// Why the multiplexor that selects a signal could not be found or read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum MultiplexingError {
+    MultiplexorMissing,
+    MultiplexorAmbiguous,
+    InvalidSignal(SignalError),
+}
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    InvalidSyntax,
+    MissingElement,
+    MissingAttribute,
+    InvalidNumber,
+    IdentifierOutOfRange,
+    InvalidByteOrder,
+    InvalidValueType,
+    UnknownReference,
+    InvalidSignal(SignalError),
+    InvalidDatabase(DatabaseError),
+}
+
+// An error importing a KCD, SYM or ARXML network description.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    line: usize,
+    column: usize,
+    kind: ImportErrorKind,
+}
+
+impl ImportError {
+    pub(crate) fn new(line: usize, column: usize, kind: ImportErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn kind(&self) -> ImportErrorKind {
+        self.kind
+    }
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at line {}, column {}", self.kind, self.line, self.column)
+    }
+}
+
+impl Error for ImportError {}
+
+// Importers give identifiers without the bit 31 flag, along with whether they are extended.
+pub(crate) fn can_db_id(identifier: u32, extended: bool) -> Option<CANDBID> {
+    let can_id = if extended {
+        CANID::Extended(u29::try_from(identifier).ok()?)
+    } else {
+        CANID::Standard(u11::try_from(identifier).ok()?)
+    };
+
+    Some(CANDBID::from(can_id))
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Database {
+    version: String,
+    nodes: Vec<Node>,
+    value_tables: BTreeMap<String, ValueDescriptions>,
+    messages: BTreeMap<CANDBID, Message>,
+    attribute_definitions: Vec<AttributeDefinition>,
+    attributes: Attributes,
+    comment: Option<String>,
+}
+
+impl Database {
+    pub fn new(version: String) -> Self {
+        Self {
+            version,
+            ..Self::default()
+        }
+    }
+
+    pub fn add_node(&mut self, node: Node) -> Result<(), DatabaseError> {
+        if self.node(&node.name).is_some() {
+            return Err(DatabaseError::DuplicateNode);
+        }
+
+        self.nodes.push(node);
+
+        Ok(())
+    }
+
+    pub fn add_value_table(
+        &mut self,
+        name: String,
+        value_descriptions: ValueDescriptions,
+    ) -> Result<(), DatabaseError> {
+        if self.value_tables.contains_key(&name) {
+            return Err(DatabaseError::DuplicateValueTable);
+        }
+
+        self.value_tables.insert(name, value_descriptions);
+
+        Ok(())
+    }
+
+    pub fn add_message(&mut self, message: Message) -> Result<(), DatabaseError> {
+        if let CANDBID::Extended(identifier) = message.identifier
+            && identifier & EXTENDED_IDENTIFIER_FLAG == 0
+        {
+            return Err(DatabaseError::IdentifierInvalid);
+        }
+
+        if self.messages.contains_key(&message.identifier) {
+            return Err(DatabaseError::DuplicateMessage);
+        }
+
+        self.messages.insert(message.identifier, message);
+
+        Ok(())
+    }
+
+    pub fn add_attribute_definition(
+        &mut self,
+        attribute_definition: AttributeDefinition,
+    ) -> Result<(), DatabaseError> {
+        if self
+            .attribute_definition(&attribute_definition.name)
+            .is_some()
+        {
+            return Err(DatabaseError::DuplicateAttributeDefinition);
+        }
+
+        self.attribute_definitions.push(attribute_definition);
+
+        Ok(())
+    }
+
+    pub fn set_attribute(&mut self, name: String, value: AttributeValue) {
+        self.attributes.insert(name, value);
+    }
+
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn node(&self, name: &str) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    pub fn value_tables(&self) -> &BTreeMap<String, ValueDescriptions> {
+        &self.value_tables
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.messages.values()
+    }
+
+    pub fn message(&self, identifier: &CANDBID) -> Option<&Message> {
+        self.messages.get(identifier)
+    }
+
+    pub fn message_by_name(&self, name: &str) -> Option<&Message> {
+        self.messages.values().find(|message| message.name == name)
+    }
+
+    pub fn attribute_definitions(&self) -> &[AttributeDefinition] {
+        &self.attribute_definitions
+    }
+
+    pub fn attribute_definition(&self, name: &str) -> Option<&AttributeDefinition> {
+        self.attribute_definitions
+            .iter()
+            .find(|attribute_definition| attribute_definition.name == name)
+    }
+
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    name: String,
+    attributes: Attributes,
+    comment: Option<String>,
+}
+
+impl Node {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            attributes: Attributes::new(),
+            comment: None,
+        }
+    }
+
+    pub fn set_attribute(&mut self, name: String, value: AttributeValue) {
+        self.attributes.insert(name, value);
+    }
+
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    identifier: CANDBID,
+    name: String,
+    size: usize,
+    transmitter: Option<String>,
+    signals: Vec<Signal>,
+    attributes: Attributes,
+    comment: Option<String>,
+}
+
+impl Message {
+    pub fn new(
+        identifier: CANDBID,
+        name: String,
+        size: usize,
+        transmitter: Option<String>,
+    ) -> Self {
+        Self {
+            identifier,
+            name,
+            size,
+            transmitter,
+            signals: Vec::new(),
+            attributes: Attributes::new(),
+            comment: None,
+        }
+    }
+
+    pub fn add_signal(&mut self, signal: Signal) -> Result<(), DatabaseError> {
+        if self.signal(&signal.name).is_some() {
+            return Err(DatabaseError::DuplicateSignal);
+        }
+
+        self.signals.push(signal);
+
+        Ok(())
+    }
+
+    pub fn set_attribute(&mut self, name: String, value: AttributeValue) {
+        self.attributes.insert(name, value);
+    }
+
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    pub fn identifier(&self) -> CANDBID {
+        self.identifier
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn transmitter(&self) -> Option<&str> {
+        self.transmitter.as_deref()
+    }
+
+    pub fn signals(&self) -> &[Signal] {
+        &self.signals
+    }
+
+    pub fn signal(&self, name: &str) -> Option<&Signal> {
+        self.signals.iter().find(|signal| signal.name == name)
+    }
+
+    pub fn multiplexor(&self) -> Option<&Signal> {
+        self.signals
+            .iter()
+            .find(|signal| signal.multiplexing == Multiplexing::Multiplexor)
+    }
+
+    // The multiplexor that selects a signal. Without extended multiplexing, that is the only other
+    // multiplexor in the message.
+    fn selector(&self, signal: &Signal) -> Result<Option<&Signal>, MultiplexingError> {
+        if matches!(
+            signal.multiplexing,
+            Multiplexing::None | Multiplexing::Multiplexor
+        ) {
+            return Ok(None);
+        }
+
+        if let Some(extended_multiplexing) = &signal.extended_multiplexing {
+            return self
+                .signal(&extended_multiplexing.multiplexor)
+                .map(Some)
+                .ok_or(MultiplexingError::MultiplexorMissing);
+        }
+
+        let mut multiplexors = self.signals.iter().filter(|multiplexor| {
+            multiplexor.name != signal.name
+                && matches!(
+                    multiplexor.multiplexing,
+                    Multiplexing::Multiplexor | Multiplexing::MultiplexedMultiplexor(_)
+                )
+        });
+
+        match (multiplexors.next(), multiplexors.next()) {
+            (Some(multiplexor), None) => Ok(Some(multiplexor)),
+            (None, _) => Err(MultiplexingError::MultiplexorMissing),
+            (Some(_), Some(_)) => Err(MultiplexingError::MultiplexorAmbiguous),
+        }
+    }
+
+    // A signal is present when each multiplexor above it is present and has one of the raw values
+    // that select the next one down.
+    pub(crate) fn is_selected(
+        &self,
+        signal: &Signal,
+        data_field: &[u8],
+    ) -> Result<bool, MultiplexingError> {
+        let mut signal = signal;
+
+        for _ in 0..=self.signals.len() {
+            let Some(multiplexor) = self.selector(signal)? else {
+                return Ok(true);
+            };
+
+            let raw_value = multiplexor
+                .spec()
+                .decode_raw(data_field)
+                .map_err(MultiplexingError::InvalidSignal)?;
+
+            if !signal.is_selected_by(raw_value) {
+                return Ok(false);
+            }
+
+            signal = multiplexor;
+        }
+
+        // Multiplexors that select each other never lead to one that is always present.
+        Err(MultiplexingError::MultiplexorMissing)
+    }
+
+    // The number of multiplexors above a signal, which are all read before it.
+    pub(crate) fn multiplexing_depth(&self, signal: &Signal) -> Result<usize, MultiplexingError> {
+        let mut signal = signal;
+
+        for depth in 0..=self.signals.len() {
+            match self.selector(signal)? {
+                Some(multiplexor) => signal = multiplexor,
+                None => return Ok(depth),
+            }
+        }
+
+        Err(MultiplexingError::MultiplexorMissing)
+    }
+
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Multiplexing {
+    None,
+    Multiplexor,
+    // Only present when the multiplexor has this value.
+    Multiplexed(u64),
+    // Both present for one value of the multiplexor and a multiplexor itself.
+    MultiplexedMultiplexor(u64),
+}
+
+// Names the multiplexor that selects a signal, and the ranges of its raw values that do, for
+// messages with more than one multiplexor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedMultiplexing {
+    multiplexor: String,
+    values: Vec<RangeInclusive<u64>>,
+}
+
+impl ExtendedMultiplexing {
+    pub fn new(multiplexor: String, values: Vec<RangeInclusive<u64>>) -> Self {
+        Self {
+            multiplexor,
+            values,
+        }
+    }
+
+    pub fn multiplexor(&self) -> &str {
+        &self.multiplexor
+    }
+
+    pub fn values(&self) -> &[RangeInclusive<u64>] {
+        &self.values
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signal {
+    name: String,
+    spec: SignalSpec,
+    unit: String,
+    receivers: Vec<String>,
+    multiplexing: Multiplexing,
+    extended_multiplexing: Option<ExtendedMultiplexing>,
+    value_descriptions: ValueDescriptions,
+    attributes: Attributes,
+    comment: Option<String>,
+}
+
+impl Signal {
+    pub fn new(
+        name: String,
+        spec: SignalSpec,
+        unit: String,
+        receivers: Vec<String>,
+        multiplexing: Multiplexing,
+    ) -> Self {
+        Self {
+            name,
+            spec,
+            unit,
+            receivers,
+            multiplexing,
+            extended_multiplexing: None,
+            value_descriptions: ValueDescriptions::new(),
+            attributes: Attributes::new(),
+            comment: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_extended_multiplexing(
+        &mut self,
+        extended_multiplexing: Option<ExtendedMultiplexing>,
+    ) {
+        self.extended_multiplexing = extended_multiplexing;
+    }
+
+    pub fn set_value_descriptions(&mut self, value_descriptions: ValueDescriptions) {
+        self.value_descriptions = value_descriptions;
+    }
+
+    pub fn set_attribute(&mut self, name: String, value: AttributeValue) {
+        self.attributes.insert(name, value);
+    }
+
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.comment = comment;
+    }
+
+    pub fn spec(&self) -> &SignalSpec {
+        &self.spec
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn receivers(&self) -> &[String] {
+        &self.receivers
+    }
+
+    pub fn multiplexing(&self) -> Multiplexing {
+        self.multiplexing
+    }
+
+    pub fn extended_multiplexing(&self) -> Option<&ExtendedMultiplexing> {
+        self.extended_multiplexing.as_ref()
+    }
+
+    // Whether a raw value of the signal's multiplexor selects it, which extended multiplexing
+    // widens from the single value to ranges of them.
+    fn is_selected_by(&self, raw_value: u64) -> bool {
+        match (&self.extended_multiplexing, self.multiplexing) {
+            (Some(extended_multiplexing), _) => extended_multiplexing
+                .values
+                .iter()
+                .any(|values| values.contains(&raw_value)),
+            (None, Multiplexing::Multiplexed(value))
+            | (None, Multiplexing::MultiplexedMultiplexor(value)) => raw_value == value,
+            (None, Multiplexing::None) | (None, Multiplexing::Multiplexor) => true,
+        }
+    }
+
+    pub fn value_descriptions(&self) -> &ValueDescriptions {
+        &self.value_descriptions
+    }
+
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AttributeObjectType {
+    Network,
+    Node,
+    Message,
+    Signal,
+    EnvironmentVariable,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValueType {
+    Integer { minimum: i64, maximum: i64 },
+    Hex { minimum: i64, maximum: i64 },
+    Float { minimum: f64, maximum: f64 },
+    String,
+    Enumeration { values: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeDefinition {
+    name: String,
+    object_type: AttributeObjectType,
+    value_type: AttributeValueType,
+    default: Option<AttributeValue>,
+}
+
+impl AttributeDefinition {
+    pub fn new(
+        name: String,
+        object_type: AttributeObjectType,
+        value_type: AttributeValueType,
+        default: Option<AttributeValue>,
+    ) -> Self {
+        Self {
+            name,
+            object_type,
+            value_type,
+            default,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn object_type(&self) -> AttributeObjectType {
+        self.object_type
+    }
+
+    pub fn value_type(&self) -> &AttributeValueType {
+        &self.value_type
+    }
+
+    pub fn default(&self) -> Option<&AttributeValue> {
+        self.default.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::u11;
+
+    use super::*;
+
+    #[test]
+    fn test_database_errors() {
+        let mut database = Database::default();
+
+        assert_ok!(database.add_node(Node::new(String::from("Engine"))));
+        assert_err_eq!(
+            database.add_node(Node::new(String::from("Engine"))),
+            DatabaseError::DuplicateNode
+        );
+
+        let message = Message::new(CANDBID::Extended(1), String::from("A"), 8, None);
+        assert_err_eq!(
+            database.add_message(message),
+            DatabaseError::IdentifierInvalid
+        );
+
+        let message = Message::new(CANDBID::Standard(u11::new(1)), String::from("A"), 8, None);
+        assert_ok!(database.add_message(message.clone()));
+        assert_err_eq!(
+            database.add_message(message),
+            DatabaseError::DuplicateMessage
+        );
+    }
+}
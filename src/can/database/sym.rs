@@ -0,0 +1,633 @@
+use std::collections::BTreeMap;
+
+use crate::can::{
+    database::{
+        Database, ImportError, ImportErrorKind, Message, Multiplexing, Signal, ValueDescriptions,
+        can_db_id,
+    },
+    signal::{ByteOrder, Scaling, SignalSpec, ValueType, msb0_to_dbc_start_bit},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Section {
+    Header,
+    Enums,
+    Signals,
+    Messages,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SignalDefinition {
+    line: usize,
+    length: usize,
+    byte_order: ByteOrder,
+    value_type: ValueType,
+    factor: f64,
+    offset: f64,
+    minimum: f64,
+    maximum: f64,
+    unit: String,
+    enumeration: Option<String>,
+}
+
+// A message section, several of which with the same name make up one multiplexed message.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Block {
+    line: usize,
+    name: String,
+    identifier: Option<u32>,
+    extended: bool,
+    length: Option<usize>,
+    multiplexor: Option<(String, usize, SignalDefinition, u64)>,
+    signals: Vec<(String, usize, SignalDefinition)>,
+}
+
+// Parses a PCAN symbol file.
+pub fn parse(text: &str) -> Result<Database, ImportError> {
+    let mut database = Database::default();
+    let mut section = Section::Header;
+    let mut enumerations: BTreeMap<String, ValueDescriptions> = BTreeMap::new();
+    let mut signal_definitions: BTreeMap<String, SignalDefinition> = BTreeMap::new();
+    let mut blocks: Vec<Block> = Vec::new();
+
+    // Enumerations may continue over several lines, up to their closing parenthesis.
+    let mut enumeration = String::new();
+    let mut enumeration_line = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('{')
+            .and_then(|line| line.strip_suffix('}'))
+        {
+            section = match name {
+                "ENUMS" => Section::Enums,
+                "SIGNALS" => Section::Signals,
+                "SEND" | "RECEIVE" | "SENDRECEIVE" => Section::Messages,
+                _ => return Err(error_at(line_number, ImportErrorKind::InvalidSyntax)),
+            };
+            continue;
+        }
+
+        match section {
+            Section::Header => {
+                if let Some(title) = line.strip_prefix("Title=") {
+                    database.set_comment(Some(String::from(title.trim_matches('"'))));
+                }
+            }
+            Section::Enums => {
+                if enumeration.is_empty() {
+                    enumeration_line = line_number;
+                }
+
+                enumeration.push_str(line);
+                enumeration.push(' ');
+
+                if line.ends_with(')') {
+                    let (name, value_descriptions) =
+                        parse_enumeration(&enumeration, enumeration_line)?;
+                    enumerations.insert(name, value_descriptions);
+                    enumeration.clear();
+                }
+            }
+            Section::Signals => {
+                let definition = line
+                    .strip_prefix("Sig=")
+                    .ok_or(error_at(line_number, ImportErrorKind::InvalidSyntax))?;
+                let fields = split_fields(definition);
+                let name = fields
+                    .first()
+                    .ok_or(error_at(line_number, ImportErrorKind::MissingAttribute))?;
+
+                signal_definitions.insert(
+                    name.clone(),
+                    parse_signal_definition(&fields[1..], line_number)?,
+                );
+            }
+            Section::Messages => {
+                if let Some(name) = line
+                    .strip_prefix('[')
+                    .and_then(|line| line.strip_suffix(']'))
+                {
+                    blocks.push(Block {
+                        line: line_number,
+                        name: String::from(name),
+                        ..Block::default()
+                    });
+                    continue;
+                }
+
+                let block = blocks
+                    .last_mut()
+                    .ok_or(error_at(line_number, ImportErrorKind::InvalidSyntax))?;
+                let (key, value) = line
+                    .split_once('=')
+                    .ok_or(error_at(line_number, ImportErrorKind::InvalidSyntax))?;
+
+                parse_block_line(block, key, value, &signal_definitions, line_number)?;
+            }
+        }
+    }
+
+    if !enumeration.is_empty() {
+        return Err(error_at(enumeration_line, ImportErrorKind::InvalidSyntax));
+    }
+
+    // Messages are kept with the line they start on, for errors adding them to the database.
+    let mut messages: Vec<(usize, Message)> = Vec::new();
+
+    for block in blocks {
+        let message = match messages
+            .iter_mut()
+            .find(|(_, message)| message.name() == block.name)
+        {
+            Some((_, message)) => message,
+            None => {
+                // Only the first section of a message needs its identifier.
+                let identifier = block
+                    .identifier
+                    .ok_or(error_at(block.line, ImportErrorKind::MissingAttribute))?;
+                let identifier = can_db_id(identifier, block.extended)
+                    .ok_or(error_at(block.line, ImportErrorKind::IdentifierOutOfRange))?;
+
+                let message = Message::new(
+                    identifier,
+                    block.name.clone(),
+                    block.length.unwrap_or(8),
+                    None,
+                );
+                messages.push((block.line, message));
+                &mut messages.last_mut().unwrap().1
+            }
+        };
+
+        let multiplexing = match &block.multiplexor {
+            Some((name, start_bit, definition, value)) => {
+                if message.signal(name).is_none() {
+                    let signal = build_signal(
+                        name,
+                        *start_bit,
+                        definition,
+                        Multiplexing::Multiplexor,
+                        &enumerations,
+                    )?;
+                    message.add_signal(signal).map_err(|error| {
+                        error_at(definition.line, ImportErrorKind::InvalidDatabase(error))
+                    })?;
+                }
+
+                Multiplexing::Multiplexed(*value)
+            }
+            None => Multiplexing::None,
+        };
+
+        for (name, start_bit, definition) in &block.signals {
+            let signal = build_signal(name, *start_bit, definition, multiplexing, &enumerations)?;
+            message.add_signal(signal).map_err(|error| {
+                error_at(definition.line, ImportErrorKind::InvalidDatabase(error))
+            })?;
+        }
+    }
+
+    for (line, message) in messages {
+        database
+            .add_message(message)
+            .map_err(|error| error_at(line, ImportErrorKind::InvalidDatabase(error)))?;
+    }
+
+    Ok(database)
+}
+
+fn parse_block_line(
+    block: &mut Block,
+    key: &str,
+    value: &str,
+    signal_definitions: &BTreeMap<String, SignalDefinition>,
+    line_number: usize,
+) -> Result<(), ImportError> {
+    let fields = split_fields(value);
+
+    match key {
+        // A range of identifiers can be given, of which the first is used.
+        "ID" => {
+            let identifier = value.split('-').next().unwrap_or_default();
+            let identifier = u32::try_from(parse_integer(identifier, line_number)?)
+                .map_err(|_| error_at(line_number, ImportErrorKind::IdentifierOutOfRange))?;
+            block.identifier = Some(identifier);
+        }
+        "Type" => block.extended = value.trim().eq_ignore_ascii_case("extended"),
+        "Len" => block.length = Some(parse_number(value, line_number)?),
+        "Sig" => {
+            let name = field(&fields, 0, line_number)?;
+            let definition = signal_definitions
+                .get(name)
+                .ok_or(error_at(line_number, ImportErrorKind::UnknownReference))?;
+            let start_bit = parse_number(field(&fields, 1, line_number)?, line_number)?;
+
+            block
+                .signals
+                .push((name.clone(), start_bit, definition.clone()));
+        }
+        "Var" => {
+            let name = field(&fields, 0, line_number)?;
+            let (start_bit, definition) = parse_inline_signal(&fields[1..], line_number)?;
+
+            block.signals.push((name.clone(), start_bit, definition));
+        }
+        // A multiplexor gives its start bit and length, then the value this section is for, then
+        // its flags.
+        "Mux" => {
+            let name = field(&fields, 0, line_number)?;
+            let mut definition_fields = vec![
+                String::from("unsigned"),
+                field(&fields, 1, line_number)?.clone(),
+            ];
+            definition_fields.extend(fields.iter().skip(3).cloned());
+
+            let (start_bit, definition) = parse_inline_signal(&definition_fields, line_number)?;
+            let value = parse_integer(field(&fields, 2, line_number)?, line_number)?;
+
+            block.multiplexor = Some((name.clone(), start_bit, definition, value));
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// An inline signal gives its type, then its start bit and length, then its flags.
+fn parse_inline_signal(
+    fields: &[String],
+    line_number: usize,
+) -> Result<(usize, SignalDefinition), ImportError> {
+    let value_type = field(fields, 0, line_number)?;
+    let (start_bit, length) = field(fields, 1, line_number)?
+        .split_once(',')
+        .ok_or(error_at(line_number, ImportErrorKind::MissingAttribute))?;
+
+    let mut definition_fields = vec![value_type.clone(), String::from(length)];
+    definition_fields.extend(fields[2..].iter().cloned());
+
+    Ok((
+        parse_number(start_bit, line_number)?,
+        parse_signal_definition(&definition_fields, line_number)?,
+    ))
+}
+
+// A signal definition gives its type, then its length if it is not implied by the type, then
+// its flags.
+fn parse_signal_definition(
+    fields: &[String],
+    line_number: usize,
+) -> Result<SignalDefinition, ImportError> {
+    let (value_type, default_length) = match field(fields, 0, line_number)?.as_str() {
+        "unsigned" | "char" | "string" | "raw" => (ValueType::Unsigned, None),
+        "bit" => (ValueType::Unsigned, Some(1)),
+        "signed" => (ValueType::Signed, None),
+        "float" => (ValueType::Float32, Some(32)),
+        "double" => (ValueType::Float64, Some(64)),
+        _ => return Err(error_at(line_number, ImportErrorKind::InvalidValueType)),
+    };
+
+    let mut flags = &fields[1..];
+
+    let length = match flags.first() {
+        Some(length) if !length.starts_with(['-', '/']) => {
+            flags = &flags[1..];
+            parse_number(length, line_number)?
+        }
+        _ => default_length.ok_or(error_at(line_number, ImportErrorKind::MissingAttribute))?,
+    };
+
+    let mut definition = SignalDefinition {
+        line: line_number,
+        length,
+        byte_order: ByteOrder::LittleEndian,
+        value_type,
+        factor: 1.0,
+        offset: 0.0,
+        minimum: 0.0,
+        maximum: 0.0,
+        unit: String::new(),
+        enumeration: None,
+    };
+
+    for flag in flags {
+        if flag == "-m" {
+            definition.byte_order = ByteOrder::BigEndian;
+        } else if let Some(unit) = flag.strip_prefix("/u:") {
+            definition.unit = String::from(unit);
+        } else if let Some(factor) = flag.strip_prefix("/f:") {
+            definition.factor = parse_number(factor, line_number)?;
+        } else if let Some(offset) = flag.strip_prefix("/o:") {
+            definition.offset = parse_number(offset, line_number)?;
+        } else if let Some(minimum) = flag.strip_prefix("/min:") {
+            definition.minimum = parse_number(minimum, line_number)?;
+        } else if let Some(maximum) = flag.strip_prefix("/max:") {
+            definition.maximum = parse_number(maximum, line_number)?;
+        } else if let Some(enumeration) = flag.strip_prefix("/e:") {
+            definition.enumeration = Some(String::from(enumeration));
+        }
+    }
+
+    Ok(definition)
+}
+
+// Big endian signals are placed by their most significant bit, counted from the most
+// significant bit of the first byte.
+fn build_signal(
+    name: &str,
+    start_bit: usize,
+    definition: &SignalDefinition,
+    multiplexing: Multiplexing,
+    enumerations: &BTreeMap<String, ValueDescriptions>,
+) -> Result<Signal, ImportError> {
+    let start_bit = match definition.byte_order {
+        ByteOrder::LittleEndian => start_bit,
+        ByteOrder::BigEndian => msb0_to_dbc_start_bit(start_bit),
+    };
+
+    let spec = SignalSpec::new(
+        start_bit,
+        definition.length,
+        definition.byte_order,
+        definition.value_type,
+        Scaling::new(definition.factor, definition.offset),
+        definition.minimum..=definition.maximum,
+    )
+    .map_err(|error| error_at(definition.line, ImportErrorKind::InvalidSignal(error)))?;
+
+    let mut signal = Signal::new(
+        String::from(name),
+        spec,
+        definition.unit.clone(),
+        Vec::new(),
+        multiplexing,
+    );
+
+    if let Some(enumeration) = &definition.enumeration {
+        let value_descriptions = enumerations
+            .get(enumeration)
+            .ok_or(error_at(definition.line, ImportErrorKind::UnknownReference))?;
+        signal.set_value_descriptions(value_descriptions.clone());
+    }
+
+    Ok(signal)
+}
+
+// Enumerations are written as enum Name(0="Label", 1="Label").
+fn parse_enumeration(
+    text: &str,
+    line_number: usize,
+) -> Result<(String, ValueDescriptions), ImportError> {
+    let (name, values) = text
+        .trim()
+        .strip_prefix("enum")
+        .and_then(|text| text.trim().strip_suffix(')'))
+        .and_then(|text| text.split_once('('))
+        .ok_or(error_at(line_number, ImportErrorKind::InvalidSyntax))?;
+
+    let mut value_descriptions = ValueDescriptions::new();
+
+    for entry in split_outside_quotes(values, ',') {
+        let Some((value, description)) = entry.split_once('=') else {
+            continue;
+        };
+
+        value_descriptions.insert(
+            parse_number(value, line_number)?,
+            String::from(description.trim().trim_matches('"')),
+        );
+    }
+
+    Ok((String::from(name.trim()), value_descriptions))
+}
+
+fn field(fields: &[String], index: usize, line_number: usize) -> Result<&String, ImportError> {
+    fields
+        .get(index)
+        .ok_or(error_at(line_number, ImportErrorKind::MissingAttribute))
+}
+
+// Fields are separated by whitespace, and quotes keep whitespace within a field.
+fn split_fields(text: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+
+    for character in text.chars() {
+        match character {
+            '"' => quoted = !quoted,
+            character if character.is_whitespace() && !quoted => {
+                if !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                }
+            }
+            character => field.push(character),
+        }
+    }
+
+    if !field.is_empty() {
+        fields.push(field);
+    }
+
+    fields
+}
+
+fn split_outside_quotes(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+
+    for (index, character) in text.char_indices() {
+        if character == '"' {
+            quoted = !quoted;
+        } else if character == separator && !quoted {
+            parts.push(&text[start..index]);
+            start = index + 1;
+        }
+    }
+
+    parts.push(&text[start..]);
+
+    parts
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+
+    for (index, character) in line.char_indices() {
+        if character == '"' {
+            quoted = !quoted;
+        } else if !quoted && line[index..].starts_with("//") {
+            return &line[..index];
+        }
+    }
+
+    line
+}
+
+// Integers are decimal, or hexadecimal with an h suffix.
+fn parse_integer(text: &str, line_number: usize) -> Result<u64, ImportError> {
+    let text = text.trim();
+
+    match text.strip_suffix(['h', 'H']) {
+        Some(hexadecimal) => u64::from_str_radix(hexadecimal, 16)
+            .map_err(|_| error_at(line_number, ImportErrorKind::InvalidNumber)),
+        None => parse_number(text, line_number),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(text: &str, line_number: usize) -> Result<T, ImportError> {
+    text.trim()
+        .parse()
+        .map_err(|_| error_at(line_number, ImportErrorKind::InvalidNumber))
+}
+
+fn error_at(line_number: usize, kind: ImportErrorKind) -> ImportError {
+    ImportError::new(line_number, 1, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok, assert_some, assert_some_eq};
+    use ux::{u11, u29};
+
+    use crate::can::{can_db_id::CANDBID, can_id::CANID};
+
+    use super::*;
+
+    const SYM: &str = r#"FormatVersion=6.0 // Do not edit this line!
+Title="Example network"
+
+{ENUMS}
+enum Gear(0="Park", 1="Reverse",
+  2="Neutral", 3="Drive")
+
+{SIGNALS}
+Sig="Speed" unsigned 16 /u:rpm /f:0.25 /max:16383.75
+Sig="Temperature" signed 8 -m /u:degC /o:-40 /min:-40 /max:87
+Sig="Gear" unsigned 2 /e:Gear
+Sig="Voltage" float 32 /u:V
+
+{SENDRECEIVE}
+
+[EngineStatus]
+ID=100h
+Len=4
+Sig="Speed" 0
+Sig="Temperature" 16
+Sig="Gear" 24
+CycleTime=10
+
+[Diagnostics]
+ID=200h
+Type=Extended
+Len=8
+Mux=Mode 0,8 0
+Sig="Voltage" 8
+
+[Diagnostics]
+Len=8
+Mux=Mode 0,8 1
+Var=Current signed 8,16 /u:A /f:0.1 /min:-100 /max:100
+"#;
+
+    #[test]
+    fn test_parse() {
+        let database = assert_ok!(parse(SYM));
+
+        assert_eq!(database.comment(), Some("Example network"));
+
+        let engine_status = assert_some!(database.message(&CANDBID::Standard(u11::new(0x100))));
+        assert_eq!(engine_status.name(), "EngineStatus");
+        assert_eq!(engine_status.size(), 4);
+
+        let speed = assert_some!(engine_status.signal("Speed"));
+        assert_eq!(speed.spec().factor(), 0.25);
+        assert_eq!(speed.spec().maximum(), 16383.75);
+        assert_eq!(speed.unit(), "rpm");
+
+        let temperature = assert_some!(engine_status.signal("Temperature"));
+        assert_eq!(temperature.spec().start_bit(), 23);
+        assert_eq!(temperature.spec().byte_order(), ByteOrder::BigEndian);
+        assert_eq!(temperature.spec().offset(), -40.0);
+
+        let gear = assert_some!(engine_status.signal("Gear"));
+        assert_eq!(gear.value_descriptions().len(), 4);
+        assert_some_eq!(gear.value_descriptions().get(&2), "Neutral");
+
+        let diagnostics = assert_some!(database.message_by_name("Diagnostics"));
+        assert_eq!(diagnostics.identifier(), CANDBID::Extended(0x80000200));
+
+        let names: Vec<&str> = diagnostics.signals().iter().map(Signal::name).collect();
+        assert_eq!(names, vec!["Mode", "Voltage", "Current"]);
+        assert_eq!(assert_some!(diagnostics.multiplexor()).name(), "Mode");
+
+        let voltage = assert_some!(diagnostics.signal("Voltage"));
+        assert_eq!(voltage.multiplexing(), Multiplexing::Multiplexed(0));
+        assert_eq!(voltage.spec().value_type(), ValueType::Float32);
+
+        let current = assert_some!(diagnostics.signal("Current"));
+        assert_eq!(current.multiplexing(), Multiplexing::Multiplexed(1));
+        assert_eq!(current.spec().value_type(), ValueType::Signed);
+
+        let decoded_message = assert_ok!(
+            database.decode(CANID::Standard(u11::new(0x100)), &[0x40, 0x1F, 0x5A, 0x03])
+        );
+        let decoded_values: Vec<f64> = decoded_message
+            .signals()
+            .iter()
+            .map(|signal| signal.value())
+            .collect();
+        assert_eq!(decoded_values, vec![2000.0, 50.0, 3.0]);
+
+        let decoded_message = assert_ok!(database.decode(
+            CANID::Extended(u29::new(0x200)),
+            &[0x01, 0x9C, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00]
+        ));
+        assert_eq!(
+            assert_some!(decoded_message.signal("Current")).value(),
+            -10.0
+        );
+    }
+
+    #[test]
+    fn test_split_fields() {
+        assert_eq!(
+            split_fields("\"Engine Speed\" unsigned 16 /u:\"km/h\""),
+            vec!["Engine Speed", "unsigned", "16", "/u:km/h"]
+        );
+        assert_eq!(strip_comment("Title=\"a // b\" // c"), "Title=\"a // b\" ");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_err_eq!(
+            parse("{SENDRECEIVE}\n[A]\nLen=8\nSig=B 0"),
+            ImportError::new(4, 1, ImportErrorKind::UnknownReference)
+        );
+        assert_err_eq!(
+            parse("{SENDRECEIVE}\n[A]\nID=800h\nLen=8"),
+            ImportError::new(2, 1, ImportErrorKind::IdentifierOutOfRange)
+        );
+        assert_err_eq!(
+            parse("{SENDRECEIVE}\n[A]\nLen=8"),
+            ImportError::new(2, 1, ImportErrorKind::MissingAttribute)
+        );
+        assert_err_eq!(
+            parse("{SIGNALS}\nSig=A number 8"),
+            ImportError::new(2, 1, ImportErrorKind::InvalidValueType)
+        );
+        assert_err_eq!(
+            parse("{ENUMS}\nenum A(0=\"B\""),
+            ImportError::new(2, 1, ImportErrorKind::InvalidSyntax)
+        );
+    }
+}
@@ -0,0 +1,36 @@
+pub(crate) const DBC: &str = r#"VERSION ""
+
+BU_: Engine Dashboard
+
+BO_ 256 EngineStatus: 4 Engine
+ SG_ Speed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dashboard
+ SG_ Temperature : 23|8@0- (1,-40) [-40|87] "degC" Dashboard
+ SG_ Gear : 24|2@1+ (1,0) [0|3] "" Dashboard
+
+BO_ 2147484160 Diagnostics: 8 Engine
+ SG_ Mode M : 0|8@1+ (1,0) [0|0] "" Dashboard
+ SG_ Voltage m0 : 8|16@1+ (0.01,0) [0|0] "V" Dashboard
+ SG_ Current m1 : 8|16@1- (0.1,0) [-100|100] "A" Dashboard
+
+VAL_ 256 Gear 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+VAL_ 256 Temperature -1 "Error" ;
+"#;
+
+// Counter and Voltage share a multiplexed value, but under different multiplexors.
+pub(crate) const NESTED_DBC: &str = r#"BO_ 1024 Diagnostics: 4 Tester
+ SG_ Service M : 0|8@1+ (1,0) [0|0] "" Engine
+ SG_ Session m1M : 8|8@1+ (1,0) [0|0] "" Engine
+ SG_ Counter m2 : 8|8@1+ (1,0) [0|0] "" Engine
+ SG_ Voltage m2 : 16|16@1+ (0.01,0) [0|0] "V" Engine
+ SG_ Current m5 : 16|16@1- (0.1,0) [-100|100] "A" Engine
+
+SG_MUL_VAL_ 1024 Session Service 1-1;
+SG_MUL_VAL_ 1024 Counter Service 2-2;
+SG_MUL_VAL_ 1024 Voltage Session 2-3;
+SG_MUL_VAL_ 1024 Current Session 5-5;
+"#;
+
+// Without extended multiplexing, either multiplexor could select the voltage.
+pub(crate) fn ambiguous_nested_dbc() -> &'static str {
+    NESTED_DBC.split("\n\n").next().unwrap_or_default()
+}
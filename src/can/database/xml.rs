@@ -0,0 +1,78 @@
+use std::str::FromStr;
+
+use roxmltree::{Document, Node};
+
+use crate::can::database::{ImportError, ImportErrorKind};
+
+// Helpers shared by the XML based importers, which look elements up by their local name and
+// report errors at the start of the element.
+
+pub(crate) fn parse_document(text: &str) -> Result<Document<'_>, ImportError> {
+    Document::parse(text).map_err(|error| {
+        let position = error.pos();
+        ImportError::new(
+            position.row as usize,
+            position.col as usize,
+            ImportErrorKind::InvalidSyntax,
+        )
+    })
+}
+
+pub(crate) fn error_at(node: Node, kind: ImportErrorKind) -> ImportError {
+    let position = node.document().text_pos_at(node.range().start);
+
+    ImportError::new(position.row as usize, position.col as usize, kind)
+}
+
+pub(crate) fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+pub(crate) fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+pub(crate) fn descendant<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &str,
+) -> Option<Node<'a, 'input>> {
+    node.descendants()
+        .find(|descendant| descendant.is_element() && descendant.tag_name().name() == name)
+}
+
+pub(crate) fn required_child<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &str,
+) -> Result<Node<'a, 'input>, ImportError> {
+    child(node, name).ok_or(error_at(node, ImportErrorKind::MissingElement))
+}
+
+pub(crate) fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, ImportError> {
+    node.attribute(name)
+        .ok_or(error_at(node, ImportErrorKind::MissingAttribute))
+}
+
+pub(crate) fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or_default().trim()
+}
+
+pub(crate) fn parse_number<T: FromStr>(node: Node, text: &str) -> Result<T, ImportError> {
+    text.trim()
+        .parse()
+        .map_err(|_| error_at(node, ImportErrorKind::InvalidNumber))
+}
+
+// Identifiers are written in decimal or, with a 0x prefix, in hexadecimal.
+pub(crate) fn parse_identifier(node: Node, text: &str) -> Result<u32, ImportError> {
+    let text = text.trim();
+
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hexadecimal) => u32::from_str_radix(hexadecimal, 16)
+            .map_err(|_| error_at(node, ImportErrorKind::InvalidNumber)),
+        None => parse_number(node, text),
+    }
+}
@@ -0,0 +1,98 @@
+use ux::u4;
+
+const CLASSIC_MAXIMUM_DATA_FIELD_LENGTH: usize = 8;
+const FD_DATA_FIELD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DLC(u4);
+
+impl DLC {
+    pub fn new(raw: u4) -> Self {
+        Self(raw)
+    }
+
+    pub fn raw(&self) -> u4 {
+        self.0
+    }
+
+    pub fn from_len_classic(data_field_length: usize) -> Option<Self> {
+        if data_field_length > CLASSIC_MAXIMUM_DATA_FIELD_LENGTH {
+            return None;
+        }
+
+        Some(Self(u4::new(data_field_length as u8)))
+    }
+
+    pub fn from_len_fd(data_field_length: usize) -> Option<Self> {
+        // Lengths between the valid CAN FD lengths are rounded up, as the data field is padded.
+        FD_DATA_FIELD_LENGTHS
+            .iter()
+            .position(|fd_data_field_length| *fd_data_field_length >= data_field_length)
+            .map(|data_length_code| Self(u4::new(data_length_code as u8)))
+    }
+
+    pub fn to_len_classic(&self) -> usize {
+        // A classic DLC above eight still means eight bytes.
+        usize::from(u8::from(self.0)).min(CLASSIC_MAXIMUM_DATA_FIELD_LENGTH)
+    }
+
+    pub fn to_len_fd(&self) -> usize {
+        FD_DATA_FIELD_LENGTHS[usize::from(u8::from(self.0))]
+    }
+}
+
+impl From<u4> for DLC {
+    fn from(value: u4) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<DLC> for u4 {
+    fn from(value: DLC) -> Self {
+        value.raw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_len_classic() {
+        assert_eq!(DLC::new(u4::new(0)).to_len_classic(), 0);
+        assert_eq!(DLC::new(u4::new(8)).to_len_classic(), 8);
+        assert_eq!(DLC::new(u4::new(9)).to_len_classic(), 8);
+        assert_eq!(DLC::new(u4::new(15)).to_len_classic(), 8);
+    }
+
+    #[test]
+    fn test_to_len_fd() {
+        let fd_data_field_lengths = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+        for (data_length_code, data_field_length) in fd_data_field_lengths.into_iter().enumerate()
+        {
+            assert_eq!(
+                DLC::new(u4::new(data_length_code as u8)).to_len_fd(),
+                data_field_length
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_len_classic() {
+        assert_eq!(DLC::from_len_classic(0), Some(DLC::new(u4::new(0))));
+        assert_eq!(DLC::from_len_classic(8), Some(DLC::new(u4::new(8))));
+        assert_eq!(DLC::from_len_classic(9), None);
+    }
+
+    #[test]
+    fn test_from_len_fd() {
+        assert_eq!(DLC::from_len_fd(8), Some(DLC::new(u4::new(8))));
+        assert_eq!(DLC::from_len_fd(9), Some(DLC::new(u4::new(9))));
+        assert_eq!(DLC::from_len_fd(12), Some(DLC::new(u4::new(9))));
+        assert_eq!(DLC::from_len_fd(13), Some(DLC::new(u4::new(10))));
+        assert_eq!(DLC::from_len_fd(33), Some(DLC::new(u4::new(14))));
+        assert_eq!(DLC::from_len_fd(64), Some(DLC::new(u4::new(15))));
+        assert_eq!(DLC::from_len_fd(65), None);
+    }
+}
@@ -0,0 +1,772 @@
+use std::{collections::BTreeSet, error::Error};
+
+use strum::Display;
+use ux::{u11, u29};
+
+use crate::can::can_id::CANID;
+
+const STANDARD_IDENTIFIER_MASK: u32 = 0x7FF;
+const EXTENDED_IDENTIFIER_MASK: u32 = 0x1FFFFFFF;
+// A single FDCAN range element beats a dual element from this many consecutive identifiers.
+const MINIMUM_RANGE_LENGTH: u32 = 3;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CANFilterError {
+    IdentifierTypeMismatch,
+    MaskTooWide,
+    RangeInverted,
+    TooManyFilterBanks,
+    TooManyStandardFilters,
+    TooManyExtendedFilters,
+}
+
+impl Error for CANFilterError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CANFilter {
+    // The identifier bits that are set in the mask must match, and the rest are ignored.
+    Mask { identifier: CANID, mask: u32 },
+    Range { first: CANID, last: CANID },
+    Dual { first: CANID, second: CANID },
+}
+
+impl CANFilter {
+    pub fn new_mask(identifier: CANID, mask: u32) -> Result<Self, CANFilterError> {
+        if mask & !identifier_mask(identifier) != 0 {
+            return Err(CANFilterError::MaskTooWide);
+        }
+
+        Ok(Self::Mask { identifier, mask })
+    }
+
+    pub fn new_exact(identifier: CANID) -> Self {
+        Self::Mask {
+            identifier,
+            mask: identifier_mask(identifier),
+        }
+    }
+
+    pub fn new_range(first: CANID, last: CANID) -> Result<Self, CANFilterError> {
+        if !is_same_identifier_type(first, last) {
+            return Err(CANFilterError::IdentifierTypeMismatch);
+        }
+
+        if raw_identifier(first) > raw_identifier(last) {
+            return Err(CANFilterError::RangeInverted);
+        }
+
+        Ok(Self::Range { first, last })
+    }
+
+    pub fn new_dual(first: CANID, second: CANID) -> Self {
+        Self::Dual { first, second }
+    }
+
+    pub fn matches(&self, identifier: &CANID) -> bool {
+        match self {
+            Self::Mask {
+                identifier: filter_identifier,
+                mask,
+            } => {
+                is_same_identifier_type(*filter_identifier, *identifier)
+                    && (raw_identifier(*filter_identifier) ^ raw_identifier(*identifier)) & mask
+                        == 0
+            }
+            Self::Range { first, last } => {
+                is_same_identifier_type(*first, *identifier)
+                    && (raw_identifier(*first)..=raw_identifier(*last))
+                        .contains(&raw_identifier(*identifier))
+            }
+            Self::Dual { first, second } => first == identifier || second == identifier,
+        }
+    }
+}
+
+// The bxCAN filter bank layouts. The 16-bit scale only holds standard identifiers, and unused
+// slots repeat a used one, as the hardware has no way to leave them empty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BxCANFilterBank {
+    Mask32 { identifier: CANID, mask: u32 },
+    List32 { identifiers: [CANID; 2] },
+    Mask16 { filters: [(u11, u11); 2] },
+    List16 { identifiers: [u11; 4] },
+}
+
+impl BxCANFilterBank {
+    pub fn matches(&self, identifier: &CANID) -> bool {
+        match self {
+            Self::Mask32 {
+                identifier: filter_identifier,
+                mask,
+            } => CANFilter::Mask {
+                identifier: *filter_identifier,
+                mask: *mask,
+            }
+            .matches(identifier),
+            Self::List32 { identifiers } => identifiers.contains(identifier),
+            Self::Mask16 { filters } => filters.iter().any(|(filter_identifier, mask)| {
+                CANFilter::Mask {
+                    identifier: CANID::Standard(*filter_identifier),
+                    mask: u32::from(*mask),
+                }
+                .matches(identifier)
+            }),
+            Self::List16 { identifiers } => match identifier {
+                CANID::Standard(identifier) => identifiers.contains(identifier),
+                CANID::Extended(_) => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FilterBank {
+    BxCAN(BxCANFilterBank),
+    // Each FDCAN filter element is a range, dual or mask filter over one identifier type.
+    FDCAN(CANFilter),
+}
+
+impl FilterBank {
+    pub fn matches(&self, identifier: &CANID) -> bool {
+        match self {
+            Self::BxCAN(bx_can_filter_bank) => bx_can_filter_bank.matches(identifier),
+            Self::FDCAN(can_filter) => can_filter.matches(identifier),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FilterController {
+    BxCAN {
+        bank_count: usize,
+    },
+    FDCAN {
+        standard_filter_count: usize,
+        extended_filter_count: usize,
+    },
+}
+
+impl FilterController {
+    pub fn stm32f4_bxcan() -> Self {
+        Self::BxCAN { bank_count: 14 }
+    }
+
+    pub fn stm32g4_fdcan() -> Self {
+        Self::FDCAN {
+            standard_filter_count: 28,
+            extended_filter_count: 8,
+        }
+    }
+
+    // Filter banks that accept exactly the given identifiers. On bxCAN, identifiers that differ
+    // only in some bits share a mask and, on FDCAN, consecutive identifiers share a range.
+    pub fn compile(&self, identifiers: &[CANID]) -> Result<Vec<FilterBank>, CANFilterError> {
+        let standard_identifiers: BTreeSet<u32> = identifiers
+            .iter()
+            .filter_map(|identifier| match identifier {
+                CANID::Standard(identifier) => Some(u32::from(*identifier)),
+                CANID::Extended(_) => None,
+            })
+            .collect();
+        let extended_identifiers: BTreeSet<u32> = identifiers
+            .iter()
+            .filter_map(|identifier| match identifier {
+                CANID::Standard(_) => None,
+                CANID::Extended(identifier) => Some(u32::from(*identifier)),
+            })
+            .collect();
+
+        match self {
+            Self::BxCAN { bank_count } => {
+                let bx_can_filter_banks =
+                    compile_bx_can(&standard_identifiers, &extended_identifiers);
+
+                if bx_can_filter_banks.len() > *bank_count {
+                    return Err(CANFilterError::TooManyFilterBanks);
+                }
+
+                Ok(bx_can_filter_banks
+                    .into_iter()
+                    .map(FilterBank::BxCAN)
+                    .collect())
+            }
+            Self::FDCAN {
+                standard_filter_count,
+                extended_filter_count,
+            } => {
+                let standard_filters = compile_fd_can(&standard_identifiers, standard_identifier);
+                let extended_filters = compile_fd_can(&extended_identifiers, extended_identifier);
+
+                if standard_filters.len() > *standard_filter_count {
+                    return Err(CANFilterError::TooManyStandardFilters);
+                }
+
+                if extended_filters.len() > *extended_filter_count {
+                    return Err(CANFilterError::TooManyExtendedFilters);
+                }
+
+                Ok(standard_filters
+                    .into_iter()
+                    .chain(extended_filters)
+                    .map(FilterBank::FDCAN)
+                    .collect())
+            }
+        }
+    }
+}
+
+fn compile_bx_can(
+    standard_identifiers: &BTreeSet<u32>,
+    extended_identifiers: &BTreeSet<u32>,
+) -> Vec<BxCANFilterBank> {
+    let (mut standard_blocks, mut standard_singles) =
+        merged_blocks(standard_identifiers, STANDARD_IDENTIFIER_MASK);
+    let (extended_blocks, mut extended_singles) =
+        merged_blocks(extended_identifiers, EXTENDED_IDENTIFIER_MASK);
+    let is_standard_pair_block =
+        |(_, mask): &(u32, u32)| (STANDARD_IDENTIFIER_MASK & !mask).count_ones() == 1;
+
+    // A block of two standard identifiers takes half a 16-bit mask bank, but its identifiers fit
+    // in half a 16-bit list bank too, so try each number of split blocks and keep the fewest banks.
+    let pair_block_count = standard_blocks
+        .iter()
+        .filter(|block| is_standard_pair_block(block))
+        .count();
+    let split_pair_block_count = (0..=pair_block_count)
+        .min_by_key(|split_pair_block_count| {
+            standard_bank_count(
+                standard_blocks.len() - split_pair_block_count,
+                standard_singles.len() + 2 * split_pair_block_count,
+            )
+        })
+        .unwrap_or(0);
+
+    for _ in 0..split_pair_block_count {
+        let index = standard_blocks
+            .iter()
+            .position(is_standard_pair_block)
+            .unwrap();
+        let (identifier, mask) = standard_blocks.remove(index);
+        standard_singles.extend([identifier, identifier | (STANDARD_IDENTIFIER_MASK & !mask)]);
+    }
+    standard_singles.sort();
+    standard_singles.dedup();
+
+    let mut bx_can_filter_banks = Vec::new();
+
+    for standard_block_pair in standard_blocks.chunks(2) {
+        let mut filters: Vec<(u11, u11)> = standard_block_pair
+            .iter()
+            .map(|(identifier, mask)| (u11::new(*identifier as u16), u11::new(*mask as u16)))
+            .collect();
+
+        if filters.len() == 1 {
+            let filter = match standard_singles.pop() {
+                Some(single) => (
+                    u11::new(single as u16),
+                    u11::new(STANDARD_IDENTIFIER_MASK as u16),
+                ),
+                None => filters[0],
+            };
+            filters.push(filter);
+        }
+
+        bx_can_filter_banks.push(BxCANFilterBank::Mask16 {
+            filters: [filters[0], filters[1]],
+        });
+    }
+
+    // A last standard identifier on its own can share a 32-bit list bank with a last extended one.
+    let shared_standard_single = (standard_singles.len() % 4 == 1
+        && extended_singles.len() % 2 == 1)
+        .then(|| standard_singles.pop())
+        .flatten();
+
+    for standard_singles in standard_singles.chunks(4) {
+        let identifiers: Vec<u11> = (0..4)
+            .map(|index| u11::new(standard_singles[index.min(standard_singles.len() - 1)] as u16))
+            .collect();
+
+        bx_can_filter_banks.push(BxCANFilterBank::List16 {
+            identifiers: [
+                identifiers[0],
+                identifiers[1],
+                identifiers[2],
+                identifiers[3],
+            ],
+        });
+    }
+
+    for (identifier, mask) in extended_blocks {
+        bx_can_filter_banks.push(BxCANFilterBank::Mask32 {
+            identifier: extended_identifier(identifier),
+            mask,
+        });
+    }
+
+    let last_extended_single = (extended_singles.len() % 2 == 1)
+        .then(|| extended_singles.pop())
+        .flatten();
+
+    for extended_single_pair in extended_singles.chunks(2) {
+        bx_can_filter_banks.push(BxCANFilterBank::List32 {
+            identifiers: [
+                extended_identifier(extended_single_pair[0]),
+                extended_identifier(extended_single_pair[1]),
+            ],
+        });
+    }
+
+    if let Some(last_extended_single) = last_extended_single {
+        let last_extended_identifier = extended_identifier(last_extended_single);
+
+        bx_can_filter_banks.push(BxCANFilterBank::List32 {
+            identifiers: [
+                last_extended_identifier,
+                shared_standard_single.map_or(last_extended_identifier, standard_identifier),
+            ],
+        });
+    }
+
+    bx_can_filter_banks
+}
+
+fn standard_bank_count(block_count: usize, single_count: usize) -> usize {
+    // An odd block leaves a slot in its 16-bit mask bank for a single identifier.
+    let mask_single_count = (block_count % 2).min(single_count);
+
+    block_count.div_ceil(2) + (single_count - mask_single_count).div_ceil(4)
+}
+
+fn compile_fd_can(identifiers: &BTreeSet<u32>, to_can_id: fn(u32) -> CANID) -> Vec<CANFilter> {
+    let mut can_filters = Vec::new();
+    let mut singles = Vec::new();
+
+    for (first, last) in consecutive_runs(identifiers) {
+        if last - first + 1 >= MINIMUM_RANGE_LENGTH {
+            can_filters.push(CANFilter::Range {
+                first: to_can_id(first),
+                last: to_can_id(last),
+            });
+        } else {
+            singles.extend(first..=last);
+        }
+    }
+
+    for single_pair in singles.chunks(2) {
+        can_filters.push(CANFilter::Dual {
+            first: to_can_id(single_pair[0]),
+            second: to_can_id(single_pair[single_pair.len() - 1]),
+        });
+    }
+
+    can_filters
+}
+
+// The identifiers as (identifier, mask) blocks that each accept only given identifiers, with
+// blocks of one returned apart. Like Quine-McCluskey, blocks with the same mask that differ in one
+// bit merge until no more can, then the largest merged blocks are picked until all are covered.
+// Picking the fewest blocks is not guaranteed.
+fn merged_blocks(identifiers: &BTreeSet<u32>, identifier_mask: u32) -> (Vec<(u32, u32)>, Vec<u32>) {
+    let aligned_blocks = aligned_blocks(identifiers, identifier_mask);
+
+    let mut prime_blocks = Vec::new();
+    let mut level: BTreeSet<(u32, u32)> = aligned_blocks.iter().copied().collect();
+
+    while !level.is_empty() {
+        let mut next_level = BTreeSet::new();
+        let mut merged = BTreeSet::new();
+
+        for (identifier, mask) in &level {
+            for bit in (0..u32::BITS).map(|index| 1 << index).filter(|bit| mask & bit != 0) {
+                if level.contains(&(identifier ^ bit, *mask)) {
+                    merged.insert((*identifier, *mask));
+                    next_level.insert((identifier & !bit, mask & !bit));
+                }
+            }
+        }
+
+        prime_blocks.extend(level.difference(&merged).copied());
+        level = next_level;
+    }
+
+    prime_blocks.sort_by_key(|(_, mask)| mask.count_ones());
+
+    let mut uncovered_blocks = aligned_blocks;
+    let mut blocks = Vec::new();
+    let mut singles = Vec::new();
+
+    for (identifier, mask) in prime_blocks {
+        let uncovered_block_count = uncovered_blocks.len();
+        uncovered_blocks.retain(|(uncovered_identifier, uncovered_mask)| {
+            mask & !uncovered_mask != 0 || (identifier ^ uncovered_identifier) & mask != 0
+        });
+
+        if uncovered_blocks.len() == uncovered_block_count {
+            continue;
+        }
+
+        if mask == identifier_mask {
+            singles.push(identifier);
+        } else {
+            blocks.push((identifier, mask));
+        }
+    }
+
+    blocks.sort();
+    singles.sort();
+
+    (blocks, singles)
+}
+
+// The identifiers split into the largest blocks of consecutive identifiers that a mask can cover
+// exactly.
+fn aligned_blocks(identifiers: &BTreeSet<u32>, identifier_mask: u32) -> Vec<(u32, u32)> {
+    let mut blocks = Vec::new();
+
+    for (first, last) in consecutive_runs(identifiers) {
+        let mut block_start = first;
+
+        while block_start <= last {
+            let mut block_length = 1;
+            while block_start % (2 * block_length) == 0
+                && block_start + 2 * block_length - 1 <= last
+            {
+                block_length *= 2;
+            }
+
+            blocks.push((block_start, block_mask(identifier_mask, block_length)));
+
+            block_start += block_length;
+        }
+    }
+
+    blocks
+}
+
+fn consecutive_runs(identifiers: &BTreeSet<u32>) -> Vec<(u32, u32)> {
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+
+    for identifier in identifiers {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == *identifier => *last = *identifier,
+            _ => runs.push((*identifier, *identifier)),
+        }
+    }
+
+    runs
+}
+
+fn block_mask(identifier_mask: u32, block_length: u32) -> u32 {
+    identifier_mask & !(block_length - 1)
+}
+
+fn standard_identifier(identifier: u32) -> CANID {
+    CANID::Standard(u11::new(identifier as u16))
+}
+
+fn extended_identifier(identifier: u32) -> CANID {
+    CANID::Extended(u29::new(identifier))
+}
+
+fn identifier_mask(identifier: CANID) -> u32 {
+    match identifier {
+        CANID::Standard(_) => STANDARD_IDENTIFIER_MASK,
+        CANID::Extended(_) => EXTENDED_IDENTIFIER_MASK,
+    }
+}
+
+fn raw_identifier(identifier: CANID) -> u32 {
+    match identifier {
+        CANID::Standard(identifier) => u32::from(identifier),
+        CANID::Extended(identifier) => u32::from(identifier),
+    }
+}
+
+fn is_same_identifier_type(first: CANID, second: CANID) -> bool {
+    matches!(
+        (first, second),
+        (CANID::Standard(_), CANID::Standard(_)) | (CANID::Extended(_), CANID::Extended(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+
+    use super::*;
+
+    fn accepted_standard_identifiers(filter_banks: &[FilterBank]) -> Vec<u16> {
+        (0..=0x7FF)
+            .filter(|identifier| {
+                let identifier = CANID::Standard(u11::new(*identifier));
+                filter_banks
+                    .iter()
+                    .any(|filter_bank| filter_bank.matches(&identifier))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mask_filter() {
+        let can_filter = assert_ok!(CANFilter::new_mask(CANID::Standard(u11::new(0x120)), 0x7F0));
+
+        assert!(can_filter.matches(&CANID::Standard(u11::new(0x120))));
+        assert!(can_filter.matches(&CANID::Standard(u11::new(0x12F))));
+        assert!(!can_filter.matches(&CANID::Standard(u11::new(0x130))));
+        assert!(!can_filter.matches(&CANID::Extended(u29::new(0x120))));
+
+        let can_filter = CANFilter::new_exact(CANID::Extended(u29::new(0x18FEF100)));
+
+        assert!(can_filter.matches(&CANID::Extended(u29::new(0x18FEF100))));
+        assert!(!can_filter.matches(&CANID::Extended(u29::new(0x18FEF101))));
+    }
+
+    #[test]
+    fn test_range_filter() {
+        let can_filter = assert_ok!(CANFilter::new_range(
+            CANID::Extended(u29::new(0x18FEF100)),
+            CANID::Extended(u29::new(0x18FEF1FF))
+        ));
+
+        assert!(can_filter.matches(&CANID::Extended(u29::new(0x18FEF100))));
+        assert!(can_filter.matches(&CANID::Extended(u29::new(0x18FEF1FF))));
+        assert!(!can_filter.matches(&CANID::Extended(u29::new(0x18FEF200))));
+        assert!(!can_filter.matches(&CANID::Standard(u11::new(0x100))));
+    }
+
+    #[test]
+    fn test_dual_filter() {
+        let can_filter = CANFilter::new_dual(
+            CANID::Standard(u11::new(0x100)),
+            CANID::Extended(u29::new(0x100)),
+        );
+
+        assert!(can_filter.matches(&CANID::Standard(u11::new(0x100))));
+        assert!(can_filter.matches(&CANID::Extended(u29::new(0x100))));
+        assert!(!can_filter.matches(&CANID::Standard(u11::new(0x101))));
+    }
+
+    #[test]
+    fn test_can_filter_errors() {
+        assert_err_eq!(
+            CANFilter::new_mask(CANID::Standard(u11::new(0x100)), 0xFFF),
+            CANFilterError::MaskTooWide
+        );
+        assert_err_eq!(
+            CANFilter::new_range(
+                CANID::Standard(u11::new(0x100)),
+                CANID::Extended(u29::new(0x200))
+            ),
+            CANFilterError::IdentifierTypeMismatch
+        );
+        assert_err_eq!(
+            CANFilter::new_range(
+                CANID::Standard(u11::new(0x200)),
+                CANID::Standard(u11::new(0x100))
+            ),
+            CANFilterError::RangeInverted
+        );
+    }
+
+    #[test]
+    fn test_compile_bx_can() {
+        let mut identifiers: Vec<CANID> = (0x100..=0x107)
+            .map(|identifier| CANID::Standard(u11::new(identifier)))
+            .collect();
+        identifiers.extend([
+            CANID::Standard(u11::new(0x200)),
+            CANID::Standard(u11::new(0x201)),
+            CANID::Standard(u11::new(0x300)),
+            CANID::Standard(u11::new(0x305)),
+            CANID::Extended(u29::new(0x18FEF100)),
+            CANID::Extended(u29::new(0x0CF00400)),
+            CANID::Extended(u29::new(0x18FEF200)),
+        ]);
+
+        let filter_banks = assert_ok!(FilterController::stm32f4_bxcan().compile(&identifiers));
+
+        assert_eq!(
+            filter_banks,
+            vec![
+                FilterBank::BxCAN(BxCANFilterBank::Mask16 {
+                    filters: [
+                        (u11::new(0x100), u11::new(0x7F8)),
+                        (u11::new(0x200), u11::new(0x7FE))
+                    ]
+                }),
+                FilterBank::BxCAN(BxCANFilterBank::List16 {
+                    identifiers: [
+                        u11::new(0x300),
+                        u11::new(0x305),
+                        u11::new(0x305),
+                        u11::new(0x305)
+                    ]
+                }),
+                FilterBank::BxCAN(BxCANFilterBank::List32 {
+                    identifiers: [
+                        CANID::Extended(u29::new(0x0CF00400)),
+                        CANID::Extended(u29::new(0x18FEF100))
+                    ]
+                }),
+                FilterBank::BxCAN(BxCANFilterBank::List32 {
+                    identifiers: [
+                        CANID::Extended(u29::new(0x18FEF200)),
+                        CANID::Extended(u29::new(0x18FEF200))
+                    ]
+                }),
+            ]
+        );
+
+        assert_eq!(
+            accepted_standard_identifiers(&filter_banks),
+            vec![
+                0x100, 0x101, 0x102, 0x103, 0x104, 0x105, 0x106, 0x107, 0x200, 0x201, 0x300, 0x305
+            ]
+        );
+        assert!(
+            !filter_banks
+                .iter()
+                .any(|filter_bank| { filter_bank.matches(&CANID::Extended(u29::new(0x18FEF101))) })
+        );
+    }
+
+    #[test]
+    fn test_compile_bx_can_packing() {
+        // Splitting the block of two fits every identifier in a single 16-bit list bank.
+        let identifiers = [
+            CANID::Standard(u11::new(0x200)),
+            CANID::Standard(u11::new(0x201)),
+            CANID::Standard(u11::new(0x300)),
+            CANID::Standard(u11::new(0x305)),
+        ];
+
+        let filter_banks = assert_ok!(FilterController::stm32f4_bxcan().compile(&identifiers));
+
+        assert_eq!(filter_banks.len(), 1);
+        assert_eq!(
+            accepted_standard_identifiers(&filter_banks),
+            vec![0x200, 0x201, 0x300, 0x305]
+        );
+
+        let identifiers = [
+            CANID::Standard(u11::new(0x300)),
+            CANID::Extended(u29::new(0x0CF00400)),
+        ];
+
+        let filter_banks = assert_ok!(FilterController::stm32f4_bxcan().compile(&identifiers));
+
+        assert_eq!(
+            filter_banks,
+            vec![FilterBank::BxCAN(BxCANFilterBank::List32 {
+                identifiers: [
+                    CANID::Extended(u29::new(0x0CF00400)),
+                    CANID::Standard(u11::new(0x300))
+                ]
+            })]
+        );
+    }
+
+    #[test]
+    fn test_compile_bx_can_merges_blocks() {
+        // The blocks are not consecutive, but only differ in bits 8 and 9.
+        let identifiers: Vec<CANID> = [0x000, 0x100, 0x200, 0x300]
+            .into_iter()
+            .flat_map(|block_start| block_start..=block_start + 3)
+            .map(|identifier| CANID::Standard(u11::new(identifier)))
+            .collect();
+
+        let filter_banks = assert_ok!(FilterController::stm32f4_bxcan().compile(&identifiers));
+
+        assert_eq!(
+            filter_banks,
+            vec![FilterBank::BxCAN(BxCANFilterBank::Mask16 {
+                filters: [
+                    (u11::new(0x000), u11::new(0x4FC)),
+                    (u11::new(0x000), u11::new(0x4FC))
+                ]
+            })]
+        );
+
+        let mut expected_standard_identifiers: Vec<u16> = Vec::new();
+        for block_start in [0x000, 0x100, 0x200, 0x300] {
+            expected_standard_identifiers.extend(block_start..=block_start + 3);
+        }
+
+        assert_eq!(
+            accepted_standard_identifiers(&filter_banks),
+            expected_standard_identifiers
+        );
+    }
+
+    #[test]
+    fn test_compile_fd_can() {
+        let mut identifiers: Vec<CANID> = (0x100..=0x10F)
+            .map(|identifier| CANID::Standard(u11::new(identifier)))
+            .collect();
+        identifiers.extend([
+            CANID::Standard(u11::new(0x300)),
+            CANID::Standard(u11::new(0x200)),
+            CANID::Standard(u11::new(0x301)),
+            CANID::Extended(u29::new(0x18FEF100)),
+        ]);
+
+        let filter_banks = assert_ok!(FilterController::stm32g4_fdcan().compile(&identifiers));
+
+        assert_eq!(
+            filter_banks,
+            vec![
+                FilterBank::FDCAN(CANFilter::Range {
+                    first: CANID::Standard(u11::new(0x100)),
+                    last: CANID::Standard(u11::new(0x10F))
+                }),
+                FilterBank::FDCAN(CANFilter::Dual {
+                    first: CANID::Standard(u11::new(0x200)),
+                    second: CANID::Standard(u11::new(0x300))
+                }),
+                FilterBank::FDCAN(CANFilter::Dual {
+                    first: CANID::Standard(u11::new(0x301)),
+                    second: CANID::Standard(u11::new(0x301))
+                }),
+                FilterBank::FDCAN(CANFilter::Dual {
+                    first: CANID::Extended(u29::new(0x18FEF100)),
+                    second: CANID::Extended(u29::new(0x18FEF100))
+                }),
+            ]
+        );
+
+        let mut expected_standard_identifiers: Vec<u16> = (0x100..=0x10F).collect();
+        expected_standard_identifiers.extend([0x200, 0x300, 0x301]);
+
+        assert_eq!(
+            accepted_standard_identifiers(&filter_banks),
+            expected_standard_identifiers
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        // Multiples of three never differ in a single bit, so no two identifiers share a mask.
+        let identifiers: Vec<CANID> = (0..20)
+            .map(|identifier| CANID::Extended(u29::new(0x18FEF100 + 3 * identifier)))
+            .collect();
+
+        assert_err_eq!(
+            FilterController::stm32g4_fdcan().compile(&identifiers),
+            CANFilterError::TooManyExtendedFilters
+        );
+        assert_err_eq!(
+            FilterController::BxCAN { bank_count: 4 }.compile(&identifiers),
+            CANFilterError::TooManyFilterBanks
+        );
+
+        let identifiers: Vec<CANID> = (0..60)
+            .map(|identifier| CANID::Standard(u11::new(4 * identifier)))
+            .collect();
+
+        assert_err_eq!(
+            FilterController::stm32g4_fdcan().compile(&identifiers),
+            CANFilterError::TooManyStandardFilters
+        );
+    }
+}
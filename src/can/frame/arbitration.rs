@@ -0,0 +1,241 @@
+use std::error::Error;
+
+use bitvec::prelude::*;
+use strum::Display;
+
+use crate::can::frame::{
+    BASE_REMOTE_TRANSMISSION_REQUEST_INDEX, CANFrame, EXTENDED_REMOTE_TRANSMISSION_REQUEST_INDEX,
+    IDENTIFIER_EXTENSION_BIT_INDEX, stuffing::STUFF_WIDTH,
+};
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArbitrationError {
+    NoFrames,
+}
+
+impl Error for ArbitrationError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ArbitrationLoss {
+    frame_index: usize,
+    bit_index: usize,
+    unstuffed_bit_index: usize,
+}
+
+impl ArbitrationLoss {
+    pub fn frame_index(&self) -> usize {
+        self.frame_index
+    }
+
+    // The position on the bus, counted from the start of frame and including stuff bits.
+    pub fn bit_index(&self) -> usize {
+        self.bit_index
+    }
+
+    pub fn unstuffed_bit_index(&self) -> usize {
+        self.unstuffed_bit_index
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arbitration {
+    winners: Vec<usize>,
+    losses: Vec<ArbitrationLoss>,
+}
+
+impl Arbitration {
+    // Every frame starts at the same start of frame, so the bus carries the wired AND of their
+    // bits, and a node that sends a recessive bit but sees a dominant one backs off.
+    pub fn simulate(can_frames: &[CANFrame]) -> Result<Self, ArbitrationError> {
+        if can_frames.is_empty() {
+            return Err(ArbitrationError::NoFrames);
+        }
+
+        let unstuffed_frames: Vec<_> =
+            can_frames.iter().map(|can_frame| can_frame.to_unstuffed_bits()).collect();
+
+        let mut winners: Vec<usize> = (0..can_frames.len()).collect();
+        let mut losses = Vec::new();
+
+        let mut bit_index = 0;
+        let mut unstuffed_bit_index = 0;
+        let mut previous_bit = true;
+        let mut same_bit_count = 0;
+
+        while winners.len() > 1
+            && winners.iter().any(|frame_index| {
+                unstuffed_bit_index <= arbitration_field_end_index(&unstuffed_frames[*frame_index])
+            })
+        {
+            // Every node has sent the same bits so far, so they all send the same stuff bit.
+            if same_bit_count == STUFF_WIDTH {
+                previous_bit = !previous_bit;
+                same_bit_count = 1;
+                bit_index += 1;
+                continue;
+            }
+
+            let bus_bit = winners
+                .iter()
+                .all(|frame_index| unstuffed_frames[*frame_index][unstuffed_bit_index]);
+
+            winners.retain(|frame_index| {
+                if unstuffed_frames[*frame_index][unstuffed_bit_index] == bus_bit {
+                    return true;
+                }
+
+                losses.push(ArbitrationLoss {
+                    frame_index: *frame_index,
+                    bit_index,
+                    unstuffed_bit_index,
+                });
+
+                false
+            });
+
+            if bus_bit == previous_bit {
+                same_bit_count += 1;
+            } else {
+                previous_bit = bus_bit;
+                same_bit_count = 1;
+            }
+
+            bit_index += 1;
+            unstuffed_bit_index += 1;
+        }
+
+        Ok(Self { winners, losses })
+    }
+
+    // None when more than one frame is left after the arbitration field, as frames with the same
+    // arbitration field go on to collide.
+    pub fn winner(&self) -> Option<usize> {
+        match self.winners.as_slice() {
+            [winner] => Some(*winner),
+            _ => None,
+        }
+    }
+
+    pub fn winners(&self) -> &[usize] {
+        &self.winners
+    }
+
+    pub fn losses(&self) -> &[ArbitrationLoss] {
+        &self.losses
+    }
+}
+
+fn arbitration_field_end_index(unstuffed_bits: &BitSlice<usize, Msb0>) -> usize {
+    if unstuffed_bits[IDENTIFIER_EXTENSION_BIT_INDEX] {
+        EXTENDED_REMOTE_TRANSMISSION_REQUEST_INDEX
+    } else {
+        BASE_REMOTE_TRANSMISSION_REQUEST_INDEX
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::{u4, u11, u29};
+
+    use crate::can::{can_id::CANID, dlc::DLC};
+
+    use super::*;
+
+    #[test]
+    fn test_simulate() {
+        let can_frames = [
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x63C)), vec![0x00])),
+            assert_ok!(CANFrame::new(CANID::Extended(u29::new(0x0CF00400)), vec![0x00])),
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x7FF)), vec![0x00])),
+        ];
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames));
+
+        // The extended frame has base identifier 0x33C, so it wins on the first identifier bit.
+        assert_eq!(arbitration.winner(), Some(1));
+        assert_eq!(
+            arbitration.losses(),
+            &[
+                ArbitrationLoss {
+                    frame_index: 0,
+                    bit_index: 1,
+                    unstuffed_bit_index: 1
+                },
+                ArbitrationLoss {
+                    frame_index: 2,
+                    bit_index: 1,
+                    unstuffed_bit_index: 1
+                },
+            ]
+        );
+
+        let winner = can_frames.iter().map(CANFrame::identifier).min();
+        assert_eq!(winner, Some(can_frames[1].identifier()));
+    }
+
+    #[test]
+    fn test_simulate_same_base_identifier() {
+        let can_frames = [
+            assert_ok!(CANFrame::new(CANID::Extended(u29::new(0x04000000)), vec![0x00])),
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x100)), vec![0x00])),
+        ];
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames));
+
+        // The extended frame backs off at its recessive substitute remote request bit, after one
+        // stuff bit in the identifier.
+        assert_eq!(arbitration.winner(), Some(1));
+        assert_eq!(arbitration.losses()[0].frame_index(), 0);
+        assert_eq!(arbitration.losses()[0].bit_index(), 13);
+        assert_eq!(arbitration.losses()[0].unstuffed_bit_index(), 12);
+
+        let can_frames = [
+            assert_ok!(CANFrame::new(CANID::Extended(u29::new(0x04000000)), vec![0x00])),
+            CANFrame::new_remote(CANID::Standard(u11::new(0x100)), DLC::new(u4::new(0))),
+        ];
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames));
+
+        // A base remote frame still wins, at the identifier extension bit.
+        assert_eq!(arbitration.winner(), Some(1));
+        assert_eq!(arbitration.losses()[0].bit_index(), 14);
+        assert_eq!(arbitration.losses()[0].unstuffed_bit_index(), 13);
+    }
+
+    #[test]
+    fn test_simulate_remote_frame() {
+        let can_frames = [
+            CANFrame::new_remote(CANID::Standard(u11::new(0x123)), DLC::new(u4::new(1))),
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x123)), vec![0x00])),
+        ];
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames));
+
+        assert_eq!(arbitration.winner(), Some(1));
+        assert_eq!(arbitration.losses()[0].unstuffed_bit_index(), 12);
+    }
+
+    #[test]
+    fn test_simulate_collision() {
+        let can_frames = [
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x123)), vec![0x00])),
+            assert_ok!(CANFrame::new(CANID::Standard(u11::new(0x123)), vec![0xFF])),
+        ];
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames));
+
+        assert_eq!(arbitration.winner(), None);
+        assert_eq!(arbitration.winners(), &[0, 1]);
+        assert!(arbitration.losses().is_empty());
+
+        let arbitration = assert_ok!(Arbitration::simulate(&can_frames[..1]));
+
+        assert_eq!(arbitration.winner(), Some(0));
+    }
+
+    #[test]
+    fn test_simulate_no_frames() {
+        assert_err_eq!(Arbitration::simulate(&[]), ArbitrationError::NoFrames);
+    }
+}
@@ -0,0 +1,120 @@
+use bitvec::prelude::*;
+use ux::{u13, u15, u17, u21};
+
+const CRC_15_POLYNOMIAL: u32 = 0x4599;
+const CRC_17_POLYNOMIAL: u32 = 0x1685B;
+const CRC_21_POLYNOMIAL: u32 = 0x102899;
+// The CAN XL polynomials and initial values are taken from the CiA 610-1 drafts this crate was
+// written against and have not been checked against the published ISO 11898-1:2024.
+const CRC_13_POLYNOMIAL: u32 = 0x1C1B;
+const CRC_32_POLYNOMIAL: u32 = 0xF1922815;
+
+pub fn crc15<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(bits: B) -> u15 {
+    u15::new(crc(bits.as_ref(), 15, CRC_15_POLYNOMIAL, 0) as u16)
+}
+
+pub fn crc17<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(bits: B) -> u17 {
+    u17::new(crc(bits.as_ref(), 17, CRC_17_POLYNOMIAL, 1 << 16))
+}
+
+pub fn crc21<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(bits: B) -> u21 {
+    u21::new(crc(bits.as_ref(), 21, CRC_21_POLYNOMIAL, 1 << 20))
+}
+
+pub fn crc13<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(bits: B) -> u13 {
+    u13::new(crc(bits.as_ref(), 13, CRC_13_POLYNOMIAL, 0x1FFF) as u16)
+}
+
+pub fn crc32<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(bits: B) -> u32 {
+    crc(bits.as_ref(), 32, CRC_32_POLYNOMIAL, 0xFFFFFFFF)
+}
+
+fn crc<T: BitStore>(bits: &BitSlice<T, Msb0>, width: u32, polynomial: u32, initial: u32) -> u32 {
+    let mask = u32::MAX >> (32 - width);
+    let mut crc = initial;
+
+    for bit in bits.iter().by_vals() {
+        let crc_next = bit ^ (crc & (1 << (width - 1)) != 0);
+        crc = (crc << 1) & mask;
+
+        if crc_next {
+            crc ^= polynomial;
+        }
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc15() {
+        assert_eq!(crc15(bitvec![usize, Msb0;]), u15::new(0));
+
+        assert_eq!(
+            crc15(bitvec![usize, Msb0;
+                0,                                              // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                // ID
+                0,                                              // RTR
+                0,                                              // IDE
+                0,                                              // R0
+                0, 0, 0, 1,                                     // DLC
+                0, 0, 0, 0, 0, 0, 0, 1,                         // DF
+            ]),
+            u15::new(0b111011101010011)
+        );
+    }
+
+    #[test]
+    fn test_crc_check_values() {
+        // The check values of the CRC catalogue, which start from a zero register.
+        let bits = BitVec::<u8, Msb0>::from_slice(b"123456789");
+
+        assert_eq!(crc(&bits, 15, CRC_15_POLYNOMIAL, 0), 0x059E);
+        assert_eq!(crc(&bits, 17, CRC_17_POLYNOMIAL, 0), 0x04F03);
+        assert_eq!(crc(&bits, 21, CRC_21_POLYNOMIAL, 0), 0x0ED841);
+    }
+
+    #[test]
+    fn test_crc17() {
+        assert_eq!(crc17(bitvec![usize, Msb0;]), u17::new(1 << 16));
+
+        // Appending the CRC to the message leaves a remainder of zero.
+        let mut bits = bitvec![usize, Msb0; 0, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1];
+        let crc = crc17(&bits[..]);
+        bits.extend((0..17).rev().map(|bit_index| u32::from(crc) & (1 << bit_index) != 0));
+        assert_eq!(crc17(bits), u17::new(0));
+    }
+
+    #[test]
+    fn test_crc21() {
+        assert_eq!(crc21(bitvec![usize, Msb0;]), u21::new(1 << 20));
+
+        let mut bits = bitvec![usize, Msb0; 0, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1];
+        let crc = crc21(&bits[..]);
+        bits.extend((0..21).rev().map(|bit_index| u32::from(crc) & (1 << bit_index) != 0));
+        assert_eq!(crc21(bits), u21::new(0));
+    }
+
+    #[test]
+    fn test_crc13() {
+        assert_eq!(crc13(bitvec![usize, Msb0;]), u13::new(0x1FFF));
+
+        let mut bits = bitvec![usize, Msb0; 0, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1];
+        let crc = crc13(&bits[..]);
+        bits.extend((0..13).rev().map(|bit_index| u16::from(crc) & (1 << bit_index) != 0));
+        assert_eq!(crc13(bits), u13::new(0));
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(bitvec![usize, Msb0;]), 0xFFFFFFFF);
+
+        let mut bits = bitvec![usize, Msb0; 0, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1, 0, 1];
+        let crc = crc32(&bits[..]);
+        bits.extend((0..32).rev().map(|bit_index| crc & (1 << bit_index) != 0));
+        assert_eq!(crc32(bits), 0);
+    }
+}
@@ -1,18 +1,23 @@
 use bitvec::prelude::*;
 use ux::{u1, u3, u4, u7, u11, u15};
 
-use crate::{
-    can::frame::{CANFrameDecodingError, data::extract_field},
-    unstuff,
+use crate::can::dlc::DLC;
+use crate::can::frame::{
+    CANFrameDecodingError, CANFrameEncodingError,
+    crc::crc15,
+    extract_field, push_field,
+    stuffing::{stuff_frames, try_unstuff_frames},
+    verify_cyclic_redundancy_check,
 };
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BaseDataFrame {
     start_of_frame: u1,
     identifier: u11,
     remote_transmission_request: u1,
     identifier_extension_bit: u1,
     reserved_bit_zero: u1,
-    data_length_code: u4,
+    data_length_code: DLC,
     data_field: Vec<u8>,
     cyclic_redundancy_check: u15,
     cyclic_redundancy_check_delimiter: u1,
@@ -23,6 +28,32 @@ pub struct BaseDataFrame {
 }
 
 impl BaseDataFrame {
+    pub fn new(identifier: u11, data_field: Vec<u8>) -> Result<Self, CANFrameEncodingError> {
+        let data_length_code = DLC::from_len_classic(data_field.len())
+            .ok_or(CANFrameEncodingError::DataFieldTooLong)?;
+
+        let mut base_data_frame = Self {
+            start_of_frame: u1::new(0),
+            identifier,
+            remote_transmission_request: u1::new(0),
+            identifier_extension_bit: u1::new(0),
+            reserved_bit_zero: u1::new(0),
+            data_length_code,
+            data_field,
+            cyclic_redundancy_check: u15::new(0),
+            cyclic_redundancy_check_delimiter: u1::new(1),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        base_data_frame.cyclic_redundancy_check =
+            crc15(base_data_frame.cyclic_redundancy_check_sequence());
+
+        Ok(base_data_frame)
+    }
+
     pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
         unstuffed_bits: B,
     ) -> Result<Self, CANFrameDecodingError> {
@@ -35,7 +66,7 @@ impl BaseDataFrame {
             bit_index,
             1,
             CANFrameDecodingError::StartOfFrameMissing,
-            |start_of_frame_bit_slice| u1::new(start_of_frame_bit_slice.load()),
+            |start_of_frame_bit_slice| u1::new(start_of_frame_bit_slice.load_be()),
         )?;
 
         if start_of_frame != u1::new(0) {
@@ -49,7 +80,7 @@ impl BaseDataFrame {
             bit_index,
             11,
             CANFrameDecodingError::IdentifierMissing,
-            |start_of_frame_bit_slice| u11::new(start_of_frame_bit_slice.load()),
+            |start_of_frame_bit_slice| u11::new(start_of_frame_bit_slice.load_be()),
         )?;
 
         bit_index += 11;
@@ -58,6 +89,9 @@ impl BaseDataFrame {
         let remote_transmission_request_bit_ref = maybe_remote_transmission_request_bit_ref
             .ok_or(CANFrameDecodingError::RemoteTransmissionRequestMissing)?;
         let remote_transmission_request = u1::from(*remote_transmission_request_bit_ref);
+        if remote_transmission_request != u1::new(0) {
+            return Err(CANFrameDecodingError::RemoteTransmissionRequestMustBeZero);
+        }
 
         bit_index += 1;
 
@@ -80,20 +114,33 @@ impl BaseDataFrame {
 
         let maybe_data_length_code_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 4));
         let data_length_code_bit_slice =
-            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
-        let data_length_code = u4::new(data_length_code_bit_slice.load());
+            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::DataLengthCodeMissing)?;
+        let data_length_code = DLC::new(u4::new(data_length_code_bit_slice.load_be()));
 
         bit_index += 4;
 
-        let data_field = Vec::new();
+        let data_field_length = data_length_code.to_len_classic();
+
+        let data_field = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8 * data_field_length,
+            CANFrameDecodingError::DataFieldMissing,
+            |data_field_bit_slice| {
+                data_field_bit_slice
+                    .chunks(8)
+                    .map(|data_byte_bit_slice| data_byte_bit_slice.load_be::<u8>())
+                    .collect::<Vec<u8>>()
+            },
+        )?;
 
-        bit_index += 8 * u8::from(data_length_code) as usize;
+        bit_index += 8 * data_field_length;
 
         let maybe_cyclic_redundancy_check_bit_slice =
             unstuffed_bits.get(bit_index..(bit_index + 15));
         let cyclic_redundancy_check_bit_slice = maybe_cyclic_redundancy_check_bit_slice
             .ok_or(CANFrameDecodingError::CyclicRedundancyCheckMissing)?;
-        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load());
+        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load_be());
 
         bit_index += 15;
 
@@ -129,7 +176,7 @@ impl BaseDataFrame {
         let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
         let end_of_frame_bit_slice =
             maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
-        let end_of_frame = u7::new(end_of_frame_bit_slice.load());
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
         if end_of_frame != u7::new(0b1111111) {
             return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
         }
@@ -139,7 +186,7 @@ impl BaseDataFrame {
         let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
         let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
             .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
-        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load());
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
         if inter_frame_spacing != u3::new(0b111) {
             return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
         }
@@ -164,7 +211,63 @@ impl BaseDataFrame {
     pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
         stuffed_bits: B,
     ) -> Result<Self, CANFrameDecodingError> {
-        Self::from_unstuffed_bits(unstuff(stuffed_bits, 5))
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let base_data_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        verify_cyclic_redundancy_check(
+            unstuffed_bits,
+            19 + 8 * base_data_frame.data_field().len(),
+            base_data_frame.cyclic_redundancy_check(),
+        )?;
+
+        Ok(base_data_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    fn cyclic_redundancy_check_sequence(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.identifier), 11);
+        push_field(&mut bits, u64::from(self.remote_transmission_request), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_zero), 1);
+        push_field(&mut bits, u64::from(self.data_length_code.raw()), 4);
+
+        for data_byte in &self.data_field {
+            push_field(&mut bits, u64::from(*data_byte), 8);
+        }
+
+        bits
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = self.cyclic_redundancy_check_sequence();
+
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check), 15);
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check_delimiter), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
     }
 
     pub fn start_of_frame(&self) -> u1 {
@@ -187,7 +290,7 @@ impl BaseDataFrame {
         self.reserved_bit_zero
     }
 
-    pub fn data_length_code(&self) -> u4 {
+    pub fn data_length_code(&self) -> DLC {
         self.data_length_code
     }
 
@@ -222,7 +325,7 @@ impl BaseDataFrame {
 
 #[cfg(test)]
 mod tests {
-    use claims::assert_ok;
+    use claims::{assert_err, assert_ok};
 
     use super::*;
 
@@ -249,7 +352,8 @@ mod tests {
         assert_eq!(base_data_frame.remote_transmission_request(), u1::new(0b0));
         assert_eq!(base_data_frame.identifier_extension_bit(), u1::new(0b0));
         assert_eq!(base_data_frame.reserved_bit_zero(), u1::new(0b0));
-        assert_eq!(base_data_frame.data_length_code(), u4::new(0b0001));
+        assert_eq!(base_data_frame.data_length_code(), DLC::new(u4::new(0b0001)));
+        assert_eq!(base_data_frame.data_field(), &vec![0b00000001]);
         assert_eq!(
             base_data_frame.cyclic_redundancy_check(),
             u15::new(0b111011101010011)
@@ -263,4 +367,209 @@ mod tests {
         assert_eq!(base_data_frame.end_of_frame(), u7::new(0b1111111));
         assert_eq!(base_data_frame.inter_frame_spacing(), u3::new(0b111));
     }
+
+    #[test]
+    fn test_data_length_code_above_eight_is_clamped() {
+        let base_data_frame = assert_ok!(BaseDataFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            1, 1, 1, 1,                                     // DLC
+            0, 0, 0, 1, 0, 0, 0, 1,                         // DF
+            0, 0, 1, 0, 0, 0, 1, 0,
+            0, 0, 1, 1, 0, 0, 1, 1,
+            0, 1, 0, 0, 0, 1, 0, 0,
+            0, 1, 0, 1, 0, 1, 0, 1,
+            0, 1, 1, 0, 0, 1, 1, 0,
+            0, 1, 1, 1, 0, 1, 1, 1,
+            1, 0, 0, 0, 1, 0, 0, 0,
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ],));
+
+        assert_eq!(base_data_frame.data_length_code(), DLC::new(u4::new(0b1111)));
+        assert_eq!(
+            base_data_frame.data_field(),
+            &vec![0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]
+        );
+    }
+
+    #[test]
+    fn test_data_field_missing() {
+        let base_data_frame_result = BaseDataFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 1, 0,                                     // DLC
+            0, 0, 0, 1, 0, 0, 0, 1,                         // DF
+            0, 0, 1, 0,
+        ]);
+
+        assert!(matches!(
+            assert_err!(base_data_frame_result),
+            CANFrameDecodingError::DataFieldMissing
+        ));
+    }
+
+    #[test]
+    fn test_from_stuffed_bits_checked() {
+        let base_data_frame = assert_ok!(BaseDataFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            1, 0, 0, 0, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 0, 0, 1,                      // DF
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ],));
+
+        assert_eq!(
+            base_data_frame.cyclic_redundancy_check(),
+            u15::new(0b111011101010011)
+        );
+    }
+
+    #[test]
+    fn test_cyclic_redundancy_check_mismatch() {
+        let base_data_frame_result = BaseDataFrame::from_unstuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1,                                     // DLC
+            0, 0, 0, 0, 0, 0, 1, 1,                         // DF
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+
+        assert!(matches!(
+            assert_err!(base_data_frame_result),
+            CANFrameDecodingError::CyclicRedundancyCheckMismatch { received, .. }
+                if received == u15::new(0b111011101010011)
+        ));
+    }
+
+    #[test]
+    fn test_new() {
+        let base_data_frame = assert_ok!(BaseDataFrame::new(
+            u11::new(0b00000010100),
+            vec![0b00000001]
+        ));
+
+        assert_eq!(base_data_frame.data_length_code(), DLC::new(u4::new(0b0001)));
+        assert_eq!(
+            base_data_frame.cyclic_redundancy_check(),
+            u15::new(0b111011101010011)
+        );
+        assert_eq!(base_data_frame.to_stuffed_bits(), bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            1, 0, 0, 0, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 0, 0, 1,                      // DF
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+    }
+
+    #[test]
+    fn test_new_round_trip() {
+        let base_data_frame = assert_ok!(BaseDataFrame::new(
+            u11::new(0x7FF),
+            vec![0x00, 0x00, 0xFF, 0xFF, 0x12, 0x34, 0x56, 0x78]
+        ));
+
+        assert_eq!(
+            assert_ok!(BaseDataFrame::from_stuffed_bits_checked(
+                base_data_frame.to_stuffed_bits()
+            )),
+            base_data_frame
+        );
+        assert_eq!(
+            assert_ok!(BaseDataFrame::from_unstuffed_bits_checked(
+                base_data_frame.to_unstuffed_bits()
+            )),
+            base_data_frame
+        );
+    }
+
+    #[test]
+    fn test_new_data_field_too_long() {
+        assert!(matches!(
+            assert_err!(BaseDataFrame::new(u11::new(0), vec![0; 9])),
+            CANFrameEncodingError::DataFieldTooLong
+        ));
+    }
+
+    #[test]
+    fn test_remote_transmission_request_must_be_zero() {
+        let base_data_frame_result = BaseDataFrame::from_stuffed_bits(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            1,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 0, // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+
+        assert!(matches!(
+            assert_err!(base_data_frame_result),
+            CANFrameDecodingError::RemoteTransmissionRequestMustBeZero
+        ));
+    }
+
+    #[test]
+    fn test_stuff_error() {
+        let base_data_frame_result = BaseDataFrame::from_stuffed_bits(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,             // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            1, 0, 0, 0, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 0, 0, 1,                      // DF
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+
+        assert_eq!(
+            assert_err!(base_data_frame_result),
+            CANFrameDecodingError::StuffError { bit_index: 5 }
+        );
+    }
 }
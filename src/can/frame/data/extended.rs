@@ -1,8 +1,16 @@
 use bitvec::prelude::*;
 use ux::{u1, u3, u4, u7, u11, u15, u18, u29};
 
-use crate::{can::frame::CANFrameDecodingError, unstuff};
-
+use crate::can::dlc::DLC;
+use crate::can::frame::{
+    CANFrameDecodingError, CANFrameEncodingError,
+    crc::crc15,
+    extract_field, push_field,
+    stuffing::{stuff_frames, try_unstuff_frames},
+    verify_cyclic_redundancy_check,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExtendedDataFrame {
     start_of_frame: u1,
     identifier_a: u11,
@@ -12,7 +20,7 @@ pub struct ExtendedDataFrame {
     remote_transmission_request: u1,
     reserved_bit_one: u1,
     reserved_bit_zero: u1,
-    data_length_code: u4,
+    data_length_code: DLC,
     data_field: Vec<u8>,
     cyclic_redundancy_check: u15,
     cyclic_redundancy_check_delimiter: u1,
@@ -23,6 +31,35 @@ pub struct ExtendedDataFrame {
 }
 
 impl ExtendedDataFrame {
+    pub fn new(identifier: u29, data_field: Vec<u8>) -> Result<Self, CANFrameEncodingError> {
+        let data_length_code = DLC::from_len_classic(data_field.len())
+            .ok_or(CANFrameEncodingError::DataFieldTooLong)?;
+
+        let mut extended_data_frame = Self {
+            start_of_frame: u1::new(0),
+            identifier_a: u11::try_from(identifier >> 18).unwrap(),
+            substitute_remote_request: u1::new(1),
+            identifier_extension_bit: u1::new(1),
+            identifier_b: u18::try_from(identifier & u29::new(0x3FFFF)).unwrap(),
+            remote_transmission_request: u1::new(0),
+            reserved_bit_one: u1::new(0),
+            reserved_bit_zero: u1::new(0),
+            data_length_code,
+            data_field,
+            cyclic_redundancy_check: u15::new(0),
+            cyclic_redundancy_check_delimiter: u1::new(1),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        extended_data_frame.cyclic_redundancy_check =
+            crc15(extended_data_frame.cyclic_redundancy_check_sequence());
+
+        Ok(extended_data_frame)
+    }
+
     pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
         unstuffed_bits: B,
     ) -> Result<Self, CANFrameDecodingError> {
@@ -43,7 +80,7 @@ impl ExtendedDataFrame {
         let maybe_identifier_a_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 11));
         let identifier_a_bit_slice =
             maybe_identifier_a_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
-        let identifier_a = u11::new(identifier_a_bit_slice.load());
+        let identifier_a = u11::new(identifier_a_bit_slice.load_be());
 
         bit_index += 11;
 
@@ -70,7 +107,7 @@ impl ExtendedDataFrame {
         let maybe_identifier_b_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 18));
         let identifier_b_bit_slice =
             maybe_identifier_b_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
-        let identifier_b = u18::new(identifier_b_bit_slice.load());
+        let identifier_b = u18::new(identifier_b_bit_slice.load_be());
 
         bit_index += 18;
 
@@ -78,6 +115,9 @@ impl ExtendedDataFrame {
         let remote_transmission_request_bit_ref = maybe_remote_transmission_request_bit_ref
             .ok_or(CANFrameDecodingError::RemoteTransmissionRequestMissing)?;
         let remote_transmission_request = u1::from(*remote_transmission_request_bit_ref);
+        if remote_transmission_request != u1::new(0) {
+            return Err(CANFrameDecodingError::RemoteTransmissionRequestMustBeZero);
+        }
 
         bit_index += 1;
 
@@ -97,20 +137,33 @@ impl ExtendedDataFrame {
 
         let maybe_data_length_code_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 4));
         let data_length_code_bit_slice =
-            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
-        let data_length_code = u4::new(data_length_code_bit_slice.load());
+            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::DataLengthCodeMissing)?;
+        let data_length_code = DLC::new(u4::new(data_length_code_bit_slice.load_be()));
 
         bit_index += 4;
 
-        let data_field = Vec::new();
+        let data_field_length = data_length_code.to_len_classic();
+
+        let data_field = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8 * data_field_length,
+            CANFrameDecodingError::DataFieldMissing,
+            |data_field_bit_slice| {
+                data_field_bit_slice
+                    .chunks(8)
+                    .map(|data_byte_bit_slice| data_byte_bit_slice.load_be::<u8>())
+                    .collect::<Vec<u8>>()
+            },
+        )?;
 
-        bit_index += 8 * u8::from(data_length_code) as usize;
+        bit_index += 8 * data_field_length;
 
         let maybe_cyclic_redundancy_check_bit_slice =
             unstuffed_bits.get(bit_index..(bit_index + 15));
         let cyclic_redundancy_check_bit_slice = maybe_cyclic_redundancy_check_bit_slice
             .ok_or(CANFrameDecodingError::CyclicRedundancyCheckMissing)?;
-        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load());
+        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load_be());
 
         bit_index += 15;
 
@@ -146,7 +199,7 @@ impl ExtendedDataFrame {
         let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
         let end_of_frame_bit_slice =
             maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
-        let end_of_frame = u7::new(end_of_frame_bit_slice.load());
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
         if end_of_frame != u7::new(0b1111111) {
             return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
         }
@@ -156,7 +209,7 @@ impl ExtendedDataFrame {
         let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
         let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
             .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
-        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load());
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
         if inter_frame_spacing != u3::new(0b111) {
             return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
         }
@@ -184,7 +237,66 @@ impl ExtendedDataFrame {
     pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
         stuffed_bits: B,
     ) -> Result<Self, CANFrameDecodingError> {
-        Self::from_unstuffed_bits(unstuff(stuffed_bits, 5))
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let extended_data_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        verify_cyclic_redundancy_check(
+            unstuffed_bits,
+            39 + 8 * extended_data_frame.data_field().len(),
+            extended_data_frame.cyclic_redundancy_check(),
+        )?;
+
+        Ok(extended_data_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    fn cyclic_redundancy_check_sequence(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.identifier_a), 11);
+        push_field(&mut bits, u64::from(self.substitute_remote_request), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.identifier_b), 18);
+        push_field(&mut bits, u64::from(self.remote_transmission_request), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_one), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_zero), 1);
+        push_field(&mut bits, u64::from(self.data_length_code.raw()), 4);
+
+        for data_byte in &self.data_field {
+            push_field(&mut bits, u64::from(*data_byte), 8);
+        }
+
+        bits
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = self.cyclic_redundancy_check_sequence();
+
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check), 15);
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check_delimiter), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
     }
 
     pub fn start_of_frame(&self) -> u1 {
@@ -223,7 +335,7 @@ impl ExtendedDataFrame {
         self.reserved_bit_zero
     }
 
-    pub fn data_length_code(&self) -> u4 {
+    pub fn data_length_code(&self) -> DLC {
         self.data_length_code
     }
 
@@ -258,7 +370,7 @@ impl ExtendedDataFrame {
 
 #[cfg(test)]
 mod tests {
-    use claims::assert_ok;
+    use claims::{assert_err, assert_ok};
 
     use super::*;
 
@@ -296,7 +408,8 @@ mod tests {
         );
         assert_eq!(extended_data_frame.reserved_bit_one(), u1::new(0b0));
         assert_eq!(extended_data_frame.reserved_bit_zero(), u1::new(0b0));
-        assert_eq!(extended_data_frame.data_length_code(), u4::new(0b0001));
+        assert_eq!(extended_data_frame.data_length_code(), DLC::new(u4::new(0b0001)));
+        assert_eq!(extended_data_frame.data_field(), &vec![0b00000001]);
         assert_eq!(
             extended_data_frame.cyclic_redundancy_check(),
             u15::new(0b111011101010011)
@@ -313,4 +426,207 @@ mod tests {
         assert_eq!(extended_data_frame.end_of_frame(), u7::new(0b1111111));
         assert_eq!(extended_data_frame.inter_frame_spacing(), u3::new(0b111));
     }
+
+    #[test]
+    fn test_data_length_code_above_eight_is_clamped() {
+        let extended_data_frame =
+            assert_ok!(ExtendedDataFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                1, 0, 0, 1,                                             // DLC
+                1, 1, 1, 1, 1, 1, 1, 0,                                 // DF
+                1, 1, 0, 1, 1, 1, 0, 0,
+                1, 0, 1, 1, 1, 0, 1, 0,
+                1, 0, 0, 1, 1, 0, 0, 0,
+                0, 1, 1, 1, 0, 1, 1, 0,
+                0, 1, 0, 1, 0, 1, 0, 0,
+                0, 0, 1, 1, 0, 0, 1, 0,
+                0, 0, 0, 1, 0, 0, 0, 0,
+                1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0, 0, 1, 1,            // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ],));
+
+        assert_eq!(extended_data_frame.data_length_code(), DLC::new(u4::new(0b1001)));
+        assert_eq!(
+            extended_data_frame.data_field(),
+            &vec![0xFE, 0xDC, 0xBA, 0x98, 0x76, 0x54, 0x32, 0x10]
+        );
+    }
+
+    #[test]
+    fn test_data_field_missing() {
+        let extended_data_frame_result =
+            ExtendedDataFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                0, 0, 0, 1,                                             // DLC
+                1, 1, 1, 1,                                             // DF
+            ]);
+
+        assert!(matches!(
+            assert_err!(extended_data_frame_result),
+            CANFrameDecodingError::DataFieldMissing
+        ));
+    }
+
+    #[test]
+    fn test_from_unstuffed_bits_checked() {
+        let extended_data_frame =
+            assert_ok!(ExtendedDataFrame::from_unstuffed_bits_checked(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                0, 0, 0, 1,                                             // DLC
+                0, 0, 0, 0, 0, 0, 0, 1,                                 // DF
+                1, 0, 1, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0,            // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ],));
+
+        assert_eq!(
+            extended_data_frame.cyclic_redundancy_check(),
+            u15::new(0b101000100101000)
+        );
+    }
+
+    #[test]
+    fn test_cyclic_redundancy_check_mismatch() {
+        let extended_data_frame_result =
+            ExtendedDataFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,                     // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                0, 1, 0, 0, 1,                                          // DLC
+                0, 0, 0, 0, 0, 1, 0, 0, 1,                              // DF
+                1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0, 0, 1, 1,            // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ]);
+
+        assert!(matches!(
+            assert_err!(extended_data_frame_result),
+            CANFrameDecodingError::CyclicRedundancyCheckMismatch { expected, received }
+                if expected == u15::new(0b101000100101000)
+                    && received == u15::new(0b111011101010011)
+        ));
+    }
+
+    #[test]
+    fn test_new() {
+        let extended_data_frame = assert_ok!(ExtendedDataFrame::new(
+            u29::new(0b00000010100101010101010101010),
+            vec![0b00000001]
+        ));
+
+        assert_eq!(extended_data_frame.identifier_a(), u11::new(0b00000010100));
+        assert_eq!(
+            extended_data_frame.identifier_b(),
+            u18::new(0b101010101010101010)
+        );
+        assert_eq!(extended_data_frame.data_length_code(), DLC::new(u4::new(0b0001)));
+        assert_eq!(
+            extended_data_frame.cyclic_redundancy_check(),
+            u15::new(0b101000100101000)
+        );
+        assert_eq!(extended_data_frame.to_unstuffed_bits(), bitvec![usize, Msb0;
+            0,                                                      // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+            1,                                                      // SRR
+            1,                                                      // IDE
+            1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+            0,                                                      // RTR
+            0,                                                      // R1
+            0,                                                      // R0
+            0, 0, 0, 1,                                             // DLC
+            0, 0, 0, 0, 0, 0, 0, 1,                                 // DF
+            1, 0, 1, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0,            // CRC
+            1,                                                      // CRC Delim
+            0,                                                      // ACK Slot
+            1,                                                      // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                                    // EOF
+            1, 1, 1,                                                // IFS
+        ]);
+    }
+
+    #[test]
+    fn test_new_round_trip() {
+        let extended_data_frame = assert_ok!(ExtendedDataFrame::new(
+            u29::new(0x18FF0105),
+            vec![0xFF, 0xFF, 0x00, 0x00, 0xAA]
+        ));
+
+        assert_eq!(
+            assert_ok!(ExtendedDataFrame::from_stuffed_bits_checked(
+                extended_data_frame.to_stuffed_bits()
+            )),
+            extended_data_frame
+        );
+    }
+
+    #[test]
+    fn test_new_data_field_too_long() {
+        assert!(matches!(
+            assert_err!(ExtendedDataFrame::new(u29::new(0), vec![0; 9])),
+            CANFrameEncodingError::DataFieldTooLong
+        ));
+    }
+
+    #[test]
+    fn test_remote_transmission_request_must_be_zero() {
+        let extended_data_frame_result =
+            ExtendedDataFrame::from_stuffed_bits(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,                     // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                1,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                1, 0, 0, 0,                                             // DLC
+                0, 0, 1, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 0, 1,   // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ]);
+
+        assert!(matches!(
+            assert_err!(extended_data_frame_result),
+            CANFrameDecodingError::RemoteTransmissionRequestMustBeZero
+        ));
+    }
 }
@@ -0,0 +1,675 @@
+use bitvec::prelude::*;
+
+use crate::can::frame::{
+    CANFrame, CANFrameDecodingError, StuffingLayout,
+    stuffing::{STUFF_WIDTH, stuff_after_last_bit},
+    stuffing_layout,
+};
+
+const ERROR_FLAG_LENGTH: usize = 6;
+const DELIMITER_LENGTH: usize = 8;
+const INTERMISSION_LENGTH: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CANBusEvent {
+    Frame {
+        bit_index: usize,
+        frame: CANFrame,
+    },
+    MalformedFrame {
+        bit_index: usize,
+        error: CANFrameDecodingError,
+    },
+    ActiveErrorFlag {
+        bit_index: usize,
+        length: usize,
+    },
+    PassiveErrorFlag {
+        bit_index: usize,
+    },
+    ErrorDelimiter {
+        bit_index: usize,
+    },
+    OverloadFlag {
+        bit_index: usize,
+        length: usize,
+    },
+    OverloadDelimiter {
+        bit_index: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum CANBusState {
+    Idle,
+    Frame(FrameReceiver),
+    ActiveErrorFlag { bit_index: usize },
+    ErrorDelimiter { bit_index: usize },
+    OverloadFlag { bit_index: usize },
+    OverloadDelimiter { bit_index: usize },
+    Intermission { bit_index: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct CANBusEventDecoder {
+    state: CANBusState,
+    bit_index: usize,
+}
+
+impl CANBusEventDecoder {
+    pub fn new() -> Self {
+        Self {
+            state: CANBusState::Idle,
+            bit_index: 0,
+        }
+    }
+
+    pub fn decode<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(stuffed_bits: B) -> Vec<CANBusEvent> {
+        let mut can_bus_event_decoder = Self::new();
+
+        stuffed_bits
+            .as_ref()
+            .iter()
+            .by_vals()
+            .filter_map(|bit| can_bus_event_decoder.push(bit))
+            .collect()
+    }
+
+    pub fn bit_index(&self) -> usize {
+        self.bit_index
+    }
+
+    pub fn push(&mut self, bit: bool) -> Option<CANBusEvent> {
+        let bit_index = self.bit_index;
+        self.bit_index += 1;
+
+        match &mut self.state {
+            CANBusState::Idle => {
+                if !bit {
+                    self.state = CANBusState::Frame(FrameReceiver::new(bit_index));
+                }
+
+                None
+            }
+            CANBusState::Frame(frame_receiver) => match frame_receiver.push(bit) {
+                FrameReceiverStatus::Receiving => None,
+                FrameReceiverStatus::StuffError if bit => {
+                    self.state = CANBusState::ErrorDelimiter {
+                        bit_index: bit_index + 1,
+                    };
+
+                    Some(CANBusEvent::PassiveErrorFlag {
+                        bit_index: bit_index + 1 - ERROR_FLAG_LENGTH,
+                    })
+                }
+                FrameReceiverStatus::StuffError => {
+                    self.state = CANBusState::ActiveErrorFlag {
+                        bit_index: bit_index + 1 - ERROR_FLAG_LENGTH,
+                    };
+
+                    None
+                }
+                FrameReceiverStatus::FormError => {
+                    self.state = CANBusState::ActiveErrorFlag { bit_index };
+
+                    None
+                }
+                FrameReceiverStatus::EndOfFrame => {
+                    let frame_bit_index = frame_receiver.bit_index;
+                    let frame_result = frame_receiver.decode();
+
+                    self.state = if bit {
+                        CANBusState::Intermission {
+                            bit_index: bit_index + 1,
+                        }
+                    } else {
+                        CANBusState::OverloadFlag { bit_index }
+                    };
+
+                    Some(match frame_result {
+                        Ok(frame) => CANBusEvent::Frame {
+                            bit_index: frame_bit_index,
+                            frame,
+                        },
+                        Err(error) => CANBusEvent::MalformedFrame {
+                            bit_index: frame_bit_index,
+                            error,
+                        },
+                    })
+                }
+            },
+            CANBusState::ActiveErrorFlag {
+                bit_index: flag_bit_index,
+            } => {
+                if !bit {
+                    return None;
+                }
+
+                let flag_bit_index = *flag_bit_index;
+                self.state = CANBusState::ErrorDelimiter { bit_index };
+
+                Some(CANBusEvent::ActiveErrorFlag {
+                    bit_index: flag_bit_index,
+                    length: bit_index - flag_bit_index,
+                })
+            }
+            CANBusState::ErrorDelimiter {
+                bit_index: delimiter_bit_index,
+            } => {
+                if !bit {
+                    self.state = CANBusState::ActiveErrorFlag { bit_index };
+                    return None;
+                }
+
+                let delimiter_bit_index = *delimiter_bit_index;
+                if bit_index + 1 - delimiter_bit_index < DELIMITER_LENGTH {
+                    return None;
+                }
+
+                self.state = CANBusState::Intermission {
+                    bit_index: bit_index + 1,
+                };
+
+                Some(CANBusEvent::ErrorDelimiter {
+                    bit_index: delimiter_bit_index,
+                })
+            }
+            CANBusState::OverloadFlag {
+                bit_index: flag_bit_index,
+            } => {
+                if !bit {
+                    return None;
+                }
+
+                let flag_bit_index = *flag_bit_index;
+                self.state = CANBusState::OverloadDelimiter { bit_index };
+
+                Some(CANBusEvent::OverloadFlag {
+                    bit_index: flag_bit_index,
+                    length: bit_index - flag_bit_index,
+                })
+            }
+            CANBusState::OverloadDelimiter {
+                bit_index: delimiter_bit_index,
+            } => {
+                if !bit {
+                    self.state = CANBusState::ActiveErrorFlag { bit_index };
+                    return None;
+                }
+
+                let delimiter_bit_index = *delimiter_bit_index;
+                if bit_index + 1 - delimiter_bit_index < DELIMITER_LENGTH {
+                    return None;
+                }
+
+                self.state = CANBusState::Intermission {
+                    bit_index: bit_index + 1,
+                };
+
+                Some(CANBusEvent::OverloadDelimiter {
+                    bit_index: delimiter_bit_index,
+                })
+            }
+            CANBusState::Intermission {
+                bit_index: intermission_bit_index,
+            } => {
+                let intermission_index = bit_index - *intermission_bit_index;
+
+                if !bit {
+                    // A dominant bit in the last bit of intermission is a start of frame.
+                    self.state = if intermission_index < INTERMISSION_LENGTH - 1 {
+                        CANBusState::OverloadFlag { bit_index }
+                    } else {
+                        CANBusState::Frame(FrameReceiver::new(bit_index))
+                    };
+                } else if intermission_index == INTERMISSION_LENGTH - 1 {
+                    self.state = CANBusState::Idle;
+                }
+
+                None
+            }
+        }
+    }
+}
+
+impl Default for CANBusEventDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) enum FrameReceiverStatus {
+    Receiving,
+    StuffError,
+    FormError,
+    EndOfFrame,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FrameReceiver {
+    bit_index: usize,
+    unstuffed_bits: BitVec<usize, Msb0>,
+    stuffing_layout: Option<StuffingLayout>,
+    previous_bit: bool,
+    run_length: usize,
+    fixed_stuff_bit_received: bool,
+}
+
+impl FrameReceiver {
+    pub(crate) fn new(bit_index: usize) -> Self {
+        Self {
+            bit_index,
+            unstuffed_bits: bitvec![usize, Msb0; 0],
+            stuffing_layout: None,
+            previous_bit: false,
+            run_length: 1,
+            fixed_stuff_bit_received: false,
+        }
+    }
+
+    pub(crate) fn unstuffed_bits(&self) -> &BitSlice<usize, Msb0> {
+        &self.unstuffed_bits
+    }
+
+    pub(crate) fn stuffing_layout(&self) -> Option<StuffingLayout> {
+        self.stuffing_layout
+    }
+
+    pub(crate) fn push(&mut self, bit: bool) -> FrameReceiverStatus {
+        let unstuffed_length = self.unstuffed_bits.len();
+
+        let Some(stuffing_layout) = self.stuffing_layout else {
+            return self.push_dynamically_stuffed(bit);
+        };
+
+        let dynamically_stuffed_length = stuffing_layout.dynamically_stuffed_length;
+        let fixed_stuffed_end = dynamically_stuffed_length + stuffing_layout.fixed_stuffed_length;
+
+        if unstuffed_length < dynamically_stuffed_length {
+            return self.push_dynamically_stuffed(bit);
+        }
+
+        // A stuff bit may follow the last dynamically stuffed bit.
+        if unstuffed_length == dynamically_stuffed_length
+            && self.run_length == STUFF_WIDTH
+            && stuff_after_last_bit(stuffing_layout)
+        {
+            return self.push_dynamically_stuffed(bit);
+        }
+
+        if unstuffed_length < fixed_stuffed_end {
+            let fixed_stuffed_index = unstuffed_length - dynamically_stuffed_length;
+
+            if stuffing_layout.is_fixed_stuff_bit_index(fixed_stuffed_index)
+                && !self.fixed_stuff_bit_received
+            {
+                if bit == self.previous_bit {
+                    return FrameReceiverStatus::StuffError;
+                }
+
+                self.fixed_stuff_bit_received = true;
+            } else {
+                self.unstuffed_bits.push(bit);
+                self.fixed_stuff_bit_received = false;
+
+                // The length of a CAN XL frame is only known once its DLC has been received.
+                self.update_stuffing_layout();
+            }
+
+            self.previous_bit = bit;
+
+            return FrameReceiverStatus::Receiving;
+        }
+
+        self.unstuffed_bits.push(bit);
+
+        let fixed_form_index = unstuffed_length - fixed_stuffed_end;
+
+        if fixed_form_index == stuffing_layout.acknowledgement_slot_index() {
+            FrameReceiverStatus::Receiving
+        } else if fixed_form_index + 1 == stuffing_layout.fixed_form.len() {
+            FrameReceiverStatus::EndOfFrame
+        } else if stuffing_layout.fixed_form[fixed_form_index] != bit {
+            FrameReceiverStatus::FormError
+        } else {
+            FrameReceiverStatus::Receiving
+        }
+    }
+
+    fn push_dynamically_stuffed(&mut self, bit: bool) -> FrameReceiverStatus {
+        if self.run_length == STUFF_WIDTH {
+            if bit == self.previous_bit {
+                return FrameReceiverStatus::StuffError;
+            }
+
+            self.previous_bit = bit;
+            self.run_length = 1;
+
+            return FrameReceiverStatus::Receiving;
+        }
+
+        self.unstuffed_bits.push(bit);
+
+        self.run_length = if bit == self.previous_bit {
+            self.run_length + 1
+        } else {
+            1
+        };
+        self.previous_bit = bit;
+
+        if self.stuffing_layout.is_none() {
+            self.update_stuffing_layout();
+        }
+
+        FrameReceiverStatus::Receiving
+    }
+
+    fn update_stuffing_layout(&mut self) {
+        self.stuffing_layout = stuffing_layout(&self.unstuffed_bits);
+    }
+
+    fn decode(&mut self) -> Result<CANFrame, CANFrameDecodingError> {
+        // A dominant last bit of end of frame starts an overload frame but does not invalidate the
+        // frame, so the frame is decoded as if it and the intermission were recessive.
+        let last_bit_index = self.unstuffed_bits.len() - 1;
+        self.unstuffed_bits.set(last_bit_index, true);
+        self.unstuffed_bits.extend([true; INTERMISSION_LENGTH]);
+
+        CANFrame::from_unstuffed_bits_checked(&self.unstuffed_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::assert_ok;
+    use ux::{u1, u11, u15, u29};
+
+    use crate::can::can_id::CANID;
+
+    use super::*;
+
+    fn can_frames() -> (CANFrame, CANFrame) {
+        (
+            assert_ok!(CANFrame::new(
+                CANID::Standard(u11::new(0x123)),
+                vec![0x00, 0xFF, 0x55]
+            )),
+            assert_ok!(CANFrame::new(
+                CANID::Extended(u29::new(0x18FEF100)),
+                vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+            )),
+        )
+    }
+
+    #[test]
+    fn test_decode_frames() {
+        let (first_can_frame, second_can_frame) = can_frames();
+
+        let mut bits = first_can_frame.to_stuffed_bits();
+        let second_bit_index = bits.len() + 4;
+        bits.extend([true; 4]);
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::Frame {
+                    bit_index: 0,
+                    frame: first_can_frame,
+                },
+                CANBusEvent::Frame {
+                    bit_index: second_bit_index,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_active_error_flag() {
+        let (first_can_frame, second_can_frame) = can_frames();
+
+        let first_bits = first_can_frame.to_stuffed_bits();
+        let flag_bit_index = (20..first_bits.len())
+            .find(|bit_index| first_bits[bit_index - 1])
+            .unwrap();
+
+        let mut bits = first_bits[..flag_bit_index].to_bitvec();
+        bits.extend([false; 6]);
+        bits.extend([true; 8]);
+        bits.extend([true; 3]);
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::ActiveErrorFlag {
+                    bit_index: flag_bit_index,
+                    length: 6,
+                },
+                CANBusEvent::ErrorDelimiter {
+                    bit_index: flag_bit_index + 6,
+                },
+                CANBusEvent::Frame {
+                    bit_index: flag_bit_index + 17,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_superimposed_active_error_flags() {
+        let (first_can_frame, _) = can_frames();
+
+        let first_bits = first_can_frame.to_stuffed_bits();
+        let flag_bit_index = (20..first_bits.len())
+            .find(|bit_index| first_bits[bit_index - 1])
+            .unwrap();
+
+        let mut bits = first_bits[..flag_bit_index].to_bitvec();
+        bits.extend([false; 11]);
+        bits.extend([true; 8]);
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::ActiveErrorFlag {
+                    bit_index: flag_bit_index,
+                    length: 11,
+                },
+                CANBusEvent::ErrorDelimiter {
+                    bit_index: flag_bit_index + 11,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_passive_error_flag() {
+        let (first_can_frame, second_can_frame) = can_frames();
+
+        let first_bits = first_can_frame.to_stuffed_bits();
+        let flag_bit_index = (20..first_bits.len())
+            .find(|bit_index| !first_bits[bit_index - 1])
+            .unwrap();
+
+        let mut bits = first_bits[..flag_bit_index].to_bitvec();
+        bits.extend([true; 6]);
+        bits.extend([true; 8]);
+        bits.extend([true; 3]);
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::PassiveErrorFlag {
+                    bit_index: flag_bit_index,
+                },
+                CANBusEvent::ErrorDelimiter {
+                    bit_index: flag_bit_index + 6,
+                },
+                CANBusEvent::Frame {
+                    bit_index: flag_bit_index + 17,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_acknowledgement_error() {
+        let (first_can_frame, _) = can_frames();
+
+        let mut bits = first_can_frame.to_stuffed_bits();
+        let acknowledgement_delimiter_bit_index = bits.len() - 11;
+        bits.truncate(acknowledgement_delimiter_bit_index);
+        bits.set(acknowledgement_delimiter_bit_index - 1, true);
+        bits.extend([false; 6]);
+        bits.extend([true; 8]);
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::ActiveErrorFlag {
+                    bit_index: acknowledgement_delimiter_bit_index,
+                    length: 6,
+                },
+                CANBusEvent::ErrorDelimiter {
+                    bit_index: acknowledgement_delimiter_bit_index + 6,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_overload_frame() {
+        let (first_can_frame, second_can_frame) = can_frames();
+
+        let mut bits = first_can_frame.to_stuffed_bits();
+        let overload_bit_index = bits.len() - 3;
+        bits.truncate(overload_bit_index);
+        bits.extend([false; 6]);
+        bits.extend([true; 8]);
+        bits.extend([true; 3]);
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::Frame {
+                    bit_index: 0,
+                    frame: first_can_frame,
+                },
+                CANBusEvent::OverloadFlag {
+                    bit_index: overload_bit_index,
+                    length: 6,
+                },
+                CANBusEvent::OverloadDelimiter {
+                    bit_index: overload_bit_index + 6,
+                },
+                CANBusEvent::Frame {
+                    bit_index: overload_bit_index + 17,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_malformed_frame() {
+        assert_eq!(
+            CANBusEventDecoder::decode(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,                     // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                0, 1, 0, 0, 1,                                          // DLC
+                0, 0, 0, 0, 0, 1, 0, 0, 1,                              // DF
+                1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0, 0, 1, 1,            // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ]),
+            vec![CANBusEvent::MalformedFrame {
+                bit_index: 0,
+                error: CANFrameDecodingError::CyclicRedundancyCheckMismatch {
+                    expected: u15::new(0b101000100101000),
+                    received: u15::new(0b111011101010011),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_decode_fd_frames() {
+        let first_can_frame = assert_ok!(CANFrame::new_fd(
+            CANID::Standard(u11::new(0x123)),
+            vec![0x00; 12],
+            u1::new(1)
+        ));
+        let second_can_frame = assert_ok!(CANFrame::new_fd(
+            CANID::Extended(u29::new(0x18FEF100)),
+            (0..64).collect(),
+            u1::new(0)
+        ));
+
+        let mut bits = first_can_frame.to_stuffed_bits();
+        let second_bit_index = bits.len();
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::Frame {
+                    bit_index: 0,
+                    frame: first_can_frame,
+                },
+                CANBusEvent::Frame {
+                    bit_index: second_bit_index,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_xl_frames() {
+        let first_can_frame = assert_ok!(CANFrame::new_xl(
+            u11::new(0x000),
+            0x01,
+            u1::new(1),
+            0x00,
+            0x00000000,
+            vec![0x00; 2048]
+        ));
+        let second_can_frame = assert_ok!(CANFrame::new(
+            CANID::Standard(u11::new(0x123)),
+            vec![0xFF; 8]
+        ));
+
+        let mut bits = first_can_frame.to_stuffed_bits();
+        let second_bit_index = bits.len();
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANBusEventDecoder::decode(bits),
+            vec![
+                CANBusEvent::Frame {
+                    bit_index: 0,
+                    frame: first_can_frame,
+                },
+                CANBusEvent::Frame {
+                    bit_index: second_bit_index,
+                    frame: second_can_frame,
+                },
+            ]
+        );
+    }
+}
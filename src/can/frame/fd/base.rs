@@ -0,0 +1,612 @@
+use bitvec::prelude::*;
+use ux::{u1, u3, u4, u7, u11};
+
+use crate::can::dlc::DLC;
+use crate::can::frame::{
+    CANFrameDecodingError, CANFrameEncodingError, extract_field,
+    fd::{
+        cyclic_redundancy_check_length, fd_cyclic_redundancy_check, fd_stuff_count,
+        from_gray_code, gray_code, stuff_count_parity, verify_fd_cyclic_redundancy_check,
+    },
+    push_field,
+    stuffing::{stuff_frames, try_unstuff_frames},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FDBaseDataFrame {
+    start_of_frame: u1,
+    identifier: u11,
+    remote_request_substitution: u1,
+    identifier_extension_bit: u1,
+    flexible_data_rate_format: u1,
+    reserved_bit: u1,
+    bit_rate_switch: u1,
+    error_state_indicator: u1,
+    data_length_code: DLC,
+    data_field: Vec<u8>,
+    stuff_count: u3,
+    stuff_count_parity: u1,
+    cyclic_redundancy_check: u32,
+    cyclic_redundancy_check_delimiter: u1,
+    acknowledgement_slot: u1,
+    acknowledgement_delimiter: u1,
+    end_of_frame: u7,
+    inter_frame_spacing: u3,
+}
+
+impl FDBaseDataFrame {
+    pub fn new(
+        identifier: u11,
+        data_field: Vec<u8>,
+        bit_rate_switch: u1,
+    ) -> Result<Self, CANFrameEncodingError> {
+        let data_length_code = DLC::from_len_fd(data_field.len())
+            .ok_or(CANFrameEncodingError::DataFieldTooLong)?;
+
+        // Lengths that would need padding are rejected rather than padded.
+        if data_length_code.to_len_fd() != data_field.len() {
+            return Err(CANFrameEncodingError::DataFieldLengthInvalid);
+        }
+
+        let mut fd_base_data_frame = Self {
+            start_of_frame: u1::new(0),
+            identifier,
+            remote_request_substitution: u1::new(0),
+            identifier_extension_bit: u1::new(0),
+            flexible_data_rate_format: u1::new(1),
+            reserved_bit: u1::new(0),
+            bit_rate_switch,
+            error_state_indicator: u1::new(0),
+            data_length_code,
+            data_field,
+            stuff_count: u3::new(0),
+            stuff_count_parity: u1::new(0),
+            cyclic_redundancy_check: 0,
+            cyclic_redundancy_check_delimiter: u1::new(1),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        let cyclic_redundancy_check_sequence =
+            fd_base_data_frame.cyclic_redundancy_check_sequence();
+
+        fd_base_data_frame.stuff_count = fd_stuff_count(&cyclic_redundancy_check_sequence);
+        fd_base_data_frame.stuff_count_parity = stuff_count_parity(fd_base_data_frame.stuff_count);
+        fd_base_data_frame.cyclic_redundancy_check = fd_cyclic_redundancy_check(
+            &cyclic_redundancy_check_sequence,
+            fd_base_data_frame.stuff_count,
+            fd_base_data_frame.stuff_count_parity,
+            fd_base_data_frame.data_field.len(),
+        );
+
+        Ok(fd_base_data_frame)
+    }
+
+    pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let mut bit_index = 0;
+
+        let start_of_frame = extract_field(
+            unstuffed_bits,
+            bit_index,
+            1,
+            CANFrameDecodingError::StartOfFrameMissing,
+            |start_of_frame_bit_slice| u1::new(start_of_frame_bit_slice.load_be()),
+        )?;
+
+        if start_of_frame != u1::new(0) {
+            return Err(CANFrameDecodingError::StartOfFrameMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let identifier = extract_field(
+            unstuffed_bits,
+            bit_index,
+            11,
+            CANFrameDecodingError::IdentifierMissing,
+            |identifier_bit_slice| u11::new(identifier_bit_slice.load_be()),
+        )?;
+
+        bit_index += 11;
+
+        let maybe_remote_request_substitution_bit_ref = unstuffed_bits.get(bit_index);
+        let remote_request_substitution_bit_ref = maybe_remote_request_substitution_bit_ref
+            .ok_or(CANFrameDecodingError::RemoteRequestSubstitutionMissing)?;
+        let remote_request_substitution = u1::from(*remote_request_substitution_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_identifier_extension_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let identifier_extension_bit_bit_ref = maybe_identifier_extension_bit_bit_ref
+            .ok_or(CANFrameDecodingError::IdentifierExtensionBitMissing)?;
+        let identifier_extension_bit = u1::from(*identifier_extension_bit_bit_ref);
+        if identifier_extension_bit != u1::new(0) {
+            return Err(CANFrameDecodingError::IdentifierExtensionBitMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let maybe_flexible_data_rate_format_bit_ref = unstuffed_bits.get(bit_index);
+        let flexible_data_rate_format_bit_ref = maybe_flexible_data_rate_format_bit_ref
+            .ok_or(CANFrameDecodingError::FlexibleDataRateFormatMissing)?;
+        let flexible_data_rate_format = u1::from(*flexible_data_rate_format_bit_ref);
+        if flexible_data_rate_format != u1::new(1) {
+            return Err(CANFrameDecodingError::FlexibleDataRateFormatMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_reserved_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let reserved_bit_bit_ref =
+            maybe_reserved_bit_bit_ref.ok_or(CANFrameDecodingError::ReservedBitMissing)?;
+        let reserved_bit = u1::from(*reserved_bit_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_bit_rate_switch_bit_ref = unstuffed_bits.get(bit_index);
+        let bit_rate_switch_bit_ref =
+            maybe_bit_rate_switch_bit_ref.ok_or(CANFrameDecodingError::BitRateSwitchMissing)?;
+        let bit_rate_switch = u1::from(*bit_rate_switch_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_error_state_indicator_bit_ref = unstuffed_bits.get(bit_index);
+        let error_state_indicator_bit_ref = maybe_error_state_indicator_bit_ref
+            .ok_or(CANFrameDecodingError::ErrorStateIndicatorMissing)?;
+        let error_state_indicator = u1::from(*error_state_indicator_bit_ref);
+
+        bit_index += 1;
+
+        let data_length_code = extract_field(
+            unstuffed_bits,
+            bit_index,
+            4,
+            CANFrameDecodingError::DataLengthCodeMissing,
+            |data_length_code_bit_slice| {
+                DLC::new(u4::new(data_length_code_bit_slice.load_be()))
+            },
+        )?;
+
+        bit_index += 4;
+
+        let data_field_length = data_length_code.to_len_fd();
+
+        let data_field = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8 * data_field_length,
+            CANFrameDecodingError::DataFieldMissing,
+            |data_field_bit_slice| {
+                data_field_bit_slice
+                    .chunks(8)
+                    .map(|data_byte_bit_slice| data_byte_bit_slice.load_be::<u8>())
+                    .collect::<Vec<u8>>()
+            },
+        )?;
+
+        bit_index += 8 * data_field_length;
+
+        let stuff_count = extract_field(
+            unstuffed_bits,
+            bit_index,
+            3,
+            CANFrameDecodingError::StuffCountMissing,
+            |stuff_count_bit_slice| from_gray_code(u3::new(stuff_count_bit_slice.load_be())),
+        )?;
+
+        bit_index += 3;
+
+        let maybe_stuff_count_parity_bit_ref = unstuffed_bits.get(bit_index);
+        let stuff_count_parity_bit_ref =
+            maybe_stuff_count_parity_bit_ref.ok_or(CANFrameDecodingError::StuffCountMissing)?;
+        let stuff_count_parity = u1::from(*stuff_count_parity_bit_ref);
+
+        bit_index += 1;
+
+        let cyclic_redundancy_check_length = cyclic_redundancy_check_length(data_field_length);
+
+        let cyclic_redundancy_check = extract_field(
+            unstuffed_bits,
+            bit_index,
+            cyclic_redundancy_check_length,
+            CANFrameDecodingError::CyclicRedundancyCheckMissing,
+            |cyclic_redundancy_check_bit_slice| cyclic_redundancy_check_bit_slice.load_be::<u32>(),
+        )?;
+
+        bit_index += cyclic_redundancy_check_length;
+
+        let maybe_cyclic_redundancy_check_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let cyclic_redundancy_check_delimiter_bit_ref =
+            maybe_cyclic_redundancy_check_delimiter_bit_ref
+                .ok_or(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMissing)?;
+        let cyclic_redundancy_check_delimiter =
+            u1::from(*cyclic_redundancy_check_delimiter_bit_ref);
+        if cyclic_redundancy_check_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_slot_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_slot_bit_ref = maybe_acknowledgement_slot_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementSlotMissing)?;
+        let acknowledgement_slot = u1::from(*acknowledgement_slot_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_delimiter_bit_ref = maybe_acknowledgement_delimiter_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementDelimiterMissing)?;
+        let acknowledgement_delimiter = u1::from(*acknowledgement_delimiter_bit_ref);
+        if acknowledgement_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::AcknowledgementDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
+        let end_of_frame_bit_slice =
+            maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
+        if end_of_frame != u7::new(0b1111111) {
+            return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
+        }
+
+        bit_index += 7;
+
+        let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
+        let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
+            .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
+        if inter_frame_spacing != u3::new(0b111) {
+            return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
+        }
+
+        Ok(Self {
+            start_of_frame,
+            identifier,
+            remote_request_substitution,
+            identifier_extension_bit,
+            flexible_data_rate_format,
+            reserved_bit,
+            bit_rate_switch,
+            error_state_indicator,
+            data_length_code,
+            data_field,
+            stuff_count,
+            stuff_count_parity,
+            cyclic_redundancy_check,
+            cyclic_redundancy_check_delimiter,
+            acknowledgement_slot,
+            acknowledgement_delimiter,
+            end_of_frame,
+            inter_frame_spacing,
+        })
+    }
+
+    pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let fd_base_data_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        verify_fd_cyclic_redundancy_check(
+            unstuffed_bits,
+            fd_base_data_frame.data_field().len(),
+            22 + 8 * fd_base_data_frame.data_field().len(),
+            fd_base_data_frame.stuff_count(),
+            fd_base_data_frame.stuff_count_parity(),
+            fd_base_data_frame.cyclic_redundancy_check(),
+        )?;
+
+        Ok(fd_base_data_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    fn cyclic_redundancy_check_sequence(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.identifier), 11);
+        push_field(&mut bits, u64::from(self.remote_request_substitution), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.flexible_data_rate_format), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit), 1);
+        push_field(&mut bits, u64::from(self.bit_rate_switch), 1);
+        push_field(&mut bits, u64::from(self.error_state_indicator), 1);
+        push_field(&mut bits, u64::from(self.data_length_code.raw()), 4);
+
+        for data_byte in &self.data_field {
+            push_field(&mut bits, u64::from(*data_byte), 8);
+        }
+
+        bits
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = self.cyclic_redundancy_check_sequence();
+
+        push_field(&mut bits, u64::from(gray_code(self.stuff_count)), 3);
+        push_field(&mut bits, u64::from(self.stuff_count_parity), 1);
+        push_field(
+            &mut bits,
+            u64::from(self.cyclic_redundancy_check),
+            cyclic_redundancy_check_length(self.data_field.len()),
+        );
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check_delimiter), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
+    }
+
+    pub fn start_of_frame(&self) -> u1 {
+        self.start_of_frame
+    }
+
+    pub fn identifier(&self) -> u11 {
+        self.identifier
+    }
+
+    pub fn remote_request_substitution(&self) -> u1 {
+        self.remote_request_substitution
+    }
+
+    pub fn identifier_extension_bit(&self) -> u1 {
+        self.identifier_extension_bit
+    }
+
+    pub fn flexible_data_rate_format(&self) -> u1 {
+        self.flexible_data_rate_format
+    }
+
+    pub fn reserved_bit(&self) -> u1 {
+        self.reserved_bit
+    }
+
+    pub fn bit_rate_switch(&self) -> u1 {
+        self.bit_rate_switch
+    }
+
+    pub fn error_state_indicator(&self) -> u1 {
+        self.error_state_indicator
+    }
+
+    pub fn data_length_code(&self) -> DLC {
+        self.data_length_code
+    }
+
+    pub fn data_field(&self) -> &Vec<u8> {
+        &self.data_field
+    }
+
+    pub fn stuff_count(&self) -> u3 {
+        self.stuff_count
+    }
+
+    pub fn stuff_count_parity(&self) -> u1 {
+        self.stuff_count_parity
+    }
+
+    pub fn cyclic_redundancy_check(&self) -> u32 {
+        self.cyclic_redundancy_check
+    }
+
+    pub fn cyclic_redundancy_check_delimiter(&self) -> u1 {
+        self.cyclic_redundancy_check_delimiter
+    }
+
+    pub fn acknowledgement_slot(&self) -> u1 {
+        self.acknowledgement_slot
+    }
+
+    pub fn acknowledgement_delimiter(&self) -> u1 {
+        self.acknowledgement_delimiter
+    }
+
+    pub fn end_of_frame(&self) -> u7 {
+        self.end_of_frame
+    }
+
+    pub fn inter_frame_spacing(&self) -> u3 {
+        self.inter_frame_spacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::*;
+
+    #[test]
+    fn test_from_unstuffed_bits_checked() {
+        let fd_base_data_frame =
+            assert_ok!(FDBaseDataFrame::from_unstuffed_bits_checked(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 1, 0, 0, 1, 0, 0, 0, 1, 1,                        // ID
+                0,                                                      // RRS
+                0,                                                      // IDE
+                1,                                                      // FDF
+                0,                                                      // RES
+                1,                                                      // BRS
+                0,                                                      // ESI
+                1, 0, 0, 1,                                             // DLC
+                1, 0, 1, 0, 1, 0, 1, 0,                                 // DF
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 1,
+                0, 0, 1,                                                // SC
+                1,                                                      // SC Parity
+                0, 0, 1, 1, 1, 0, 1, 0, 1, 1, 1, 0, 1, 1, 1, 0, 1,      // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ]));
+
+        assert_eq!(fd_base_data_frame.identifier(), u11::new(0x123));
+        assert_eq!(fd_base_data_frame.flexible_data_rate_format(), u1::new(1));
+        assert_eq!(fd_base_data_frame.bit_rate_switch(), u1::new(1));
+        assert_eq!(fd_base_data_frame.error_state_indicator(), u1::new(0));
+        assert_eq!(fd_base_data_frame.data_length_code(), DLC::new(u4::new(9)));
+        assert_eq!(
+            fd_base_data_frame.data_field(),
+            &vec![0xAA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]
+        );
+        assert_eq!(fd_base_data_frame.stuff_count(), u3::new(1));
+        assert_eq!(fd_base_data_frame.stuff_count_parity(), u1::new(1));
+        assert_eq!(
+            fd_base_data_frame.cyclic_redundancy_check(),
+            0b00111010111011101
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let fd_base_data_frame = assert_ok!(FDBaseDataFrame::new(
+            u11::new(0x123),
+            vec![0xAA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+            u1::new(1)
+        ));
+
+        assert_eq!(fd_base_data_frame.data_length_code(), DLC::new(u4::new(9)));
+        assert_eq!(fd_base_data_frame.stuff_count(), u3::new(1));
+        assert_eq!(
+            fd_base_data_frame.cyclic_redundancy_check(),
+            0b00111010111011101
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for data_field_length in [0, 1, 8, 12, 16, 20, 32, 64] {
+            let fd_base_data_frame = assert_ok!(FDBaseDataFrame::new(
+                u11::new(0x7FF),
+                (0..data_field_length).map(|index| index as u8).collect(),
+                u1::new(0)
+            ));
+
+            assert_eq!(
+                assert_ok!(FDBaseDataFrame::from_stuffed_bits_checked(
+                    fd_base_data_frame.to_stuffed_bits()
+                )),
+                fd_base_data_frame
+            );
+        }
+    }
+
+    #[test]
+    fn test_data_field_length_invalid() {
+        assert!(matches!(
+            assert_err!(FDBaseDataFrame::new(u11::new(0x123), vec![0; 9], u1::new(0))),
+            CANFrameEncodingError::DataFieldLengthInvalid
+        ));
+        assert!(matches!(
+            assert_err!(FDBaseDataFrame::new(u11::new(0x123), vec![0; 65], u1::new(0))),
+            CANFrameEncodingError::DataFieldTooLong
+        ));
+    }
+
+    #[test]
+    fn test_stuff_count_mismatch() {
+        let mut bits = assert_ok!(FDBaseDataFrame::new(
+            u11::new(0x123),
+            vec![0x00, 0x00],
+            u1::new(0)
+        ))
+        .to_unstuffed_bits();
+
+        // Replace the stuff count and its parity with a count of zero.
+        bits[38..42].store_be(0u8);
+
+        assert_eq!(
+            assert_err!(FDBaseDataFrame::from_unstuffed_bits_checked(bits)),
+            CANFrameDecodingError::StuffCountMismatch {
+                expected: u3::new(4),
+                received: u3::new(0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stuff_count_parity_mismatch() {
+        let mut bits = assert_ok!(FDBaseDataFrame::new(
+            u11::new(0x123),
+            vec![0x00, 0x00],
+            u1::new(0)
+        ))
+        .to_unstuffed_bits();
+
+        let stuff_count_parity = bits[41];
+        bits.set(41, !stuff_count_parity);
+
+        assert_eq!(
+            assert_err!(FDBaseDataFrame::from_unstuffed_bits_checked(bits)),
+            CANFrameDecodingError::StuffCountParityMismatch
+        );
+    }
+
+    #[test]
+    fn test_cyclic_redundancy_check_mismatch() {
+        let mut bits = assert_ok!(FDBaseDataFrame::new(
+            u11::new(0x123),
+            vec![0x00, 0x00],
+            u1::new(0)
+        ))
+        .to_unstuffed_bits();
+
+        let cyclic_redundancy_check_bit = bits[42];
+        bits.set(42, !cyclic_redundancy_check_bit);
+
+        assert!(matches!(
+            assert_err!(FDBaseDataFrame::from_unstuffed_bits_checked(bits)),
+            CANFrameDecodingError::FDCyclicRedundancyCheckMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_flexible_data_rate_format_must_be_one() {
+        let mut bits = assert_ok!(FDBaseDataFrame::new(u11::new(0x123), vec![], u1::new(0)))
+            .to_unstuffed_bits();
+        bits.set(14, false);
+
+        assert_eq!(
+            assert_err!(FDBaseDataFrame::from_unstuffed_bits(bits)),
+            CANFrameDecodingError::FlexibleDataRateFormatMustBeOne
+        );
+    }
+}
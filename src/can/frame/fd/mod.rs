@@ -0,0 +1,168 @@
+use bitvec::prelude::*;
+use ux::{u1, u3};
+
+use crate::can::frame::{
+    BASE_FLEXIBLE_DATA_RATE_FORMAT_INDEX, CANFrameDecodingError, CANFrameFormat,
+    EXTENDED_FLEXIBLE_DATA_RATE_FORMAT_INDEX,
+    crc::{crc17, crc21},
+    push_field,
+    stuffing::stuff_dynamically,
+};
+
+pub mod base;
+pub mod extended;
+
+const BIT_RATE_SWITCH_OFFSET: usize = 2;
+
+pub(crate) fn is_flexible_data_rate_format<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+) -> bool {
+    ends_with_recessive_bit(unstuffed_bits, 0)
+}
+
+pub(crate) fn is_bit_rate_switch<T: BitStore>(unstuffed_bits: &BitSlice<T, Msb0>) -> bool {
+    ends_with_recessive_bit(unstuffed_bits, BIT_RATE_SWITCH_OFFSET)
+}
+
+fn ends_with_recessive_bit<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+    flexible_data_rate_format_offset: usize,
+) -> bool {
+    let flexible_data_rate_format_index =
+        match CANFrameFormat::from_unstuffed_bits(unstuffed_bits) {
+            Ok(CANFrameFormat::FDBase) => BASE_FLEXIBLE_DATA_RATE_FORMAT_INDEX,
+            Ok(CANFrameFormat::FDExtended) => EXTENDED_FLEXIBLE_DATA_RATE_FORMAT_INDEX,
+            _ => return false,
+        };
+
+    unstuffed_bits.len() == flexible_data_rate_format_index + flexible_data_rate_format_offset + 1
+        && unstuffed_bits.last().is_some_and(|bit| *bit)
+}
+
+pub(crate) fn cyclic_redundancy_check_length(data_field_length: usize) -> usize {
+    if data_field_length <= 16 { 17 } else { 21 }
+}
+
+pub(crate) fn gray_code(stuff_count: u3) -> u3 {
+    stuff_count ^ (stuff_count >> 1)
+}
+
+pub(crate) fn from_gray_code(gray_code: u3) -> u3 {
+    let mut stuff_count = gray_code;
+    let mut shifted_gray_code = gray_code >> 1;
+
+    while shifted_gray_code != u3::new(0) {
+        stuff_count ^= shifted_gray_code;
+        shifted_gray_code >>= 1;
+    }
+
+    stuff_count
+}
+
+pub(crate) fn stuff_count_parity(stuff_count: u3) -> u1 {
+    u1::new((u8::from(gray_code(stuff_count)).count_ones() % 2) as u8)
+}
+
+pub(crate) fn fd_stuff_count<T: BitStore>(
+    cyclic_redundancy_check_sequence: &BitSlice<T, Msb0>,
+) -> u3 {
+    let stuffed_bits = stuff_dynamically(cyclic_redundancy_check_sequence);
+    let stuff_bit_count = stuffed_bits.len() - cyclic_redundancy_check_sequence.len();
+
+    u3::new((stuff_bit_count % 8) as u8)
+}
+
+pub(crate) fn fd_cyclic_redundancy_check<T: BitStore>(
+    cyclic_redundancy_check_sequence: &BitSlice<T, Msb0>,
+    stuff_count: u3,
+    stuff_count_parity: u1,
+    data_field_length: usize,
+) -> u32 {
+    // Unlike classic frames, the CRC covers the dynamic stuff bits and the stuff count.
+    let mut bits = stuff_dynamically(cyclic_redundancy_check_sequence);
+    push_field(&mut bits, u64::from(gray_code(stuff_count)), 3);
+    push_field(&mut bits, u64::from(stuff_count_parity), 1);
+
+    match cyclic_redundancy_check_length(data_field_length) {
+        17 => u32::from(crc17(bits)),
+        _ => u32::from(crc21(bits)),
+    }
+}
+
+pub(crate) fn verify_fd_cyclic_redundancy_check<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+    data_field_length: usize,
+    stuff_count_offset: usize,
+    stuff_count: u3,
+    stuff_count_parity: u1,
+    received: u32,
+) -> Result<(), CANFrameDecodingError> {
+    let cyclic_redundancy_check_sequence = &unstuffed_bits[..stuff_count_offset];
+
+    if self::stuff_count_parity(stuff_count) != stuff_count_parity {
+        return Err(CANFrameDecodingError::StuffCountParityMismatch);
+    }
+
+    let expected_stuff_count = fd_stuff_count(cyclic_redundancy_check_sequence);
+    if expected_stuff_count != stuff_count {
+        return Err(CANFrameDecodingError::StuffCountMismatch {
+            expected: expected_stuff_count,
+            received: stuff_count,
+        });
+    }
+
+    let expected = fd_cyclic_redundancy_check(
+        cyclic_redundancy_check_sequence,
+        stuff_count,
+        stuff_count_parity,
+        data_field_length,
+    );
+
+    if expected != received {
+        return Err(CANFrameDecodingError::FDCyclicRedundancyCheckMismatch { expected, received });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gray_code() {
+        let gray_codes = [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100];
+
+        for (stuff_count, gray_code) in gray_codes.into_iter().enumerate() {
+            let stuff_count = u3::new(stuff_count as u8);
+
+            assert_eq!(self::gray_code(stuff_count), u3::new(gray_code));
+            assert_eq!(from_gray_code(u3::new(gray_code)), stuff_count);
+        }
+    }
+
+    #[test]
+    fn test_stuff_count_parity() {
+        assert_eq!(stuff_count_parity(u3::new(0)), u1::new(0));
+        assert_eq!(stuff_count_parity(u3::new(1)), u1::new(1));
+        assert_eq!(stuff_count_parity(u3::new(2)), u1::new(0));
+        assert_eq!(stuff_count_parity(u3::new(5)), u1::new(1));
+    }
+
+    #[test]
+    fn test_is_bit_rate_switch() {
+        // SOF, ID 0x123, RRS, IDE, FDF, res, BRS
+        let unstuffed_bits = bits![usize, Msb0; 0, 0,0,1,0,0,1,0,0,0,1,1, 0, 0, 1, 0, 1];
+
+        assert!(is_flexible_data_rate_format(&unstuffed_bits[..15]));
+        assert!(!is_flexible_data_rate_format(&unstuffed_bits[..16]));
+        assert!(is_bit_rate_switch(unstuffed_bits));
+        assert!(!is_bit_rate_switch(&unstuffed_bits[..16]));
+    }
+
+    #[test]
+    fn test_cyclic_redundancy_check_length() {
+        assert_eq!(cyclic_redundancy_check_length(16), 17);
+        assert_eq!(cyclic_redundancy_check_length(20), 21);
+    }
+}
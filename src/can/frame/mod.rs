@@ -1,10 +1,39 @@
 use std::error::Error;
 
+use bitvec::prelude::*;
 use strum::Display;
+use ux::{u1, u3, u4, u11, u13, u15};
 
+use crate::{
+    can::{
+        can_id::CANID,
+        dlc::DLC,
+        frame::{
+            crc::crc15,
+            data::{base::BaseDataFrame, extended::ExtendedDataFrame},
+            fd::{
+                base::FDBaseDataFrame, cyclic_redundancy_check_length,
+                extended::FDExtendedDataFrame,
+            },
+            remote::{base::BaseRemoteFrame, extended::ExtendedRemoteFrame},
+            stuffing::{FIXED_STUFF_INTERVAL, try_unstuff_frames},
+            xl::{XLDataFrame, xl_stuffing_layout},
+        },
+    },
+    BitStuffingError,
+};
+
+pub mod arbitration;
+pub mod crc;
 pub mod data;
+pub mod event;
+pub mod fd;
+pub mod remote;
+pub mod splitter;
+pub mod stuffing;
+pub mod xl;
 
-#[derive(Display, Debug, Copy, Clone)]
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CANFrameDecodingError {
     StartOfFrameMissing,
     StartOfFrameMustBeZero,
@@ -13,13 +42,44 @@ pub enum CANFrameDecodingError {
     IdentifierBMissing,
     SubstituteRemoteRequestMissing,
     SubstituteRemoteRequestMustBeOne,
+    RemoteRequestSubstitutionMissing,
     RemoteTransmissionRequestMissing,
+    RemoteTransmissionRequestMustBeZero,
+    RemoteTransmissionRequestMustBeOne,
     IdentifierExtensionBitMissing,
     IdentifierExtensionBitMustBeZero,
     IdentifierExtensionBitMustBeOne,
+    FlexibleDataRateFormatMissing,
+    FlexibleDataRateFormatMustBeOne,
+    XLFormatMissing,
+    XLFormatMustBeOne,
+    ReservedBitMissing,
+    BitRateSwitchMissing,
+    ErrorStateIndicatorMissing,
+    ArbitrationToDataSequenceMissing,
+    ArbitrationToDataSequenceInvalid,
+    ServiceDataUnitTypeMissing,
+    SimpleExtendedContentMissing,
+    DataLengthCodeMissing,
     ReservedBitOneMissing,
     ReservedBitZeroMissing,
+    DataFieldMissing,
+    StuffError { bit_index: usize },
+    StuffCountMissing,
+    StuffCountMismatch { expected: u3, received: u3 },
+    StuffCountParityMismatch,
+    PrefaceCyclicRedundancyCheckMissing,
+    PrefaceCyclicRedundancyCheckMismatch { expected: u13, received: u13 },
+    VirtualCANNetworkIdentifierMissing,
+    AcceptanceFieldMissing,
     CyclicRedundancyCheckMissing,
+    CyclicRedundancyCheckMismatch { expected: u15, received: u15 },
+    FDCyclicRedundancyCheckMismatch { expected: u32, received: u32 },
+    FrameCyclicRedundancyCheckMismatch { expected: u32, received: u32 },
+    FormatCheckPatternMissing,
+    FormatCheckPatternInvalid,
+    DataToArbitrationSequenceMissing,
+    DataToArbitrationSequenceInvalid,
     CyclicRedundancyCheckDelimiterMissing,
     CyclicRedundancyCheckDelimiterMustBeOne,
     AcknowledgementSlotMissing,
@@ -32,3 +92,774 @@ pub enum CANFrameDecodingError {
 }
 
 impl Error for CANFrameDecodingError {}
+
+impl From<BitStuffingError> for CANFrameDecodingError {
+    fn from(value: BitStuffingError) -> Self {
+        Self::StuffError {
+            bit_index: value.bit_index(),
+        }
+    }
+}
+
+#[derive(Display, Debug, Copy, Clone)]
+pub enum CANFrameEncodingError {
+    DataFieldTooLong,
+    DataFieldLengthInvalid,
+}
+
+impl Error for CANFrameEncodingError {}
+
+const BASE_REMOTE_TRANSMISSION_REQUEST_INDEX: usize = 12;
+const IDENTIFIER_EXTENSION_BIT_INDEX: usize = 13;
+const EXTENDED_REMOTE_TRANSMISSION_REQUEST_INDEX: usize = 32;
+const BASE_FLEXIBLE_DATA_RATE_FORMAT_INDEX: usize = 14;
+const EXTENDED_FLEXIBLE_DATA_RATE_FORMAT_INDEX: usize = 33;
+const XL_FORMAT_INDEX: usize = 15;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CANFrame {
+    Base(BaseDataFrame),
+    Extended(ExtendedDataFrame),
+    BaseRemote(BaseRemoteFrame),
+    ExtendedRemote(ExtendedRemoteFrame),
+    FDBase(FDBaseDataFrame),
+    FDExtended(FDExtendedDataFrame),
+    XL(XLDataFrame),
+}
+
+impl CANFrame {
+    pub fn new(identifier: CANID, data_field: Vec<u8>) -> Result<Self, CANFrameEncodingError> {
+        match identifier {
+            CANID::Standard(identifier) => {
+                Ok(Self::Base(BaseDataFrame::new(identifier, data_field)?))
+            }
+            CANID::Extended(identifier) => {
+                Ok(Self::Extended(ExtendedDataFrame::new(identifier, data_field)?))
+            }
+        }
+    }
+
+    pub fn new_fd(
+        identifier: CANID,
+        data_field: Vec<u8>,
+        bit_rate_switch: u1,
+    ) -> Result<Self, CANFrameEncodingError> {
+        match identifier {
+            CANID::Standard(identifier) => Ok(Self::FDBase(FDBaseDataFrame::new(
+                identifier,
+                data_field,
+                bit_rate_switch,
+            )?)),
+            CANID::Extended(identifier) => Ok(Self::FDExtended(FDExtendedDataFrame::new(
+                identifier,
+                data_field,
+                bit_rate_switch,
+            )?)),
+        }
+    }
+
+    pub fn new_xl(
+        priority_identifier: u11,
+        service_data_unit_type: u8,
+        simple_extended_content: u1,
+        virtual_can_network_identifier: u8,
+        acceptance_field: u32,
+        data_field: Vec<u8>,
+    ) -> Result<Self, CANFrameEncodingError> {
+        Ok(Self::XL(XLDataFrame::new(
+            priority_identifier,
+            service_data_unit_type,
+            simple_extended_content,
+            virtual_can_network_identifier,
+            acceptance_field,
+            data_field,
+        )?))
+    }
+
+    pub fn new_remote(identifier: CANID, data_length_code: DLC) -> Self {
+        match identifier {
+            CANID::Standard(identifier) => {
+                Self::BaseRemote(BaseRemoteFrame::new(identifier, data_length_code))
+            }
+            CANID::Extended(identifier) => {
+                Self::ExtendedRemote(ExtendedRemoteFrame::new(identifier, data_length_code))
+            }
+        }
+    }
+
+    pub fn from_parts(
+        identifier: CANID,
+        remote_transmission_request: u1,
+        data_field: Vec<u8>,
+    ) -> Result<Self, CANFrameEncodingError> {
+        if remote_transmission_request == u1::new(0) {
+            return Self::new(identifier, data_field);
+        }
+
+        // A remote frame sends no data, the payload only sets how many bytes are requested
+        let data_length_code = DLC::from_len_classic(data_field.len())
+            .ok_or(CANFrameEncodingError::DataFieldTooLong)?;
+
+        Ok(Self::new_remote(identifier, data_length_code))
+    }
+
+    pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        match CANFrameFormat::from_unstuffed_bits(unstuffed_bits)? {
+            CANFrameFormat::Base => Ok(Self::Base(BaseDataFrame::from_unstuffed_bits(
+                unstuffed_bits,
+            )?)),
+            CANFrameFormat::Extended => Ok(Self::Extended(
+                ExtendedDataFrame::from_unstuffed_bits(unstuffed_bits)?,
+            )),
+            CANFrameFormat::BaseRemote => Ok(Self::BaseRemote(
+                BaseRemoteFrame::from_unstuffed_bits(unstuffed_bits)?,
+            )),
+            CANFrameFormat::ExtendedRemote => Ok(Self::ExtendedRemote(
+                ExtendedRemoteFrame::from_unstuffed_bits(unstuffed_bits)?,
+            )),
+            CANFrameFormat::FDBase => Ok(Self::FDBase(FDBaseDataFrame::from_unstuffed_bits(
+                unstuffed_bits,
+            )?)),
+            CANFrameFormat::FDExtended => Ok(Self::FDExtended(
+                FDExtendedDataFrame::from_unstuffed_bits(unstuffed_bits)?,
+            )),
+            CANFrameFormat::XL => Ok(Self::XL(XLDataFrame::from_unstuffed_bits(unstuffed_bits)?)),
+        }
+    }
+
+    pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        match CANFrameFormat::from_unstuffed_bits(unstuffed_bits)? {
+            CANFrameFormat::Base => Ok(Self::Base(
+                BaseDataFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::Extended => Ok(Self::Extended(
+                ExtendedDataFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::BaseRemote => Ok(Self::BaseRemote(
+                BaseRemoteFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::ExtendedRemote => Ok(Self::ExtendedRemote(
+                ExtendedRemoteFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::FDBase => Ok(Self::FDBase(
+                FDBaseDataFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::FDExtended => Ok(Self::FDExtended(
+                FDExtendedDataFrame::from_unstuffed_bits_checked(unstuffed_bits)?,
+            )),
+            CANFrameFormat::XL => Ok(Self::XL(XLDataFrame::from_unstuffed_bits_checked(
+                unstuffed_bits,
+            )?)),
+        }
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        match self {
+            Self::Base(base_data_frame) => base_data_frame.to_unstuffed_bits(),
+            Self::Extended(extended_data_frame) => extended_data_frame.to_unstuffed_bits(),
+            Self::BaseRemote(base_remote_frame) => base_remote_frame.to_unstuffed_bits(),
+            Self::ExtendedRemote(extended_remote_frame) => {
+                extended_remote_frame.to_unstuffed_bits()
+            }
+            Self::FDBase(fd_base_data_frame) => fd_base_data_frame.to_unstuffed_bits(),
+            Self::FDExtended(fd_extended_data_frame) => fd_extended_data_frame.to_unstuffed_bits(),
+            Self::XL(xl_data_frame) => xl_data_frame.to_unstuffed_bits(),
+        }
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        match self {
+            Self::Base(base_data_frame) => base_data_frame.to_stuffed_bits(),
+            Self::Extended(extended_data_frame) => extended_data_frame.to_stuffed_bits(),
+            Self::BaseRemote(base_remote_frame) => base_remote_frame.to_stuffed_bits(),
+            Self::ExtendedRemote(extended_remote_frame) => extended_remote_frame.to_stuffed_bits(),
+            Self::FDBase(fd_base_data_frame) => fd_base_data_frame.to_stuffed_bits(),
+            Self::FDExtended(fd_extended_data_frame) => fd_extended_data_frame.to_stuffed_bits(),
+            Self::XL(xl_data_frame) => xl_data_frame.to_stuffed_bits(),
+        }
+    }
+
+    pub fn identifier(&self) -> CANID {
+        match self {
+            Self::Base(base_data_frame) => CANID::Standard(base_data_frame.identifier()),
+            Self::Extended(extended_data_frame) => {
+                CANID::Extended(extended_data_frame.identifier())
+            }
+            Self::BaseRemote(base_remote_frame) => CANID::Standard(base_remote_frame.identifier()),
+            Self::ExtendedRemote(extended_remote_frame) => {
+                CANID::Extended(extended_remote_frame.identifier())
+            }
+            Self::FDBase(fd_base_data_frame) => CANID::Standard(fd_base_data_frame.identifier()),
+            Self::FDExtended(fd_extended_data_frame) => {
+                CANID::Extended(fd_extended_data_frame.identifier())
+            }
+            Self::XL(xl_data_frame) => CANID::Standard(xl_data_frame.priority_identifier()),
+        }
+    }
+
+    pub fn remote_transmission_request(&self) -> u1 {
+        match self {
+            Self::Base(base_data_frame) => base_data_frame.remote_transmission_request(),
+            Self::Extended(extended_data_frame) => {
+                extended_data_frame.remote_transmission_request()
+            }
+            Self::BaseRemote(base_remote_frame) => base_remote_frame.remote_transmission_request(),
+            Self::ExtendedRemote(extended_remote_frame) => {
+                extended_remote_frame.remote_transmission_request()
+            }
+            Self::FDBase(fd_base_data_frame) => fd_base_data_frame.remote_request_substitution(),
+            Self::FDExtended(fd_extended_data_frame) => {
+                fd_extended_data_frame.remote_request_substitution()
+            }
+            Self::XL(xl_data_frame) => xl_data_frame.remote_request_substitution(),
+        }
+    }
+
+    pub fn data_length_code(&self) -> Option<DLC> {
+        match self {
+            Self::Base(base_data_frame) => Some(base_data_frame.data_length_code()),
+            Self::Extended(extended_data_frame) => Some(extended_data_frame.data_length_code()),
+            Self::BaseRemote(base_remote_frame) => Some(base_remote_frame.data_length_code()),
+            Self::ExtendedRemote(extended_remote_frame) => {
+                Some(extended_remote_frame.data_length_code())
+            }
+            Self::FDBase(fd_base_data_frame) => Some(fd_base_data_frame.data_length_code()),
+            Self::FDExtended(fd_extended_data_frame) => {
+                Some(fd_extended_data_frame.data_length_code())
+            }
+            // The DLC of a CAN XL frame is eleven bits long.
+            Self::XL(_) => None,
+        }
+    }
+
+    pub fn data_field(&self) -> &[u8] {
+        match self {
+            Self::Base(base_data_frame) => base_data_frame.data_field(),
+            Self::Extended(extended_data_frame) => extended_data_frame.data_field(),
+            Self::BaseRemote(_) | Self::ExtendedRemote(_) => &[],
+            Self::FDBase(fd_base_data_frame) => fd_base_data_frame.data_field(),
+            Self::FDExtended(fd_extended_data_frame) => fd_extended_data_frame.data_field(),
+            Self::XL(xl_data_frame) => xl_data_frame.data_field(),
+        }
+    }
+}
+
+impl From<BaseDataFrame> for CANFrame {
+    fn from(value: BaseDataFrame) -> Self {
+        Self::Base(value)
+    }
+}
+
+impl From<ExtendedDataFrame> for CANFrame {
+    fn from(value: ExtendedDataFrame) -> Self {
+        Self::Extended(value)
+    }
+}
+
+impl From<BaseRemoteFrame> for CANFrame {
+    fn from(value: BaseRemoteFrame) -> Self {
+        Self::BaseRemote(value)
+    }
+}
+
+impl From<ExtendedRemoteFrame> for CANFrame {
+    fn from(value: ExtendedRemoteFrame) -> Self {
+        Self::ExtendedRemote(value)
+    }
+}
+
+impl From<FDBaseDataFrame> for CANFrame {
+    fn from(value: FDBaseDataFrame) -> Self {
+        Self::FDBase(value)
+    }
+}
+
+impl From<FDExtendedDataFrame> for CANFrame {
+    fn from(value: FDExtendedDataFrame) -> Self {
+        Self::FDExtended(value)
+    }
+}
+
+impl From<XLDataFrame> for CANFrame {
+    fn from(value: XLDataFrame) -> Self {
+        Self::XL(value)
+    }
+}
+
+enum CANFrameFormat {
+    Base,
+    Extended,
+    BaseRemote,
+    ExtendedRemote,
+    FDBase,
+    FDExtended,
+    XL,
+}
+
+impl CANFrameFormat {
+    fn from_unstuffed_bits<T: BitStore>(
+        unstuffed_bits: &BitSlice<T, Msb0>,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let maybe_identifier_extension_bit_bit_ref =
+            unstuffed_bits.get(IDENTIFIER_EXTENSION_BIT_INDEX);
+        let identifier_extension_bit_bit_ref = maybe_identifier_extension_bit_bit_ref
+            .ok_or(CANFrameDecodingError::IdentifierExtensionBitMissing)?;
+
+        let remote_transmission_request_index = if *identifier_extension_bit_bit_ref {
+            EXTENDED_REMOTE_TRANSMISSION_REQUEST_INDEX
+        } else {
+            BASE_REMOTE_TRANSMISSION_REQUEST_INDEX
+        };
+
+        let maybe_remote_transmission_request_bit_ref =
+            unstuffed_bits.get(remote_transmission_request_index);
+        let remote_transmission_request_bit_ref = maybe_remote_transmission_request_bit_ref
+            .ok_or(CANFrameDecodingError::RemoteTransmissionRequestMissing)?;
+
+        match (*identifier_extension_bit_bit_ref, *remote_transmission_request_bit_ref) {
+            (false, true) => return Ok(Self::BaseRemote),
+            (true, true) => return Ok(Self::ExtendedRemote),
+            _ => {}
+        }
+
+        // Remote frames are classic only, so the flexible data rate format bit follows a dominant
+        // remote transmission request bit.
+        let flexible_data_rate_format_index = if *identifier_extension_bit_bit_ref {
+            EXTENDED_FLEXIBLE_DATA_RATE_FORMAT_INDEX
+        } else {
+            BASE_FLEXIBLE_DATA_RATE_FORMAT_INDEX
+        };
+
+        let maybe_flexible_data_rate_format_bit_ref =
+            unstuffed_bits.get(flexible_data_rate_format_index);
+        let flexible_data_rate_format_bit_ref = maybe_flexible_data_rate_format_bit_ref
+            .ok_or(CANFrameDecodingError::FlexibleDataRateFormatMissing)?;
+
+        match (*identifier_extension_bit_bit_ref, *flexible_data_rate_format_bit_ref) {
+            (false, false) => Ok(Self::Base),
+            (true, false) => Ok(Self::Extended),
+            // In a base frame, a recessive bit after the flexible data rate format bit marks
+            // CAN XL.
+            (false, true) => match unstuffed_bits.get(XL_FORMAT_INDEX).as_deref() {
+                Some(true) => Ok(Self::XL),
+                _ => Ok(Self::FDBase),
+            },
+            (true, true) => Ok(Self::FDExtended),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct StuffingLayout {
+    dynamically_stuffed_length: usize,
+    fixed_stuffed_length: usize,
+    first_fixed_stuff_bit_index: usize,
+    fixed_stuff_interval: usize,
+    fixed_form: &'static BitSlice<u8, Msb0>,
+}
+
+impl StuffingLayout {
+    fn new(dynamically_stuffed_length: usize, fixed_stuffed_length: usize) -> Self {
+        Self {
+            dynamically_stuffed_length,
+            fixed_stuffed_length,
+            first_fixed_stuff_bit_index: 0,
+            fixed_stuff_interval: FIXED_STUFF_INTERVAL,
+            // CRC delimiter, ACK slot, ACK delimiter and EOF
+            fixed_form: bits![static u8, Msb0; 1, 0, 1, 1, 1, 1, 1, 1, 1, 1],
+        }
+    }
+
+    pub(crate) fn is_fixed_stuff_bit_index(&self, fixed_stuffed_index: usize) -> bool {
+        fixed_stuffed_index >= self.first_fixed_stuff_bit_index
+            && (fixed_stuffed_index - self.first_fixed_stuff_bit_index)
+                .is_multiple_of(self.fixed_stuff_interval)
+    }
+
+    pub(crate) fn cyclic_redundancy_check_delimiter_index(&self) -> usize {
+        self.dynamically_stuffed_length + self.fixed_stuffed_length
+    }
+
+    pub(crate) fn acknowledgement_slot_index(&self) -> usize {
+        // The ACK slot is followed by the ACK delimiter and EOF.
+        self.fixed_form.len() - 9
+    }
+}
+
+pub(crate) fn stuffing_layout<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+) -> Option<StuffingLayout> {
+    let can_frame_format = CANFrameFormat::from_unstuffed_bits(unstuffed_bits).ok()?;
+
+    let data_length_code_offset = match can_frame_format {
+        CANFrameFormat::Base | CANFrameFormat::BaseRemote => 15,
+        CANFrameFormat::Extended | CANFrameFormat::ExtendedRemote => 35,
+        CANFrameFormat::FDBase => 18,
+        CANFrameFormat::FDExtended => 37,
+        CANFrameFormat::XL => return Some(xl_stuffing_layout(unstuffed_bits)),
+    };
+
+    let data_length_code_bit_slice =
+        unstuffed_bits.get(data_length_code_offset..(data_length_code_offset + 4))?;
+    let data_length_code = DLC::new(u4::new(data_length_code_bit_slice.load_be()));
+    let data_field_offset = data_length_code_offset + 4;
+
+    match can_frame_format {
+        CANFrameFormat::Base | CANFrameFormat::Extended => Some(StuffingLayout::new(
+            data_field_offset + 8 * data_length_code.to_len_classic() + 15,
+            0,
+        )),
+        CANFrameFormat::BaseRemote | CANFrameFormat::ExtendedRemote => {
+            Some(StuffingLayout::new(data_field_offset + 15, 0))
+        }
+        CANFrameFormat::XL => None,
+        CANFrameFormat::FDBase | CANFrameFormat::FDExtended => {
+            let data_field_length = data_length_code.to_len_fd();
+
+            // The stuff count and the CRC use fixed stuff bits instead.
+            Some(StuffingLayout::new(
+                data_field_offset + 8 * data_field_length,
+                4 + cyclic_redundancy_check_length(data_field_length),
+            ))
+        }
+    }
+}
+
+fn extract_field<U, T, B, F>(
+    bits: B,
+    offset: usize,
+    length: usize,
+    missing_error: CANFrameDecodingError,
+    load: F,
+) -> Result<U, CANFrameDecodingError>
+where
+    T: BitStore,
+    B: AsRef<BitSlice<T, Msb0>>,
+    F: Fn(&BitSlice<T, Msb0>) -> U
+{
+    let maybe_bit_slice = bits.as_ref().get(offset..(offset + length));
+    let bit_slice = maybe_bit_slice.ok_or(missing_error)?;
+    Ok(load(bit_slice))
+}
+
+fn push_field<T: BitStore>(bits: &mut BitVec<T, Msb0>, value: u64, length: usize) {
+    let offset = bits.len();
+    bits.resize(offset + length, false);
+    bits[offset..].store_be(value);
+}
+
+fn verify_cyclic_redundancy_check<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+    cyclic_redundancy_check_offset: usize,
+    received: u15,
+) -> Result<(), CANFrameDecodingError> {
+    let expected = crc15(&unstuffed_bits[..cyclic_redundancy_check_offset]);
+
+    if expected != received {
+        return Err(CANFrameDecodingError::CyclicRedundancyCheckMismatch { expected, received });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+    use ux::{u11, u29};
+
+    use super::*;
+
+    #[test]
+    fn test_from_stuffed_bits_base() {
+        let can_frame = assert_ok!(CANFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            1, 0, 0, 0, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 0, 0, 1,                      // DF
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,    // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]));
+
+        assert!(matches!(can_frame, CANFrame::Base(_)));
+        assert_eq!(
+            can_frame.identifier(),
+            CANID::Standard(u11::new(0b00000010100))
+        );
+        assert_eq!(can_frame.remote_transmission_request(), u1::new(0b0));
+        assert_eq!(can_frame.data_length_code(), Some(DLC::new(u4::new(0b0001))));
+        assert_eq!(can_frame.data_field(), &[0b00000001]);
+    }
+
+    #[test]
+    fn test_from_unstuffed_bits_extended() {
+        let can_frame = assert_ok!(CANFrame::from_unstuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                                      // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+            1,                                                      // SRR
+            1,                                                      // IDE
+            1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+            0,                                                      // RTR
+            0,                                                      // R1
+            0,                                                      // R0
+            0, 0, 0, 1,                                             // DLC
+            0, 0, 0, 0, 0, 0, 0, 1,                                 // DF
+            1, 0, 1, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0,            // CRC
+            1,                                                      // CRC Delim
+            0,                                                      // ACK Slot
+            1,                                                      // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                                    // EOF
+            1, 1, 1,                                                // IFS
+        ]));
+
+        assert!(matches!(can_frame, CANFrame::Extended(_)));
+        assert_eq!(
+            can_frame.identifier(),
+            CANID::Extended(u29::new(0b00000010100101010101010101010))
+        );
+        assert_eq!(can_frame.remote_transmission_request(), u1::new(0b0));
+        assert_eq!(can_frame.data_length_code(), Some(DLC::new(u4::new(0b0001))));
+        assert_eq!(can_frame.data_field(), &[0b00000001]);
+    }
+
+    #[test]
+    fn test_identifier_extension_bit_missing() {
+        assert!(matches!(
+            assert_err!(CANFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+                0,                                          // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,            // ID
+                0,                                          // RTR
+            ])),
+            CANFrameDecodingError::IdentifierExtensionBitMissing
+        ));
+    }
+
+    #[test]
+    fn test_new_round_trip() {
+        for identifier in [
+            CANID::Standard(u11::new(0x123)),
+            CANID::Extended(u29::new(0x18FEF100)),
+        ] {
+            let can_frame = assert_ok!(CANFrame::new(identifier, vec![1, 2, 3]));
+
+            assert_eq!(can_frame.identifier(), identifier);
+            assert_eq!(
+                assert_ok!(CANFrame::from_stuffed_bits_checked(
+                    can_frame.to_stuffed_bits()
+                )),
+                can_frame
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_parts_data() {
+        let can_frame = assert_ok!(CANFrame::from_parts(
+            CANID::Standard(u11::new(0x014)),
+            u1::new(0),
+            vec![0x01],
+        ));
+
+        let base_data_frame = assert_ok!(BaseDataFrame::new(u11::new(0x014), vec![0x01]));
+        assert_eq!(can_frame, CANFrame::Base(base_data_frame));
+
+        let can_frame = assert_ok!(CANFrame::from_parts(
+            CANID::Extended(u29::new(0x0000A5AA)),
+            u1::new(0),
+            vec![0x01, 0x02],
+        ));
+
+        let extended_data_frame =
+            assert_ok!(ExtendedDataFrame::new(u29::new(0x0000A5AA), vec![0x01, 0x02]));
+        assert_eq!(can_frame.to_stuffed_bits(), extended_data_frame.to_stuffed_bits());
+    }
+
+    #[test]
+    fn test_from_parts_remote() {
+        let can_frame = assert_ok!(CANFrame::from_parts(
+            CANID::Standard(u11::new(0x014)),
+            u1::new(1),
+            vec![0x00],
+        ));
+
+        assert_eq!(can_frame.to_stuffed_bits(), bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            1,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 0, // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+
+        let can_frame = assert_ok!(CANFrame::from_parts(
+            CANID::Extended(u29::new(0x0000A5AA)),
+            u1::new(1),
+            vec![0x00; 8],
+        ));
+
+        let extended_remote_frame =
+            ExtendedRemoteFrame::new(u29::new(0x0000A5AA), DLC::new(u4::new(8)));
+        assert_eq!(can_frame, CANFrame::ExtendedRemote(extended_remote_frame));
+    }
+
+    #[test]
+    fn test_from_parts_data_field_too_long() {
+        assert_err!(CANFrame::from_parts(CANID::Standard(u11::new(0x014)), u1::new(0), vec![0; 9]));
+        assert_err!(CANFrame::from_parts(CANID::Standard(u11::new(0x014)), u1::new(1), vec![0; 9]));
+    }
+
+    #[test]
+    fn test_from_stuffed_bits_remote() {
+        let can_frame = assert_ok!(CANFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                                      // SOF
+            0, 0, 0, 0,                                             // ID A
+            1,                                                      // Stuff
+            0, 0, 1, 0, 1, 0, 0,                                    // ID A
+            1,                                                      // SRR
+            1,                                                      // IDE
+            1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+            1,                                                      // RTR
+            0,                                                      // R1
+            0,                                                      // R0
+            1, 0, 0, 0,                                             // DLC
+            0, 0,                                                   // CRC
+            1,                                                      // Stuff
+            0, 1, 1, 1, 1, 1,                                       // CRC
+            0,                                                      // Stuff
+            0, 1, 1, 1, 1, 1,                                       // CRC
+            0,                                                      // Stuff
+            1,                                                      // CRC
+            1,                                                      // CRC Delim
+            0,                                                      // ACK Slot
+            1,                                                      // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                                    // EOF
+            1, 1, 1,                                                // IFS
+        ]));
+
+        assert!(matches!(can_frame, CANFrame::ExtendedRemote(_)));
+        assert_eq!(
+            can_frame.identifier(),
+            CANID::Extended(u29::new(0b00000010100101010101010101010))
+        );
+        assert_eq!(can_frame.remote_transmission_request(), u1::new(0b1));
+        assert_eq!(can_frame.data_length_code(), Some(DLC::new(u4::new(0b1000))));
+        assert!(can_frame.data_field().is_empty());
+    }
+
+    #[test]
+    fn test_new_remote_round_trip() {
+        for identifier in [
+            CANID::Standard(u11::new(0x123)),
+            CANID::Extended(u29::new(0x18EA00FE)),
+        ] {
+            let can_frame = CANFrame::new_remote(identifier, DLC::new(u4::new(3)));
+
+            assert_eq!(can_frame.identifier(), identifier);
+            assert_eq!(can_frame.remote_transmission_request(), u1::new(1));
+            assert_eq!(
+                assert_ok!(CANFrame::from_stuffed_bits_checked(can_frame.to_stuffed_bits())),
+                can_frame
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_stuffed_bits_stuff_error() {
+        let mut bits = assert_ok!(CANFrame::new(
+            CANID::Extended(u29::new(0x18FEF100)),
+            vec![0xFF, 0xFF]
+        ))
+        .to_stuffed_bits();
+        let stuff_bit_index = (5..bits.len())
+            .find(|bit_index| {
+                let window = &bits[(bit_index - 5)..*bit_index];
+                window.all() || window.not_any()
+            })
+            .unwrap();
+        let previous_bit = bits[stuff_bit_index - 1];
+        bits.set(stuff_bit_index, previous_bit);
+
+        assert_eq!(
+            assert_err!(CANFrame::from_stuffed_bits(bits)),
+            CANFrameDecodingError::StuffError {
+                bit_index: stuff_bit_index
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_fd_round_trip() {
+        for identifier in [
+            CANID::Standard(u11::new(0x123)),
+            CANID::Extended(u29::new(0x18FEF100)),
+        ] {
+            let can_frame = assert_ok!(CANFrame::new_fd(identifier, vec![1; 48], u1::new(1)));
+
+            assert_eq!(can_frame.identifier(), identifier);
+            assert_eq!(can_frame.remote_transmission_request(), u1::new(0));
+            assert_eq!(can_frame.data_length_code(), Some(DLC::new(u4::new(14))));
+            assert_eq!(can_frame.data_field(), &[1; 48]);
+            assert_eq!(
+                assert_ok!(CANFrame::from_stuffed_bits_checked(
+                    can_frame.to_stuffed_bits()
+                )),
+                can_frame
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_xl_round_trip() {
+        let can_frame = assert_ok!(CANFrame::new_xl(
+            u11::new(0x123),
+            0x05,
+            u1::new(0),
+            0x2A,
+            0x12345678,
+            vec![0x55; 100]
+        ));
+
+        assert!(matches!(can_frame, CANFrame::XL(_)));
+        assert_eq!(can_frame.identifier(), CANID::Standard(u11::new(0x123)));
+        assert_eq!(can_frame.data_length_code(), None);
+        assert_eq!(can_frame.data_field(), &[0x55; 100]);
+        assert_eq!(
+            assert_ok!(CANFrame::from_stuffed_bits_checked(
+                can_frame.to_stuffed_bits()
+            )),
+            can_frame
+        );
+    }
+}
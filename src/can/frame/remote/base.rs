@@ -0,0 +1,375 @@
+use bitvec::prelude::*;
+use ux::{u1, u3, u4, u7, u11, u15};
+
+use crate::can::dlc::DLC;
+use crate::can::frame::{
+    CANFrameDecodingError,
+    crc::crc15,
+    extract_field, push_field,
+    stuffing::{stuff_frames, try_unstuff_frames},
+    verify_cyclic_redundancy_check,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseRemoteFrame {
+    start_of_frame: u1,
+    identifier: u11,
+    remote_transmission_request: u1,
+    identifier_extension_bit: u1,
+    reserved_bit_zero: u1,
+    data_length_code: DLC,
+    cyclic_redundancy_check: u15,
+    cyclic_redundancy_check_delimiter: u1,
+    acknowledgement_slot: u1,
+    acknowledgement_delimiter: u1,
+    end_of_frame: u7,
+    inter_frame_spacing: u3,
+}
+
+impl BaseRemoteFrame {
+    pub fn new(identifier: u11, data_length_code: DLC) -> Self {
+        let mut base_remote_frame = Self {
+            start_of_frame: u1::new(0),
+            identifier,
+            remote_transmission_request: u1::new(1),
+            identifier_extension_bit: u1::new(0),
+            reserved_bit_zero: u1::new(0),
+            data_length_code,
+            cyclic_redundancy_check: u15::new(0),
+            cyclic_redundancy_check_delimiter: u1::new(1),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        base_remote_frame.cyclic_redundancy_check =
+            crc15(base_remote_frame.cyclic_redundancy_check_sequence());
+
+        base_remote_frame
+    }
+
+    pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let mut bit_index = 0;
+
+        let start_of_frame = extract_field(
+            unstuffed_bits,
+            bit_index,
+            1,
+            CANFrameDecodingError::StartOfFrameMissing,
+            |start_of_frame_bit_slice| u1::new(start_of_frame_bit_slice.load_be()),
+        )?;
+
+        if start_of_frame != u1::new(0) {
+            return Err(CANFrameDecodingError::StartOfFrameMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let identifier = extract_field(
+            unstuffed_bits,
+            bit_index,
+            11,
+            CANFrameDecodingError::IdentifierMissing,
+            |start_of_frame_bit_slice| u11::new(start_of_frame_bit_slice.load_be()),
+        )?;
+
+        bit_index += 11;
+
+        let maybe_remote_transmission_request_bit_ref = unstuffed_bits.get(bit_index);
+        let remote_transmission_request_bit_ref = maybe_remote_transmission_request_bit_ref
+            .ok_or(CANFrameDecodingError::RemoteTransmissionRequestMissing)?;
+        let remote_transmission_request = u1::from(*remote_transmission_request_bit_ref);
+        if remote_transmission_request != u1::new(1) {
+            return Err(CANFrameDecodingError::RemoteTransmissionRequestMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_identifier_extension_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let identifier_extension_bit_bit_ref = maybe_identifier_extension_bit_bit_ref
+            .ok_or(CANFrameDecodingError::IdentifierExtensionBitMissing)?;
+        let identifier_extension_bit = u1::from(*identifier_extension_bit_bit_ref);
+        if identifier_extension_bit != u1::new(0) {
+            return Err(CANFrameDecodingError::IdentifierExtensionBitMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let maybe_reserved_bit_zero_bit_ref = unstuffed_bits.get(bit_index);
+        let reserved_bit_zero_bit_ref =
+            maybe_reserved_bit_zero_bit_ref.ok_or(CANFrameDecodingError::ReservedBitZeroMissing)?;
+        let reserved_bit_zero = u1::from(*reserved_bit_zero_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_data_length_code_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 4));
+        let data_length_code_bit_slice =
+            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::DataLengthCodeMissing)?;
+        let data_length_code = DLC::new(u4::new(data_length_code_bit_slice.load_be()));
+
+        bit_index += 4;
+
+        let maybe_cyclic_redundancy_check_bit_slice =
+            unstuffed_bits.get(bit_index..(bit_index + 15));
+        let cyclic_redundancy_check_bit_slice = maybe_cyclic_redundancy_check_bit_slice
+            .ok_or(CANFrameDecodingError::CyclicRedundancyCheckMissing)?;
+        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load_be());
+
+        bit_index += 15;
+
+        let maybe_cyclic_redundancy_check_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let cyclic_redundancy_check_delimiter_bit_ref =
+            maybe_cyclic_redundancy_check_delimiter_bit_ref
+                .ok_or(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMissing)?;
+        let cyclic_redundancy_check_delimiter =
+            u1::from(*cyclic_redundancy_check_delimiter_bit_ref);
+        if cyclic_redundancy_check_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_slot_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_slot_bit_ref = maybe_acknowledgement_slot_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementSlotMissing)?;
+        let acknowledgement_slot = u1::from(*acknowledgement_slot_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_delimiter_bit_ref = maybe_acknowledgement_delimiter_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementDelimiterMissing)?;
+        let acknowledgement_delimiter = u1::from(*acknowledgement_delimiter_bit_ref);
+        if acknowledgement_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::AcknowledgementDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
+        let end_of_frame_bit_slice =
+            maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
+        if end_of_frame != u7::new(0b1111111) {
+            return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
+        }
+
+        bit_index += 7;
+
+        let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
+        let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
+            .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
+        if inter_frame_spacing != u3::new(0b111) {
+            return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
+        }
+
+        Ok(Self {
+            start_of_frame,
+            identifier,
+            remote_transmission_request,
+            identifier_extension_bit,
+            reserved_bit_zero,
+            data_length_code,
+            cyclic_redundancy_check,
+            cyclic_redundancy_check_delimiter,
+            acknowledgement_slot,
+            acknowledgement_delimiter,
+            end_of_frame,
+            inter_frame_spacing,
+        })
+    }
+
+    pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let base_remote_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        verify_cyclic_redundancy_check(
+            unstuffed_bits,
+            19,
+            base_remote_frame.cyclic_redundancy_check(),
+        )?;
+
+        Ok(base_remote_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    fn cyclic_redundancy_check_sequence(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.identifier), 11);
+        push_field(&mut bits, u64::from(self.remote_transmission_request), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_zero), 1);
+        push_field(&mut bits, u64::from(self.data_length_code.raw()), 4);
+
+        bits
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = self.cyclic_redundancy_check_sequence();
+
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check), 15);
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check_delimiter), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
+    }
+
+    pub fn start_of_frame(&self) -> u1 {
+        self.start_of_frame
+    }
+
+    pub fn identifier(&self) -> u11 {
+        self.identifier
+    }
+
+    pub fn remote_transmission_request(&self) -> u1 {
+        self.remote_transmission_request
+    }
+
+    pub fn identifier_extension_bit(&self) -> u1 {
+        self.identifier_extension_bit
+    }
+
+    pub fn reserved_bit_zero(&self) -> u1 {
+        self.reserved_bit_zero
+    }
+
+    pub fn data_length_code(&self) -> DLC {
+        self.data_length_code
+    }
+
+    pub fn cyclic_redundancy_check(&self) -> u15 {
+        self.cyclic_redundancy_check
+    }
+
+    pub fn cyclic_redundancy_check_delimiter(&self) -> u1 {
+        self.cyclic_redundancy_check_delimiter
+    }
+
+    pub fn acknowledgement_slot(&self) -> u1 {
+        self.acknowledgement_slot
+    }
+
+    pub fn acknowledgement_delimiter(&self) -> u1 {
+        self.acknowledgement_delimiter
+    }
+
+    pub fn end_of_frame(&self) -> u7 {
+        self.end_of_frame
+    }
+
+    pub fn inter_frame_spacing(&self) -> u3 {
+        self.inter_frame_spacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::*;
+
+    #[test]
+    fn test_from_stuffed_bits() {
+        let base_remote_frame = assert_ok!(BaseRemoteFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            1,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 0, // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ],));
+
+        assert_eq!(base_remote_frame.start_of_frame(), u1::new(0b0));
+        assert_eq!(base_remote_frame.identifier(), u11::new(0b00000010100));
+        assert_eq!(base_remote_frame.remote_transmission_request(), u1::new(0b1));
+        assert_eq!(base_remote_frame.identifier_extension_bit(), u1::new(0b0));
+        assert_eq!(base_remote_frame.reserved_bit_zero(), u1::new(0b0));
+        assert_eq!(base_remote_frame.data_length_code(), DLC::new(u4::new(0b0001)));
+        assert_eq!(
+            base_remote_frame.cyclic_redundancy_check(),
+            u15::new(0b000001001110110)
+        );
+        assert_eq!(
+            base_remote_frame.cyclic_redundancy_check_delimiter(),
+            u1::new(0b1)
+        );
+        assert_eq!(base_remote_frame.acknowledgement_slot(), u1::new(0b0));
+        assert_eq!(base_remote_frame.acknowledgement_delimiter(), u1::new(0b1));
+        assert_eq!(base_remote_frame.end_of_frame(), u7::new(0b1111111));
+        assert_eq!(base_remote_frame.inter_frame_spacing(), u3::new(0b111));
+    }
+
+    #[test]
+    fn test_new() {
+        let base_remote_frame =
+            BaseRemoteFrame::new(u11::new(0b00000010100), DLC::new(u4::new(0b0001)));
+
+        assert_eq!(base_remote_frame.to_stuffed_bits(), bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,             // ID
+            1,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1, 1,                                  // DLC
+            0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 0, // CRC
+            1,                                              // CRC Delim
+            0,                                              // ACK Slot
+            1,                                              // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                            // EOF
+            1, 1, 1,                                        // IFS
+        ]);
+    }
+
+    #[test]
+    fn test_remote_transmission_request_must_be_one() {
+        let base_remote_frame_result = BaseRemoteFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+            0,                                              // SOF
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                // ID
+            0,                                              // RTR
+            0,                                              // IDE
+            0,                                              // R0
+            0, 0, 0, 1,                                     // DLC
+        ]);
+
+        assert!(matches!(
+            assert_err!(base_remote_frame_result),
+            CANFrameDecodingError::RemoteTransmissionRequestMustBeOne
+        ));
+    }
+}
@@ -0,0 +1,443 @@
+use bitvec::prelude::*;
+use ux::{u1, u3, u4, u7, u11, u15, u18, u29};
+
+use crate::can::dlc::DLC;
+use crate::can::frame::{
+    CANFrameDecodingError,
+    crc::crc15,
+    push_field,
+    stuffing::{stuff_frames, try_unstuff_frames},
+    verify_cyclic_redundancy_check,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedRemoteFrame {
+    start_of_frame: u1,
+    identifier_a: u11,
+    substitute_remote_request: u1,
+    identifier_extension_bit: u1,
+    identifier_b: u18,
+    remote_transmission_request: u1,
+    reserved_bit_one: u1,
+    reserved_bit_zero: u1,
+    data_length_code: DLC,
+    cyclic_redundancy_check: u15,
+    cyclic_redundancy_check_delimiter: u1,
+    acknowledgement_slot: u1,
+    acknowledgement_delimiter: u1,
+    end_of_frame: u7,
+    inter_frame_spacing: u3,
+}
+
+impl ExtendedRemoteFrame {
+    pub fn new(identifier: u29, data_length_code: DLC) -> Self {
+        let mut extended_remote_frame = Self {
+            start_of_frame: u1::new(0),
+            identifier_a: u11::try_from(identifier >> 18).unwrap(),
+            substitute_remote_request: u1::new(1),
+            identifier_extension_bit: u1::new(1),
+            identifier_b: u18::try_from(identifier & u29::new(0x3FFFF)).unwrap(),
+            remote_transmission_request: u1::new(1),
+            reserved_bit_one: u1::new(0),
+            reserved_bit_zero: u1::new(0),
+            data_length_code,
+            cyclic_redundancy_check: u15::new(0),
+            cyclic_redundancy_check_delimiter: u1::new(1),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        extended_remote_frame.cyclic_redundancy_check =
+            crc15(extended_remote_frame.cyclic_redundancy_check_sequence());
+
+        extended_remote_frame
+    }
+
+    pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let mut bit_index = 0;
+
+        let maybe_start_of_frame_bit_ref = unstuffed_bits.get(bit_index);
+        let start_of_frame_bit_ref =
+            maybe_start_of_frame_bit_ref.ok_or(CANFrameDecodingError::StartOfFrameMissing)?;
+        let start_of_frame = u1::from(*start_of_frame_bit_ref);
+        if start_of_frame != u1::new(0) {
+            return Err(CANFrameDecodingError::StartOfFrameMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let maybe_identifier_a_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 11));
+        let identifier_a_bit_slice =
+            maybe_identifier_a_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
+        let identifier_a = u11::new(identifier_a_bit_slice.load_be());
+
+        bit_index += 11;
+
+        let maybe_substitute_remote_request_bit_ref = unstuffed_bits.get(bit_index);
+        let substitute_remote_request_bit_ref = maybe_substitute_remote_request_bit_ref
+            .ok_or(CANFrameDecodingError::SubstituteRemoteRequestMissing)?;
+        let substitute_remote_request = u1::from(*substitute_remote_request_bit_ref);
+        if substitute_remote_request != u1::new(1) {
+            return Err(CANFrameDecodingError::SubstituteRemoteRequestMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_identifier_extension_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let identifier_extension_bit_bit_ref = maybe_identifier_extension_bit_bit_ref
+            .ok_or(CANFrameDecodingError::IdentifierExtensionBitMissing)?;
+        let identifier_extension_bit = u1::from(*identifier_extension_bit_bit_ref);
+        if identifier_extension_bit != u1::new(1) {
+            return Err(CANFrameDecodingError::IdentifierExtensionBitMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_identifier_b_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 18));
+        let identifier_b_bit_slice =
+            maybe_identifier_b_bit_slice.ok_or(CANFrameDecodingError::IdentifierMissing)?;
+        let identifier_b = u18::new(identifier_b_bit_slice.load_be());
+
+        bit_index += 18;
+
+        let maybe_remote_transmission_request_bit_ref = unstuffed_bits.get(bit_index);
+        let remote_transmission_request_bit_ref = maybe_remote_transmission_request_bit_ref
+            .ok_or(CANFrameDecodingError::RemoteTransmissionRequestMissing)?;
+        let remote_transmission_request = u1::from(*remote_transmission_request_bit_ref);
+        if remote_transmission_request != u1::new(1) {
+            return Err(CANFrameDecodingError::RemoteTransmissionRequestMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_reserved_bit_one_bit_ref = unstuffed_bits.get(bit_index);
+        let reserved_bit_one_bit_ref =
+            maybe_reserved_bit_one_bit_ref.ok_or(CANFrameDecodingError::ReservedBitOneMissing)?;
+        let reserved_bit_one = u1::from(*reserved_bit_one_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_reserved_bit_zero_bit_ref = unstuffed_bits.get(bit_index);
+        let reserved_bit_zero_bit_ref =
+            maybe_reserved_bit_zero_bit_ref.ok_or(CANFrameDecodingError::ReservedBitZeroMissing)?;
+        let reserved_bit_zero = u1::from(*reserved_bit_zero_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_data_length_code_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 4));
+        let data_length_code_bit_slice =
+            maybe_data_length_code_bit_slice.ok_or(CANFrameDecodingError::DataLengthCodeMissing)?;
+        let data_length_code = DLC::new(u4::new(data_length_code_bit_slice.load_be()));
+
+        bit_index += 4;
+
+        let maybe_cyclic_redundancy_check_bit_slice =
+            unstuffed_bits.get(bit_index..(bit_index + 15));
+        let cyclic_redundancy_check_bit_slice = maybe_cyclic_redundancy_check_bit_slice
+            .ok_or(CANFrameDecodingError::CyclicRedundancyCheckMissing)?;
+        let cyclic_redundancy_check = u15::new(cyclic_redundancy_check_bit_slice.load_be());
+
+        bit_index += 15;
+
+        let maybe_cyclic_redundancy_check_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let cyclic_redundancy_check_delimiter_bit_ref =
+            maybe_cyclic_redundancy_check_delimiter_bit_ref
+                .ok_or(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMissing)?;
+        let cyclic_redundancy_check_delimiter =
+            u1::from(*cyclic_redundancy_check_delimiter_bit_ref);
+        if cyclic_redundancy_check_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::CyclicRedundancyCheckDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_slot_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_slot_bit_ref = maybe_acknowledgement_slot_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementSlotMissing)?;
+        let acknowledgement_slot = u1::from(*acknowledgement_slot_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_delimiter_bit_ref = maybe_acknowledgement_delimiter_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementDelimiterMissing)?;
+        let acknowledgement_delimiter = u1::from(*acknowledgement_delimiter_bit_ref);
+        if acknowledgement_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::AcknowledgementDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
+        let end_of_frame_bit_slice =
+            maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
+        if end_of_frame != u7::new(0b1111111) {
+            return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
+        }
+
+        bit_index += 7;
+
+        let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
+        let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
+            .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
+        if inter_frame_spacing != u3::new(0b111) {
+            return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
+        }
+
+        Ok(Self {
+            start_of_frame,
+            identifier_a,
+            substitute_remote_request,
+            identifier_extension_bit,
+            identifier_b,
+            remote_transmission_request,
+            reserved_bit_one,
+            reserved_bit_zero,
+            data_length_code,
+            cyclic_redundancy_check,
+            cyclic_redundancy_check_delimiter,
+            acknowledgement_slot,
+            acknowledgement_delimiter,
+            end_of_frame,
+            inter_frame_spacing,
+        })
+    }
+
+    pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let extended_remote_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        verify_cyclic_redundancy_check(
+            unstuffed_bits,
+            39,
+            extended_remote_frame.cyclic_redundancy_check(),
+        )?;
+
+        Ok(extended_remote_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    fn cyclic_redundancy_check_sequence(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.identifier_a), 11);
+        push_field(&mut bits, u64::from(self.substitute_remote_request), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.identifier_b), 18);
+        push_field(&mut bits, u64::from(self.remote_transmission_request), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_one), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit_zero), 1);
+        push_field(&mut bits, u64::from(self.data_length_code.raw()), 4);
+
+        bits
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = self.cyclic_redundancy_check_sequence();
+
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check), 15);
+        push_field(&mut bits, u64::from(self.cyclic_redundancy_check_delimiter), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
+    }
+
+    pub fn start_of_frame(&self) -> u1 {
+        self.start_of_frame
+    }
+
+    pub fn identifier_a(&self) -> u11 {
+        self.identifier_a
+    }
+
+    pub fn substitute_remote_request(&self) -> u1 {
+        self.substitute_remote_request
+    }
+
+    pub fn identifier_extension_bit(&self) -> u1 {
+        self.identifier_extension_bit
+    }
+
+    pub fn identifier_b(&self) -> u18 {
+        self.identifier_b
+    }
+
+    pub fn identifier(&self) -> u29 {
+        (u29::from(self.identifier_a()) << 18) | u29::from(self.identifier_b())
+    }
+
+    pub fn remote_transmission_request(&self) -> u1 {
+        self.remote_transmission_request
+    }
+
+    pub fn reserved_bit_one(&self) -> u1 {
+        self.reserved_bit_one
+    }
+
+    pub fn reserved_bit_zero(&self) -> u1 {
+        self.reserved_bit_zero
+    }
+
+    pub fn data_length_code(&self) -> DLC {
+        self.data_length_code
+    }
+
+    pub fn cyclic_redundancy_check(&self) -> u15 {
+        self.cyclic_redundancy_check
+    }
+
+    pub fn cyclic_redundancy_check_delimiter(&self) -> u1 {
+        self.cyclic_redundancy_check_delimiter
+    }
+
+    pub fn acknowledgement_slot(&self) -> u1 {
+        self.acknowledgement_slot
+    }
+
+    pub fn acknowledgement_delimiter(&self) -> u1 {
+        self.acknowledgement_delimiter
+    }
+
+    pub fn end_of_frame(&self) -> u7 {
+        self.end_of_frame
+    }
+
+    pub fn inter_frame_spacing(&self) -> u3 {
+        self.inter_frame_spacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::*;
+
+    #[test]
+    fn test_from_stuffed_bits() {
+        let extended_remote_frame =
+            assert_ok!(ExtendedRemoteFrame::from_stuffed_bits_checked(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,                     // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                1,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                1, 0, 0, 0,                                             // DLC
+                0, 0, 1, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 0, 1,   // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+            ],));
+
+        assert_eq!(extended_remote_frame.start_of_frame(), u1::new(0b0));
+        assert_eq!(extended_remote_frame.identifier_a(), u11::new(0b00000010100));
+        assert_eq!(
+            extended_remote_frame.substitute_remote_request(),
+            u1::new(0b1)
+        );
+        assert_eq!(
+            extended_remote_frame.identifier_extension_bit(),
+            u1::new(0b1)
+        );
+        assert_eq!(
+            extended_remote_frame.identifier_b(),
+            u18::new(0b101010101010101010)
+        );
+        assert_eq!(
+            extended_remote_frame.identifier(),
+            u29::new(0b00000010100101010101010101010)
+        );
+        assert_eq!(
+            extended_remote_frame.remote_transmission_request(),
+            u1::new(0b1)
+        );
+        assert_eq!(extended_remote_frame.reserved_bit_one(), u1::new(0b0));
+        assert_eq!(extended_remote_frame.reserved_bit_zero(), u1::new(0b0));
+        assert_eq!(extended_remote_frame.data_length_code(), DLC::new(u4::new(0b1000)));
+        assert_eq!(
+            extended_remote_frame.cyclic_redundancy_check(),
+            u15::new(0b000111110111111)
+        );
+        assert_eq!(extended_remote_frame.end_of_frame(), u7::new(0b1111111));
+        assert_eq!(extended_remote_frame.inter_frame_spacing(), u3::new(0b111));
+    }
+
+    #[test]
+    fn test_new() {
+        let extended_remote_frame = ExtendedRemoteFrame::new(
+            u29::new(0b00000010100101010101010101010),
+            DLC::new(u4::new(0b1000)),
+        );
+
+        assert_eq!(extended_remote_frame.to_stuffed_bits(), bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0,                     // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                1,                                                      // RTR
+                0,                                                      // R1
+                0,                                                      // R0
+                1, 0, 0, 0,                                             // DLC
+                0, 0, 1, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 0, 1,   // CRC
+                1,                                                      // CRC Delim
+                0,                                                      // ACK Slot
+                1,                                                      // ACK Delim
+                1, 1, 1, 1, 1, 1, 1,                                    // EOF
+                1, 1, 1,                                                // IFS
+        ]);
+    }
+
+    #[test]
+    fn test_remote_transmission_request_must_be_one() {
+        let extended_remote_frame_result =
+            ExtendedRemoteFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+                0,                                                      // SOF
+                0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,                        // ID A
+                1,                                                      // SRR
+                1,                                                      // IDE
+                1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0,   // ID B
+                0,                                                      // RTR
+            ]);
+
+        assert!(matches!(
+            assert_err!(extended_remote_frame_result),
+            CANFrameDecodingError::RemoteTransmissionRequestMustBeOne
+        ));
+    }
+}
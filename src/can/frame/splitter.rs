@@ -0,0 +1,236 @@
+use bitvec::prelude::*;
+
+use crate::can::frame::{
+    CANFrame,
+    event::{CANBusEvent, CANBusEventDecoder},
+};
+
+const BUS_IDLE_LENGTH: usize = 11;
+
+#[derive(Debug, Clone)]
+pub struct CANFrameSplitter {
+    can_bus_event_decoder: Option<CANBusEventDecoder>,
+    decoder_bit_index: usize,
+    recessive_bit_count: usize,
+    bit_index: usize,
+}
+
+impl CANFrameSplitter {
+    pub fn new() -> Self {
+        Self {
+            can_bus_event_decoder: None,
+            decoder_bit_index: 0,
+            recessive_bit_count: 0,
+            bit_index: 0,
+        }
+    }
+
+    pub fn split<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Vec<(usize, CANFrame)> {
+        Self::split_iter(stuffed_bits.as_ref().iter().by_vals()).collect()
+    }
+
+    pub fn split_iter<I: IntoIterator<Item = bool>>(
+        stuffed_bits: I,
+    ) -> impl Iterator<Item = (usize, CANFrame)> {
+        let mut can_frame_splitter = Self::new();
+
+        stuffed_bits
+            .into_iter()
+            .filter_map(move |bit| can_frame_splitter.push(bit))
+    }
+
+    pub fn bit_index(&self) -> usize {
+        self.bit_index
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.can_bus_event_decoder.is_some()
+    }
+
+    pub fn push(&mut self, bit: bool) -> Option<(usize, CANFrame)> {
+        let bit_index = self.bit_index;
+        self.bit_index += 1;
+
+        let Some(can_bus_event_decoder) = &mut self.can_bus_event_decoder else {
+            self.recessive_bit_count = if bit { self.recessive_bit_count + 1 } else { 0 };
+
+            if self.recessive_bit_count == BUS_IDLE_LENGTH {
+                self.can_bus_event_decoder = Some(CANBusEventDecoder::new());
+                self.decoder_bit_index = bit_index + 1;
+            }
+
+            return None;
+        };
+
+        // A malformed frame still ends at its end of frame, so the decoder stays synchronized.
+        match can_bus_event_decoder.push(bit)? {
+            CANBusEvent::Frame { bit_index, frame } => {
+                Some((self.decoder_bit_index + bit_index, frame))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for CANFrameSplitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::assert_ok;
+    use ux::{u4, u11, u29};
+
+    use crate::can::{can_id::CANID, dlc::DLC};
+
+    use super::*;
+
+    fn can_frames() -> [CANFrame; 3] {
+        [
+            assert_ok!(CANFrame::new(
+                CANID::Standard(u11::new(0x123)),
+                vec![0x00, 0xFF, 0x55]
+            )),
+            assert_ok!(CANFrame::new(
+                CANID::Extended(u29::new(0x18FEF100)),
+                vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+            )),
+            CANFrame::new_remote(CANID::Standard(u11::new(0x7FF)), DLC::new(u4::new(8))),
+        ]
+    }
+
+    #[test]
+    fn test_split_frames() {
+        let [first_can_frame, second_can_frame, third_can_frame] = can_frames();
+
+        let mut bits = bitvec![usize, Msb0; 1; 11];
+        let first_bit_index = bits.len();
+        bits.extend(first_can_frame.to_stuffed_bits());
+        let second_bit_index = bits.len();
+        bits.extend(second_can_frame.to_stuffed_bits());
+        bits.extend([true; 20]);
+        let third_bit_index = bits.len();
+        bits.extend(third_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANFrameSplitter::split(bits),
+            vec![
+                (first_bit_index, first_can_frame),
+                (second_bit_index, second_can_frame),
+                (third_bit_index, third_can_frame),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_waits_for_bus_idle() {
+        let [first_can_frame, second_can_frame, _] = can_frames();
+
+        // The capture starts partway through the first frame.
+        let mut bits = first_can_frame.to_stuffed_bits()[20..].to_bitvec();
+        bits.extend([true; 11]);
+        let second_bit_index = bits.len();
+        bits.extend(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANFrameSplitter::split(bits),
+            vec![(second_bit_index, second_can_frame)]
+        );
+    }
+
+    #[test]
+    fn test_split_misses_first_frame_and_synchronizes_on_its_end_of_frame() {
+        let [first_can_frame, _, _] = can_frames();
+
+        // Ten idle bits are too few, so the splitter synchronizes on the frame's ACK delimiter, EOF
+        // and IFS instead.
+        let mut bits = bitvec![usize, Msb0; 1; 10];
+        bits.extend(first_can_frame.to_stuffed_bits());
+
+        let mut can_frame_splitter = CANFrameSplitter::new();
+        let frames = bits
+            .iter()
+            .by_vals()
+            .filter_map(|bit| can_frame_splitter.push(bit))
+            .collect::<Vec<_>>();
+
+        assert_eq!(frames, vec![]);
+        assert!(can_frame_splitter.is_synchronized());
+        assert_eq!(can_frame_splitter.bit_index(), bits.len());
+    }
+
+    #[test]
+    fn test_split_resynchronizes_after_error() {
+        let [first_can_frame, second_can_frame, third_can_frame] = can_frames();
+
+        let mut bits = bitvec![usize, Msb0; 1; 11];
+        let first_bit_index = bits.len();
+        bits.extend(first_can_frame.to_stuffed_bits());
+
+        let second_bits = second_can_frame.to_stuffed_bits();
+        let flag_bit_index = (20..second_bits.len())
+            .find(|bit_index| second_bits[bit_index - 1])
+            .unwrap();
+        bits.extend_from_bitslice(&second_bits[..flag_bit_index]);
+        bits.extend([false; 6]);
+        bits.extend([true; 8]);
+        bits.extend([true; 3]);
+
+        let third_bit_index = bits.len();
+        bits.extend(third_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANFrameSplitter::split(bits),
+            vec![
+                (first_bit_index, first_can_frame),
+                (third_bit_index, third_can_frame),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_resynchronizes_after_malformed_frame() {
+        let [first_can_frame, second_can_frame, third_can_frame] = can_frames();
+
+        let mut bits = bitvec![usize, Msb0; 1; 11];
+        let mut first_bits = first_can_frame.to_stuffed_bits();
+        let crc_bit_index = first_bits.len() - 14;
+        let crc_bit = first_bits[crc_bit_index];
+        first_bits.set(crc_bit_index, !crc_bit);
+        bits.extend(first_bits);
+        let second_bit_index = bits.len();
+        bits.extend(second_can_frame.to_stuffed_bits());
+        bits.extend([true; 11]);
+        let third_bit_index = bits.len();
+        bits.extend(third_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANFrameSplitter::split(bits),
+            vec![
+                (second_bit_index, second_can_frame),
+                (third_bit_index, third_can_frame),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_iter() {
+        let [first_can_frame, second_can_frame, _] = can_frames();
+
+        let bits = [true; 11]
+            .into_iter()
+            .chain(first_can_frame.to_stuffed_bits())
+            .chain(second_can_frame.to_stuffed_bits());
+
+        assert_eq!(
+            CANFrameSplitter::split_iter(bits)
+                .map(|(_, frame)| frame)
+                .collect::<Vec<_>>(),
+            vec![first_can_frame, second_can_frame]
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use bitvec::prelude::*;
+
+use crate::{
+    BitStuffingError,
+    can::frame::{StuffingLayout, stuffing_layout},
+};
+
+pub(crate) const STUFF_WIDTH: usize = 5;
+pub(crate) const FIXED_STUFF_INTERVAL: usize = 4;
+const FIXED_FORM_LENGTH: usize = 10;
+
+pub fn stuff_frames<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+    unstuffed_bits: B,
+) -> BitVec<T, Msb0> {
+    let unstuffed_bits = unstuffed_bits.as_ref();
+
+    let mut stuffed_bits = BitVec::new();
+    let mut index = 0;
+
+    while index < unstuffed_bits.len() {
+        if unstuffed_bits[index] {
+            stuffed_bits.push(true);
+            index += 1;
+            continue;
+        }
+
+        let frame_bits = &unstuffed_bits[index..];
+        let maybe_stuffing_layout = stuffing_layout(frame_bits);
+        let dynamically_stuffed_length = maybe_stuffing_layout
+            .map_or(frame_bits.len(), |stuffing_layout| {
+                stuffing_layout.dynamically_stuffed_length
+            })
+            .min(frame_bits.len());
+        let stuff_after_last_bit = maybe_stuffing_layout.is_some_and(|stuffing_layout| {
+            stuff_after_last_bit(stuffing_layout)
+                && stuffing_layout.dynamically_stuffed_length <= frame_bits.len()
+        });
+
+        push_dynamically_stuffed(
+            &mut stuffed_bits,
+            &frame_bits[..dynamically_stuffed_length],
+            stuff_after_last_bit,
+        );
+        index += dynamically_stuffed_length;
+
+        let fixed_stuffed_length = maybe_stuffing_layout
+            .map_or(0, |stuffing_layout| stuffing_layout.fixed_stuffed_length)
+            .min(unstuffed_bits.len() - index);
+
+        for (fixed_stuffed_index, bit) in unstuffed_bits[index..(index + fixed_stuffed_length)]
+            .iter()
+            .by_vals()
+            .enumerate()
+        {
+            if maybe_stuffing_layout.is_some_and(|stuffing_layout| {
+                stuffing_layout.is_fixed_stuff_bit_index(fixed_stuffed_index)
+            }) {
+                let previous_bit = stuffed_bits[stuffed_bits.len() - 1];
+                stuffed_bits.push(!previous_bit);
+            }
+
+            stuffed_bits.push(bit);
+        }
+
+        index += fixed_stuffed_length;
+
+        let fixed_form_length = maybe_stuffing_layout
+            .map_or(FIXED_FORM_LENGTH, |stuffing_layout| stuffing_layout.fixed_form.len())
+            .min(unstuffed_bits.len() - index);
+        stuffed_bits.extend_from_bitslice(&unstuffed_bits[index..(index + fixed_form_length)]);
+        index += fixed_form_length;
+    }
+
+    stuffed_bits
+}
+
+pub fn try_unstuff_frames<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+    stuffed_bits: B,
+) -> Result<BitVec<T, Msb0>, BitStuffingError> {
+    let stuffed_bits = stuffed_bits.as_ref();
+
+    let mut unstuffed_bits = BitVec::<T, Msb0>::new();
+    let mut index = 0;
+
+    while index < stuffed_bits.len() {
+        if stuffed_bits[index] {
+            unstuffed_bits.push(true);
+            index += 1;
+            continue;
+        }
+
+        let frame_offset = unstuffed_bits.len();
+        let mut maybe_stuffing_layout = None;
+        let mut previous_bit = false;
+        let mut run_length = 0;
+
+        while index < stuffed_bits.len() {
+            let bit = stuffed_bits[index];
+            let frame_length = unstuffed_bits.len() - frame_offset;
+
+            let is_stuff_bit = run_length == STUFF_WIDTH
+                && maybe_stuffing_layout.is_none_or(|stuffing_layout: StuffingLayout| {
+                    frame_length < stuffing_layout.dynamically_stuffed_length
+                        || (frame_length == stuffing_layout.dynamically_stuffed_length
+                            && stuff_after_last_bit(stuffing_layout))
+                });
+
+            if is_stuff_bit {
+                if bit == previous_bit {
+                    return Err(BitStuffingError { bit_index: index });
+                }
+
+                previous_bit = bit;
+                run_length = 1;
+                index += 1;
+                continue;
+            }
+
+            if maybe_stuffing_layout.is_some_and(|stuffing_layout| {
+                frame_length >= stuffing_layout.dynamically_stuffed_length
+            }) {
+                break;
+            }
+
+            unstuffed_bits.push(bit);
+            index += 1;
+
+            run_length = if bit == previous_bit { run_length + 1 } else { 1 };
+            previous_bit = bit;
+
+            if maybe_stuffing_layout.is_none() {
+                maybe_stuffing_layout = stuffing_layout(&unstuffed_bits[frame_offset..]);
+            }
+        }
+
+        let mut fixed_stuffed_index = 0;
+
+        while let Some(current_stuffing_layout) = maybe_stuffing_layout
+            && fixed_stuffed_index < current_stuffing_layout.fixed_stuffed_length
+        {
+            if current_stuffing_layout.is_fixed_stuff_bit_index(fixed_stuffed_index) {
+                let Some(bit) = stuffed_bits.get(index).as_deref().copied() else {
+                    break;
+                };
+
+                if bit == stuffed_bits[index - 1] {
+                    return Err(BitStuffingError { bit_index: index });
+                }
+
+                index += 1;
+            }
+
+            let Some(bit) = stuffed_bits.get(index).as_deref().copied() else {
+                break;
+            };
+
+            unstuffed_bits.push(bit);
+            index += 1;
+            fixed_stuffed_index += 1;
+
+            // The length of a CAN XL frame is only known once its DLC has been unstuffed.
+            maybe_stuffing_layout = stuffing_layout(&unstuffed_bits[frame_offset..]);
+        }
+
+        let fixed_form_length = maybe_stuffing_layout
+            .map_or(FIXED_FORM_LENGTH, |stuffing_layout| stuffing_layout.fixed_form.len())
+            .min(stuffed_bits.len() - index);
+        unstuffed_bits.extend_from_bitslice(&stuffed_bits[index..(index + fixed_form_length)]);
+        index += fixed_form_length;
+    }
+
+    Ok(unstuffed_bits)
+}
+
+pub(crate) fn stuff_dynamically<T: BitStore>(bits: &BitSlice<T, Msb0>) -> BitVec<T, Msb0> {
+    let mut stuffed_bits = BitVec::new();
+    push_dynamically_stuffed(&mut stuffed_bits, bits, false);
+    stuffed_bits
+}
+
+pub(crate) fn stuff_after_last_bit(stuffing_layout: StuffingLayout) -> bool {
+    // In a CAN FD frame, the first fixed stuff bit takes the place of a stuff bit after the data
+    // field.
+    stuffing_layout.fixed_stuffed_length == 0
+}
+
+fn push_dynamically_stuffed<T: BitStore>(
+    stuffed_bits: &mut BitVec<T, Msb0>,
+    bits: &BitSlice<T, Msb0>,
+    stuff_after_last_bit: bool,
+) {
+    let mut previous_bit = false;
+    let mut run_length = 0;
+
+    for (index, bit) in bits.iter().by_vals().enumerate() {
+        stuffed_bits.push(bit);
+
+        run_length = if bit == previous_bit { run_length + 1 } else { 1 };
+        previous_bit = bit;
+
+        let is_last_bit = index + 1 == bits.len();
+
+        if run_length == STUFF_WIDTH && (stuff_after_last_bit || !is_last_bit) {
+            stuffed_bits.push(!bit);
+            previous_bit = !bit;
+            run_length = 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::assert_ok;
+    use ux::{u1, u4, u11, u29};
+
+    use crate::{
+        can::{can_id::CANID, dlc::DLC, frame::CANFrame},
+        stuff,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_stuff_frames() {
+        let bits = bitvec![usize, Msb0;
+            0,
+            0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0,
+            0,
+            0,
+            0,
+            0, 0, 0, 1,
+            0, 0, 0, 0, 0, 0, 0, 1,
+            1, 1, 1, 0, 1, 1, 1, 0, 1, 0, 1, 0 ,0, 1, 1,
+            1,
+            0,
+            1,
+            1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1,
+        ];
+
+        assert_eq!(stuff_frames(&bits), stuff(&bits, 5));
+        assert_eq!(assert_ok!(try_unstuff_frames(stuff_frames(&bits))), bits);
+    }
+
+    #[test]
+    fn test_stuff_frames_without_inter_frame_spacing() {
+        let can_frame = assert_ok!(CANFrame::new(
+            CANID::Standard(u11::new(0x000)),
+            vec![0x00, 0x00]
+        ));
+
+        let unstuffed_bits = can_frame.to_unstuffed_bits();
+        let unstuffed_bits = &unstuffed_bits[..(unstuffed_bits.len() - 3)];
+        let stuffed_bits = can_frame.to_stuffed_bits();
+        let stuffed_bits = &stuffed_bits[..(stuffed_bits.len() - 3)];
+
+        assert_eq!(stuff_frames(unstuffed_bits), stuffed_bits);
+        assert_eq!(assert_ok!(try_unstuff_frames(stuffed_bits)), unstuffed_bits);
+    }
+
+    #[test]
+    fn test_stuff_concatenated_frames() {
+        let can_frames = [
+            assert_ok!(CANFrame::new(
+                CANID::Standard(u11::new(0x7FF)),
+                vec![0xFF; 8]
+            )),
+            CANFrame::new_remote(CANID::Extended(u29::new(0x00000000)), DLC::new(u4::new(0))),
+            assert_ok!(CANFrame::new(
+                CANID::Extended(u29::new(0x18FEF100)),
+                vec![0x00; 3]
+            )),
+        ];
+
+        let mut unstuffed_bits = BitVec::<usize, Msb0>::new();
+        let mut stuffed_bits = BitVec::<usize, Msb0>::new();
+
+        for (index, can_frame) in can_frames.iter().enumerate() {
+            unstuffed_bits.extend(can_frame.to_unstuffed_bits());
+            stuffed_bits.extend(can_frame.to_stuffed_bits());
+
+            unstuffed_bits.extend([true; 5][..index].iter());
+            stuffed_bits.extend([true; 5][..index].iter());
+        }
+
+        assert_eq!(stuff_frames(&unstuffed_bits), stuffed_bits);
+        assert_eq!(assert_ok!(try_unstuff_frames(&stuffed_bits)), unstuffed_bits);
+    }
+
+    #[test]
+    fn test_try_unstuff_frames_stuff_error() {
+        let first_stuffed_bits =
+            CANFrame::new_remote(CANID::Standard(u11::new(0x7FF)), DLC::new(u4::new(0)))
+                .to_stuffed_bits();
+        let mut stuffed_bits = first_stuffed_bits.clone();
+        stuffed_bits.extend(
+            CANFrame::new_remote(CANID::Standard(u11::new(0x000)), DLC::new(u4::new(0)))
+                .to_stuffed_bits(),
+        );
+
+        let stuff_bit_index = first_stuffed_bits.len() + 5;
+        stuffed_bits.set(stuff_bit_index, false);
+
+        assert_eq!(
+            try_unstuff_frames(&stuffed_bits),
+            Err(BitStuffingError {
+                bit_index: stuff_bit_index
+            })
+        );
+    }
+
+    #[test]
+    fn test_stuff_frames_fixed_stuff_bits() {
+        let can_frame = assert_ok!(CANFrame::new_fd(
+            CANID::Standard(u11::new(0x000)),
+            vec![0x00; 16],
+            u1::new(0)
+        ));
+
+        let unstuffed_bits = can_frame.to_unstuffed_bits();
+        let stuffed_bits = can_frame.to_stuffed_bits();
+
+        // The stuff count and the CRC-17 are followed by the fixed form and preceded by six fixed
+        // stuff bits, one before every four bits.
+        let fixed_stuffed_bits =
+            &stuffed_bits[(stuffed_bits.len() - FIXED_FORM_LENGTH - 3 - 27)..][..27];
+        let mut fixed_unstuffed_bits = BitVec::<usize, Msb0>::new();
+
+        for (index, bit) in fixed_stuffed_bits.iter().by_vals().enumerate() {
+            if index % (FIXED_STUFF_INTERVAL + 1) == 0 {
+                let previous_bit = if index == 0 {
+                    stuffed_bits[stuffed_bits.len() - FIXED_FORM_LENGTH - 3 - 28]
+                } else {
+                    fixed_stuffed_bits[index - 1]
+                };
+                assert_ne!(bit, previous_bit);
+            } else {
+                fixed_unstuffed_bits.push(bit);
+            }
+        }
+
+        assert_eq!(
+            fixed_unstuffed_bits,
+            unstuffed_bits[(unstuffed_bits.len() - FIXED_FORM_LENGTH - 3 - 21)..][..21]
+        );
+        assert_eq!(assert_ok!(try_unstuff_frames(&stuffed_bits)), unstuffed_bits);
+    }
+
+    #[test]
+    fn test_try_unstuff_frames_fixed_stuff_error() {
+        let mut stuffed_bits = assert_ok!(CANFrame::new_fd(
+            CANID::Extended(u29::new(0x18FEF100)),
+            vec![0x55; 32],
+            u1::new(1)
+        ))
+        .to_stuffed_bits();
+
+        // The last fixed stuff bit precedes the last bit of the CRC-21.
+        let fixed_stuff_bit_index = stuffed_bits.len() - FIXED_FORM_LENGTH - 3 - 2;
+        let previous_bit = stuffed_bits[fixed_stuff_bit_index - 1];
+        stuffed_bits.set(fixed_stuff_bit_index, previous_bit);
+
+        assert_eq!(
+            try_unstuff_frames(&stuffed_bits),
+            Err(BitStuffingError {
+                bit_index: fixed_stuff_bit_index
+            })
+        );
+    }
+}
@@ -0,0 +1,825 @@
+use bitvec::prelude::*;
+use ux::{u1, u3, u4, u7, u11, u13};
+
+use crate::can::frame::{
+    CANFrameDecodingError, CANFrameEncodingError, StuffingLayout,
+    crc::{crc13, crc32},
+    extract_field,
+    fd::{fd_stuff_count, from_gray_code, gray_code, stuff_count_parity},
+    push_field,
+    stuffing::{stuff_dynamically, stuff_frames, try_unstuff_frames},
+};
+
+pub const MAXIMUM_DATA_FIELD_LENGTH: usize = 2048;
+
+// SOF through the reserved bit are dynamically stuffed. The fixed stuff bits start after the
+// arbitration to data sequence and follow every ten bits up to the end of the frame CRC.
+const DYNAMICALLY_STUFFED_LENGTH: usize = 17;
+const ARBITRATION_TO_DATA_SEQUENCE_LENGTH: usize = 4;
+const FIXED_STUFF_INTERVAL: usize = 10;
+const DATA_LENGTH_CODE_OFFSET: usize = 30;
+const STUFF_BIT_COUNT_OFFSET: usize = 41;
+const DATA_FIELD_OFFSET: usize = 98;
+
+pub(crate) fn xl_data_field_length(data_length_code: u11) -> usize {
+    usize::from(u16::from(data_length_code)) + 1
+}
+
+pub(crate) fn xl_stuffing_layout<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+) -> StuffingLayout {
+    // Until the DLC has been received, the fixed stuffed bits are known to run at least to its end.
+    let fixed_stuffed_end = unstuffed_bits
+        .get(DATA_LENGTH_CODE_OFFSET..STUFF_BIT_COUNT_OFFSET)
+        .map_or(STUFF_BIT_COUNT_OFFSET, |data_length_code_bit_slice| {
+            let data_length_code = u11::new(data_length_code_bit_slice.load_be());
+            DATA_FIELD_OFFSET + 8 * xl_data_field_length(data_length_code) + 32
+        });
+
+    StuffingLayout {
+        dynamically_stuffed_length: DYNAMICALLY_STUFFED_LENGTH,
+        fixed_stuffed_length: fixed_stuffed_end - DYNAMICALLY_STUFFED_LENGTH,
+        first_fixed_stuff_bit_index: ARBITRATION_TO_DATA_SEQUENCE_LENGTH + FIXED_STUFF_INTERVAL,
+        fixed_stuff_interval: FIXED_STUFF_INTERVAL,
+        // FCP, DAS, ACK slot, ACK delimiter and EOF
+        fixed_form: bits![static u8, Msb0; 1, 1, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XLDataFrame {
+    start_of_frame: u1,
+    priority_identifier: u11,
+    remote_request_substitution: u1,
+    identifier_extension_bit: u1,
+    flexible_data_rate_format: u1,
+    xl_format: u1,
+    reserved_bit: u1,
+    arbitration_to_data_sequence: u4,
+    service_data_unit_type: u8,
+    simple_extended_content: u1,
+    data_length_code: u11,
+    stuff_bit_count: u3,
+    stuff_bit_count_parity: u1,
+    preface_cyclic_redundancy_check: u13,
+    virtual_can_network_identifier: u8,
+    acceptance_field: u32,
+    data_field: Vec<u8>,
+    frame_cyclic_redundancy_check: u32,
+    format_check_pattern: u4,
+    data_to_arbitration_sequence: u4,
+    acknowledgement_slot: u1,
+    acknowledgement_delimiter: u1,
+    end_of_frame: u7,
+    inter_frame_spacing: u3,
+}
+
+impl XLDataFrame {
+    pub fn new(
+        priority_identifier: u11,
+        service_data_unit_type: u8,
+        simple_extended_content: u1,
+        virtual_can_network_identifier: u8,
+        acceptance_field: u32,
+        data_field: Vec<u8>,
+    ) -> Result<Self, CANFrameEncodingError> {
+        if data_field.len() > MAXIMUM_DATA_FIELD_LENGTH {
+            return Err(CANFrameEncodingError::DataFieldTooLong);
+        }
+
+        if data_field.is_empty() {
+            return Err(CANFrameEncodingError::DataFieldLengthInvalid);
+        }
+
+        let mut xl_data_frame = Self {
+            start_of_frame: u1::new(0),
+            priority_identifier,
+            remote_request_substitution: u1::new(0),
+            identifier_extension_bit: u1::new(0),
+            flexible_data_rate_format: u1::new(1),
+            xl_format: u1::new(1),
+            reserved_bit: u1::new(0),
+            arbitration_to_data_sequence: u4::new(0b1110),
+            service_data_unit_type,
+            simple_extended_content,
+            data_length_code: u11::new((data_field.len() - 1) as u16),
+            stuff_bit_count: u3::new(0),
+            stuff_bit_count_parity: u1::new(0),
+            preface_cyclic_redundancy_check: u13::new(0),
+            virtual_can_network_identifier,
+            acceptance_field,
+            data_field,
+            frame_cyclic_redundancy_check: 0,
+            format_check_pattern: u4::new(0b1100),
+            data_to_arbitration_sequence: u4::new(0b1101),
+            acknowledgement_slot: u1::new(0),
+            acknowledgement_delimiter: u1::new(1),
+            end_of_frame: u7::new(0b1111111),
+            inter_frame_spacing: u3::new(0b111),
+        };
+
+        let bits = xl_data_frame.to_unstuffed_bits();
+
+        xl_data_frame.stuff_bit_count = fd_stuff_count(&bits[..DYNAMICALLY_STUFFED_LENGTH]);
+        xl_data_frame.stuff_bit_count_parity = stuff_count_parity(xl_data_frame.stuff_bit_count);
+
+        let bits = xl_data_frame.to_unstuffed_bits();
+
+        xl_data_frame.preface_cyclic_redundancy_check =
+            crc13(cyclic_redundancy_check_sequence(&bits, STUFF_BIT_COUNT_OFFSET + 4));
+
+        let bits = xl_data_frame.to_unstuffed_bits();
+
+        xl_data_frame.frame_cyclic_redundancy_check = crc32(cyclic_redundancy_check_sequence(
+            &bits,
+            DATA_FIELD_OFFSET + 8 * xl_data_frame.data_field.len(),
+        ));
+
+        Ok(xl_data_frame)
+    }
+
+    pub fn from_unstuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let mut bit_index = 0;
+
+        let start_of_frame = extract_field(
+            unstuffed_bits,
+            bit_index,
+            1,
+            CANFrameDecodingError::StartOfFrameMissing,
+            |start_of_frame_bit_slice| u1::new(start_of_frame_bit_slice.load_be()),
+        )?;
+
+        if start_of_frame != u1::new(0) {
+            return Err(CANFrameDecodingError::StartOfFrameMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let priority_identifier = extract_field(
+            unstuffed_bits,
+            bit_index,
+            11,
+            CANFrameDecodingError::IdentifierMissing,
+            |priority_identifier_bit_slice| u11::new(priority_identifier_bit_slice.load_be()),
+        )?;
+
+        bit_index += 11;
+
+        let maybe_remote_request_substitution_bit_ref = unstuffed_bits.get(bit_index);
+        let remote_request_substitution_bit_ref = maybe_remote_request_substitution_bit_ref
+            .ok_or(CANFrameDecodingError::RemoteRequestSubstitutionMissing)?;
+        let remote_request_substitution = u1::from(*remote_request_substitution_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_identifier_extension_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let identifier_extension_bit_bit_ref = maybe_identifier_extension_bit_bit_ref
+            .ok_or(CANFrameDecodingError::IdentifierExtensionBitMissing)?;
+        let identifier_extension_bit = u1::from(*identifier_extension_bit_bit_ref);
+        if identifier_extension_bit != u1::new(0) {
+            return Err(CANFrameDecodingError::IdentifierExtensionBitMustBeZero);
+        }
+
+        bit_index += 1;
+
+        let maybe_flexible_data_rate_format_bit_ref = unstuffed_bits.get(bit_index);
+        let flexible_data_rate_format_bit_ref = maybe_flexible_data_rate_format_bit_ref
+            .ok_or(CANFrameDecodingError::FlexibleDataRateFormatMissing)?;
+        let flexible_data_rate_format = u1::from(*flexible_data_rate_format_bit_ref);
+        if flexible_data_rate_format != u1::new(1) {
+            return Err(CANFrameDecodingError::FlexibleDataRateFormatMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_xl_format_bit_ref = unstuffed_bits.get(bit_index);
+        let xl_format_bit_ref =
+            maybe_xl_format_bit_ref.ok_or(CANFrameDecodingError::XLFormatMissing)?;
+        let xl_format = u1::from(*xl_format_bit_ref);
+        if xl_format != u1::new(1) {
+            return Err(CANFrameDecodingError::XLFormatMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_reserved_bit_bit_ref = unstuffed_bits.get(bit_index);
+        let reserved_bit_bit_ref =
+            maybe_reserved_bit_bit_ref.ok_or(CANFrameDecodingError::ReservedBitMissing)?;
+        let reserved_bit = u1::from(*reserved_bit_bit_ref);
+
+        bit_index += 1;
+
+        let arbitration_to_data_sequence = extract_field(
+            unstuffed_bits,
+            bit_index,
+            4,
+            CANFrameDecodingError::ArbitrationToDataSequenceMissing,
+            |arbitration_to_data_sequence_bit_slice| {
+                u4::new(arbitration_to_data_sequence_bit_slice.load_be())
+            },
+        )?;
+
+        if arbitration_to_data_sequence != u4::new(0b1110) {
+            return Err(CANFrameDecodingError::ArbitrationToDataSequenceInvalid);
+        }
+
+        bit_index += 4;
+
+        let service_data_unit_type = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8,
+            CANFrameDecodingError::ServiceDataUnitTypeMissing,
+            |service_data_unit_type_bit_slice| service_data_unit_type_bit_slice.load_be::<u8>(),
+        )?;
+
+        bit_index += 8;
+
+        let maybe_simple_extended_content_bit_ref = unstuffed_bits.get(bit_index);
+        let simple_extended_content_bit_ref = maybe_simple_extended_content_bit_ref
+            .ok_or(CANFrameDecodingError::SimpleExtendedContentMissing)?;
+        let simple_extended_content = u1::from(*simple_extended_content_bit_ref);
+
+        bit_index += 1;
+
+        let data_length_code = extract_field(
+            unstuffed_bits,
+            bit_index,
+            11,
+            CANFrameDecodingError::DataLengthCodeMissing,
+            |data_length_code_bit_slice| u11::new(data_length_code_bit_slice.load_be()),
+        )?;
+
+        bit_index += 11;
+
+        let stuff_bit_count = extract_field(
+            unstuffed_bits,
+            bit_index,
+            3,
+            CANFrameDecodingError::StuffCountMissing,
+            |stuff_bit_count_bit_slice| {
+                from_gray_code(u3::new(stuff_bit_count_bit_slice.load_be()))
+            },
+        )?;
+
+        bit_index += 3;
+
+        let maybe_stuff_bit_count_parity_bit_ref = unstuffed_bits.get(bit_index);
+        let stuff_bit_count_parity_bit_ref =
+            maybe_stuff_bit_count_parity_bit_ref.ok_or(CANFrameDecodingError::StuffCountMissing)?;
+        let stuff_bit_count_parity = u1::from(*stuff_bit_count_parity_bit_ref);
+
+        bit_index += 1;
+
+        let preface_cyclic_redundancy_check = extract_field(
+            unstuffed_bits,
+            bit_index,
+            13,
+            CANFrameDecodingError::PrefaceCyclicRedundancyCheckMissing,
+            |preface_cyclic_redundancy_check_bit_slice| {
+                u13::new(preface_cyclic_redundancy_check_bit_slice.load_be())
+            },
+        )?;
+
+        bit_index += 13;
+
+        let virtual_can_network_identifier = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8,
+            CANFrameDecodingError::VirtualCANNetworkIdentifierMissing,
+            |virtual_can_network_identifier_bit_slice| {
+                virtual_can_network_identifier_bit_slice.load_be::<u8>()
+            },
+        )?;
+
+        bit_index += 8;
+
+        let acceptance_field = extract_field(
+            unstuffed_bits,
+            bit_index,
+            32,
+            CANFrameDecodingError::AcceptanceFieldMissing,
+            |acceptance_field_bit_slice| acceptance_field_bit_slice.load_be::<u32>(),
+        )?;
+
+        bit_index += 32;
+
+        let data_field_length = xl_data_field_length(data_length_code);
+
+        let data_field = extract_field(
+            unstuffed_bits,
+            bit_index,
+            8 * data_field_length,
+            CANFrameDecodingError::DataFieldMissing,
+            |data_field_bit_slice| {
+                data_field_bit_slice
+                    .chunks(8)
+                    .map(|data_byte_bit_slice| data_byte_bit_slice.load_be::<u8>())
+                    .collect::<Vec<u8>>()
+            },
+        )?;
+
+        bit_index += 8 * data_field_length;
+
+        let frame_cyclic_redundancy_check = extract_field(
+            unstuffed_bits,
+            bit_index,
+            32,
+            CANFrameDecodingError::CyclicRedundancyCheckMissing,
+            |frame_cyclic_redundancy_check_bit_slice| {
+                frame_cyclic_redundancy_check_bit_slice.load_be::<u32>()
+            },
+        )?;
+
+        bit_index += 32;
+
+        let format_check_pattern = extract_field(
+            unstuffed_bits,
+            bit_index,
+            4,
+            CANFrameDecodingError::FormatCheckPatternMissing,
+            |format_check_pattern_bit_slice| u4::new(format_check_pattern_bit_slice.load_be()),
+        )?;
+
+        if format_check_pattern != u4::new(0b1100) {
+            return Err(CANFrameDecodingError::FormatCheckPatternInvalid);
+        }
+
+        bit_index += 4;
+
+        let data_to_arbitration_sequence = extract_field(
+            unstuffed_bits,
+            bit_index,
+            4,
+            CANFrameDecodingError::DataToArbitrationSequenceMissing,
+            |data_to_arbitration_sequence_bit_slice| {
+                u4::new(data_to_arbitration_sequence_bit_slice.load_be())
+            },
+        )?;
+
+        if data_to_arbitration_sequence != u4::new(0b1101) {
+            return Err(CANFrameDecodingError::DataToArbitrationSequenceInvalid);
+        }
+
+        bit_index += 4;
+
+        let maybe_acknowledgement_slot_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_slot_bit_ref = maybe_acknowledgement_slot_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementSlotMissing)?;
+        let acknowledgement_slot = u1::from(*acknowledgement_slot_bit_ref);
+
+        bit_index += 1;
+
+        let maybe_acknowledgement_delimiter_bit_ref = unstuffed_bits.get(bit_index);
+        let acknowledgement_delimiter_bit_ref = maybe_acknowledgement_delimiter_bit_ref
+            .ok_or(CANFrameDecodingError::AcknowledgementDelimiterMissing)?;
+        let acknowledgement_delimiter = u1::from(*acknowledgement_delimiter_bit_ref);
+        if acknowledgement_delimiter != u1::new(1) {
+            return Err(CANFrameDecodingError::AcknowledgementDelimiterMustBeOne);
+        }
+
+        bit_index += 1;
+
+        let maybe_end_of_frame_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 7));
+        let end_of_frame_bit_slice =
+            maybe_end_of_frame_bit_slice.ok_or(CANFrameDecodingError::EndOfFrameMissing)?;
+        let end_of_frame = u7::new(end_of_frame_bit_slice.load_be());
+        if end_of_frame != u7::new(0b1111111) {
+            return Err(CANFrameDecodingError::EndOfFrameMustBeOne);
+        }
+
+        bit_index += 7;
+
+        let maybe_inter_frame_spacing_bit_slice = unstuffed_bits.get(bit_index..(bit_index + 3));
+        let inter_frame_spacing_bit_slice = maybe_inter_frame_spacing_bit_slice
+            .ok_or(CANFrameDecodingError::InterFrameSpacingMissing)?;
+        let inter_frame_spacing = u3::new(inter_frame_spacing_bit_slice.load_be());
+        if inter_frame_spacing != u3::new(0b111) {
+            return Err(CANFrameDecodingError::InterFrameSpacingMustBeOne);
+        }
+
+        Ok(Self {
+            start_of_frame,
+            priority_identifier,
+            remote_request_substitution,
+            identifier_extension_bit,
+            flexible_data_rate_format,
+            xl_format,
+            reserved_bit,
+            arbitration_to_data_sequence,
+            service_data_unit_type,
+            simple_extended_content,
+            data_length_code,
+            stuff_bit_count,
+            stuff_bit_count_parity,
+            preface_cyclic_redundancy_check,
+            virtual_can_network_identifier,
+            acceptance_field,
+            data_field,
+            frame_cyclic_redundancy_check,
+            format_check_pattern,
+            data_to_arbitration_sequence,
+            acknowledgement_slot,
+            acknowledgement_delimiter,
+            end_of_frame,
+            inter_frame_spacing,
+        })
+    }
+
+    pub fn from_stuffed_bits<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn from_unstuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        unstuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        let unstuffed_bits = unstuffed_bits.as_ref();
+
+        let xl_data_frame = Self::from_unstuffed_bits(unstuffed_bits)?;
+
+        if stuff_count_parity(xl_data_frame.stuff_bit_count) != xl_data_frame.stuff_bit_count_parity
+        {
+            return Err(CANFrameDecodingError::StuffCountParityMismatch);
+        }
+
+        let expected_stuff_bit_count =
+            fd_stuff_count(&unstuffed_bits[..DYNAMICALLY_STUFFED_LENGTH]);
+        if expected_stuff_bit_count != xl_data_frame.stuff_bit_count {
+            return Err(CANFrameDecodingError::StuffCountMismatch {
+                expected: expected_stuff_bit_count,
+                received: xl_data_frame.stuff_bit_count,
+            });
+        }
+
+        let expected = crc13(cyclic_redundancy_check_sequence(
+            unstuffed_bits,
+            STUFF_BIT_COUNT_OFFSET + 4,
+        ));
+        let received = xl_data_frame.preface_cyclic_redundancy_check;
+
+        if expected != received {
+            return Err(CANFrameDecodingError::PrefaceCyclicRedundancyCheckMismatch {
+                expected,
+                received,
+            });
+        }
+
+        let expected = crc32(cyclic_redundancy_check_sequence(
+            unstuffed_bits,
+            DATA_FIELD_OFFSET + 8 * xl_data_frame.data_field.len(),
+        ));
+        let received = xl_data_frame.frame_cyclic_redundancy_check;
+
+        if expected != received {
+            return Err(CANFrameDecodingError::FrameCyclicRedundancyCheckMismatch {
+                expected,
+                received,
+            });
+        }
+
+        Ok(xl_data_frame)
+    }
+
+    pub fn from_stuffed_bits_checked<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(
+        stuffed_bits: B,
+    ) -> Result<Self, CANFrameDecodingError> {
+        Self::from_unstuffed_bits_checked(try_unstuff_frames(stuffed_bits)?)
+    }
+
+    pub fn to_unstuffed_bits(&self) -> BitVec<usize, Msb0> {
+        let mut bits = BitVec::new();
+
+        push_field(&mut bits, u64::from(self.start_of_frame), 1);
+        push_field(&mut bits, u64::from(self.priority_identifier), 11);
+        push_field(&mut bits, u64::from(self.remote_request_substitution), 1);
+        push_field(&mut bits, u64::from(self.identifier_extension_bit), 1);
+        push_field(&mut bits, u64::from(self.flexible_data_rate_format), 1);
+        push_field(&mut bits, u64::from(self.xl_format), 1);
+        push_field(&mut bits, u64::from(self.reserved_bit), 1);
+        push_field(&mut bits, u64::from(self.arbitration_to_data_sequence), 4);
+        push_field(&mut bits, u64::from(self.service_data_unit_type), 8);
+        push_field(&mut bits, u64::from(self.simple_extended_content), 1);
+        push_field(&mut bits, u64::from(self.data_length_code), 11);
+        push_field(&mut bits, u64::from(gray_code(self.stuff_bit_count)), 3);
+        push_field(&mut bits, u64::from(self.stuff_bit_count_parity), 1);
+        push_field(&mut bits, u64::from(self.preface_cyclic_redundancy_check), 13);
+        push_field(&mut bits, u64::from(self.virtual_can_network_identifier), 8);
+        push_field(&mut bits, u64::from(self.acceptance_field), 32);
+
+        for data_byte in &self.data_field {
+            push_field(&mut bits, u64::from(*data_byte), 8);
+        }
+
+        push_field(&mut bits, u64::from(self.frame_cyclic_redundancy_check), 32);
+        push_field(&mut bits, u64::from(self.format_check_pattern), 4);
+        push_field(&mut bits, u64::from(self.data_to_arbitration_sequence), 4);
+        push_field(&mut bits, u64::from(self.acknowledgement_slot), 1);
+        push_field(&mut bits, u64::from(self.acknowledgement_delimiter), 1);
+        push_field(&mut bits, u64::from(self.end_of_frame), 7);
+        push_field(&mut bits, u64::from(self.inter_frame_spacing), 3);
+
+        bits
+    }
+
+    pub fn to_stuffed_bits(&self) -> BitVec<usize, Msb0> {
+        stuff_frames(self.to_unstuffed_bits())
+    }
+
+    pub fn start_of_frame(&self) -> u1 {
+        self.start_of_frame
+    }
+
+    pub fn priority_identifier(&self) -> u11 {
+        self.priority_identifier
+    }
+
+    pub fn remote_request_substitution(&self) -> u1 {
+        self.remote_request_substitution
+    }
+
+    pub fn identifier_extension_bit(&self) -> u1 {
+        self.identifier_extension_bit
+    }
+
+    pub fn flexible_data_rate_format(&self) -> u1 {
+        self.flexible_data_rate_format
+    }
+
+    pub fn xl_format(&self) -> u1 {
+        self.xl_format
+    }
+
+    pub fn reserved_bit(&self) -> u1 {
+        self.reserved_bit
+    }
+
+    pub fn arbitration_to_data_sequence(&self) -> u4 {
+        self.arbitration_to_data_sequence
+    }
+
+    pub fn service_data_unit_type(&self) -> u8 {
+        self.service_data_unit_type
+    }
+
+    pub fn simple_extended_content(&self) -> u1 {
+        self.simple_extended_content
+    }
+
+    pub fn data_length_code(&self) -> u11 {
+        self.data_length_code
+    }
+
+    pub fn stuff_bit_count(&self) -> u3 {
+        self.stuff_bit_count
+    }
+
+    pub fn stuff_bit_count_parity(&self) -> u1 {
+        self.stuff_bit_count_parity
+    }
+
+    pub fn preface_cyclic_redundancy_check(&self) -> u13 {
+        self.preface_cyclic_redundancy_check
+    }
+
+    pub fn virtual_can_network_identifier(&self) -> u8 {
+        self.virtual_can_network_identifier
+    }
+
+    pub fn acceptance_field(&self) -> u32 {
+        self.acceptance_field
+    }
+
+    pub fn data_field(&self) -> &Vec<u8> {
+        &self.data_field
+    }
+
+    pub fn frame_cyclic_redundancy_check(&self) -> u32 {
+        self.frame_cyclic_redundancy_check
+    }
+
+    pub fn format_check_pattern(&self) -> u4 {
+        self.format_check_pattern
+    }
+
+    pub fn data_to_arbitration_sequence(&self) -> u4 {
+        self.data_to_arbitration_sequence
+    }
+
+    pub fn acknowledgement_slot(&self) -> u1 {
+        self.acknowledgement_slot
+    }
+
+    pub fn acknowledgement_delimiter(&self) -> u1 {
+        self.acknowledgement_delimiter
+    }
+
+    pub fn end_of_frame(&self) -> u7 {
+        self.end_of_frame
+    }
+
+    pub fn inter_frame_spacing(&self) -> u3 {
+        self.inter_frame_spacing
+    }
+}
+
+fn cyclic_redundancy_check_sequence<T: BitStore>(
+    unstuffed_bits: &BitSlice<T, Msb0>,
+    cyclic_redundancy_check_offset: usize,
+) -> BitVec<T, Msb0> {
+    // Both CRCs cover the dynamic stuff bits but not the fixed stuff bits.
+    let mut bits = stuff_dynamically(&unstuffed_bits[..DYNAMICALLY_STUFFED_LENGTH]);
+    bits.extend_from_bitslice(
+        &unstuffed_bits[DYNAMICALLY_STUFFED_LENGTH..cyclic_redundancy_check_offset],
+    );
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok};
+
+    use super::*;
+
+    fn xl_data_frame(data_field: Vec<u8>) -> XLDataFrame {
+        assert_ok!(XLDataFrame::new(
+            u11::new(0x123),
+            0x05,
+            u1::new(0),
+            0x2A,
+            0x12345678,
+            data_field
+        ))
+    }
+
+    #[test]
+    fn test_from_unstuffed_bits() {
+        let xl_data_frame = assert_ok!(XLDataFrame::from_unstuffed_bits(bitvec![usize, Msb0;
+            0,                                                      // SOF
+            0, 0, 1, 0, 0, 1, 0, 0, 0, 1, 1,                        // PID
+            0,                                                      // RRS
+            0,                                                      // IDE
+            1,                                                      // FDF
+            1,                                                      // XLF
+            0,                                                      // resXL
+            1, 1, 1, 0,                                             // ADS
+            0, 0, 0, 0, 0, 1, 0, 1,                                 // SDT
+            0,                                                      // SEC
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,                        // DLC
+            0, 0, 1,                                                // SBC
+            1,                                                      // SBC Parity
+            1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1,                  // PCRC
+            0, 0, 1, 0, 1, 0, 1, 0,                                 // VCID
+            0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 1, 1, 0, 1, 0, 0,         // AF
+            0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0,
+            1, 0, 1, 0, 1, 0, 1, 0,                                 // DF
+            0, 1, 0, 1, 0, 1, 0, 1,
+            1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0,         // FCRC
+            1, 1, 1, 1, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0,
+            1, 1, 0, 0,                                             // FCP
+            1, 1, 0, 1,                                             // DAS
+            0,                                                      // ACK Slot
+            1,                                                      // ACK Delim
+            1, 1, 1, 1, 1, 1, 1,                                    // EOF
+            1, 1, 1,                                                // IFS
+        ]));
+
+        assert_eq!(xl_data_frame.priority_identifier(), u11::new(0x123));
+        assert_eq!(xl_data_frame.service_data_unit_type(), 0x05);
+        assert_eq!(xl_data_frame.simple_extended_content(), u1::new(0));
+        assert_eq!(xl_data_frame.data_length_code(), u11::new(1));
+        assert_eq!(xl_data_frame.stuff_bit_count(), u3::new(1));
+        assert_eq!(xl_data_frame.preface_cyclic_redundancy_check(), u13::new(0x1555));
+        assert_eq!(xl_data_frame.virtual_can_network_identifier(), 0x2A);
+        assert_eq!(xl_data_frame.acceptance_field(), 0x12345678);
+        assert_eq!(xl_data_frame.data_field(), &vec![0xAA, 0x55]);
+        assert_eq!(xl_data_frame.frame_cyclic_redundancy_check(), 0xF0F0F0F0);
+    }
+
+    #[test]
+    fn test_new() {
+        let xl_data_frame = assert_ok!(XLDataFrame::new(
+            u11::new(0x000),
+            0x05,
+            u1::new(0),
+            0x2A,
+            0x12345678,
+            vec![0xAA, 0x55]
+        ));
+
+        assert_eq!(xl_data_frame.data_length_code(), u11::new(1));
+        // SOF, the priority identifier, RRS and IDE are fourteen dominant bits in a row.
+        assert_eq!(xl_data_frame.stuff_bit_count(), u3::new(2));
+        assert_eq!(xl_data_frame.stuff_bit_count_parity(), u1::new(0));
+        assert_ok!(XLDataFrame::from_unstuffed_bits_checked(
+            xl_data_frame.to_unstuffed_bits()
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for data_field_length in [1, 8, 64, 65, 1000, MAXIMUM_DATA_FIELD_LENGTH] {
+            let xl_data_frame =
+                xl_data_frame((0..data_field_length).map(|index| index as u8).collect());
+
+            assert_eq!(
+                assert_ok!(XLDataFrame::from_stuffed_bits_checked(
+                    xl_data_frame.to_stuffed_bits()
+                )),
+                xl_data_frame
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_stuff_bits() {
+        let xl_data_frame = xl_data_frame(vec![0x00; 16]);
+
+        let unstuffed_bits = xl_data_frame.to_unstuffed_bits();
+        let stuffed_bits = xl_data_frame.to_stuffed_bits();
+
+        let fixed_stuffed_length = DATA_FIELD_OFFSET + 8 * 16 + 32 - DYNAMICALLY_STUFFED_LENGTH;
+        let mut index =
+            DYNAMICALLY_STUFFED_LENGTH + u8::from(xl_data_frame.stuff_bit_count()) as usize;
+        let mut fixed_unstuffed_bits = BitVec::<usize, Msb0>::new();
+
+        // After the arbitration to data sequence, every ten bits are followed by a fixed stuff bit.
+        for fixed_stuffed_index in 0..fixed_stuffed_length {
+            if fixed_stuffed_index >= ARBITRATION_TO_DATA_SEQUENCE_LENGTH + FIXED_STUFF_INTERVAL
+                && (fixed_stuffed_index - ARBITRATION_TO_DATA_SEQUENCE_LENGTH)
+                    .is_multiple_of(FIXED_STUFF_INTERVAL)
+            {
+                assert_ne!(stuffed_bits[index], stuffed_bits[index - 1]);
+                index += 1;
+            }
+
+            fixed_unstuffed_bits.push(stuffed_bits[index]);
+            index += 1;
+        }
+
+        assert_eq!(
+            fixed_unstuffed_bits,
+            unstuffed_bits[DYNAMICALLY_STUFFED_LENGTH..][..fixed_stuffed_length]
+        );
+        assert_eq!(
+            stuffed_bits[index..],
+            unstuffed_bits[(DYNAMICALLY_STUFFED_LENGTH + fixed_stuffed_length)..]
+        );
+    }
+
+    #[test]
+    fn test_data_field_length_invalid() {
+        assert!(matches!(
+            assert_err!(XLDataFrame::new(u11::new(0x123), 0, u1::new(0), 0, 0, vec![])),
+            CANFrameEncodingError::DataFieldLengthInvalid
+        ));
+        assert!(matches!(
+            assert_err!(XLDataFrame::new(u11::new(0x123), 0, u1::new(0), 0, 0, vec![0; 2049])),
+            CANFrameEncodingError::DataFieldTooLong
+        ));
+    }
+
+    #[test]
+    fn test_preface_cyclic_redundancy_check_mismatch() {
+        let mut bits = xl_data_frame(vec![0xAA, 0x55]).to_unstuffed_bits();
+
+        let service_data_unit_type_bit = bits[21];
+        bits.set(21, !service_data_unit_type_bit);
+
+        assert!(matches!(
+            assert_err!(XLDataFrame::from_unstuffed_bits_checked(bits)),
+            CANFrameDecodingError::PrefaceCyclicRedundancyCheckMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_frame_cyclic_redundancy_check_mismatch() {
+        let mut bits = xl_data_frame(vec![0xAA, 0x55]).to_unstuffed_bits();
+
+        let data_field_bit = bits[DATA_FIELD_OFFSET];
+        bits.set(DATA_FIELD_OFFSET, !data_field_bit);
+
+        assert!(matches!(
+            assert_err!(XLDataFrame::from_unstuffed_bits_checked(bits)),
+            CANFrameDecodingError::FrameCyclicRedundancyCheckMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_virtual_can_network_identifier_missing() {
+        let bits = xl_data_frame(vec![0xAA]).to_unstuffed_bits();
+
+        assert_eq!(
+            assert_err!(XLDataFrame::from_unstuffed_bits(
+                &bits[..(STUFF_BIT_COUNT_OFFSET + 4 + 13)]
+            )),
+            CANFrameDecodingError::VirtualCANNetworkIdentifierMissing
+        );
+    }
+}
@@ -1,3 +1,11 @@
+pub mod bus_load;
 pub mod can_db_id;
 pub mod can_id;
+pub mod database;
+pub mod dlc;
+pub mod filter;
 pub mod frame;
+pub mod sampler;
+pub mod schedulability;
+pub mod signal;
+pub mod timing;
@@ -0,0 +1,695 @@
+use std::error::Error;
+
+use bitvec::prelude::*;
+use strum::Display;
+
+use crate::can::frame::{
+    event::{FrameReceiver, FrameReceiverStatus},
+    fd::{is_bit_rate_switch, is_flexible_data_rate_format},
+};
+
+const MINIMUM_SAMPLES_PER_BIT: f64 = 3.0;
+// A start of frame may follow the second bit of intermission, which is the tenth recessive bit
+// after the acknowledgement slot.
+const HARD_SYNCHRONIZATION_RECESSIVE_LENGTH: usize = 10;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SamplerConfigurationError {
+    BitrateMustBeNonZero,
+    SampleRateTooLow,
+    SamplePointOutOfRange,
+    TimeQuantaPerBitMustBeNonZero,
+    SynchronizationJumpWidthMustBeNonZero,
+    SynchronizationJumpWidthTooLong,
+    SampleRateMismatch,
+}
+
+impl Error for SamplerConfigurationError {}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SamplerConfiguration {
+    sample_rate: u32,
+    bitrate: u32,
+    sample_point: f64,
+    time_quanta_per_bit: u32,
+    synchronization_jump_width: u32,
+}
+
+impl SamplerConfiguration {
+    pub fn new(
+        sample_rate: u32,
+        bitrate: u32,
+        sample_point: f64,
+        time_quanta_per_bit: u32,
+        synchronization_jump_width: u32,
+    ) -> Result<Self, SamplerConfigurationError> {
+        if bitrate == 0 {
+            return Err(SamplerConfigurationError::BitrateMustBeNonZero);
+        }
+
+        if f64::from(sample_rate) / f64::from(bitrate) < MINIMUM_SAMPLES_PER_BIT {
+            return Err(SamplerConfigurationError::SampleRateTooLow);
+        }
+
+        if !(sample_point > 0.0 && sample_point < 100.0) {
+            return Err(SamplerConfigurationError::SamplePointOutOfRange);
+        }
+
+        if time_quanta_per_bit == 0 {
+            return Err(SamplerConfigurationError::TimeQuantaPerBitMustBeNonZero);
+        }
+
+        if synchronization_jump_width == 0 {
+            return Err(SamplerConfigurationError::SynchronizationJumpWidthMustBeNonZero);
+        }
+
+        let phase_segment_2 = f64::from(time_quanta_per_bit) * (100.0 - sample_point) / 100.0;
+        if f64::from(synchronization_jump_width) > phase_segment_2 {
+            return Err(SamplerConfigurationError::SynchronizationJumpWidthTooLong);
+        }
+
+        Ok(Self {
+            sample_rate,
+            bitrate,
+            sample_point,
+            time_quanta_per_bit,
+            synchronization_jump_width,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn bitrate(&self) -> u32 {
+        self.bitrate
+    }
+
+    pub fn sample_point(&self) -> f64 {
+        self.sample_point
+    }
+
+    pub fn time_quanta_per_bit(&self) -> u32 {
+        self.time_quanta_per_bit
+    }
+
+    pub fn synchronization_jump_width(&self) -> u32 {
+        self.synchronization_jump_width
+    }
+
+    fn samples_per_bit(&self) -> f64 {
+        f64::from(self.sample_rate) / f64::from(self.bitrate)
+    }
+
+    fn samples_per_time_quantum(&self) -> f64 {
+        self.samples_per_bit() / f64::from(self.time_quanta_per_bit)
+    }
+
+    fn samples_to_sample_point(&self) -> f64 {
+        self.samples_per_bit() * self.sample_point / 100.0
+    }
+
+    fn samples_per_synchronization_jump_width(&self) -> f64 {
+        self.samples_per_time_quantum() * f64::from(self.synchronization_jump_width)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransmitterDelayCompensation {
+    secondary_sample_point_offset: u32,
+}
+
+impl TransmitterDelayCompensation {
+    pub fn new(secondary_sample_point_offset: u32) -> Self {
+        Self {
+            secondary_sample_point_offset,
+        }
+    }
+
+    pub fn secondary_sample_point_offset(&self) -> u32 {
+        self.secondary_sample_point_offset
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CANSampler {
+    configuration: SamplerConfiguration,
+    data_configuration: Option<SamplerConfiguration>,
+    transmitter_delay_compensation: Option<TransmitterDelayCompensation>,
+    transmitter_delay: Option<f64>,
+    frame_receiver: Option<FrameReceiver>,
+    data_phase: bool,
+    sample_index: usize,
+    bit_start: f64,
+    bit_sampled: bool,
+    bit_resynchronized: bool,
+    previous_sample: bool,
+    previous_bit: bool,
+    recessive_bit_count: usize,
+}
+
+impl CANSampler {
+    pub fn new(configuration: SamplerConfiguration) -> Self {
+        Self {
+            configuration,
+            data_configuration: None,
+            transmitter_delay_compensation: None,
+            transmitter_delay: None,
+            frame_receiver: None,
+            data_phase: false,
+            sample_index: 0,
+            bit_start: 0.0,
+            bit_sampled: false,
+            bit_resynchronized: false,
+            previous_sample: true,
+            previous_bit: true,
+            // Until the first edge, the bus is assumed to be idle.
+            recessive_bit_count: HARD_SYNCHRONIZATION_RECESSIVE_LENGTH,
+        }
+    }
+
+    pub fn sample<I: IntoIterator<Item = bool>>(
+        configuration: SamplerConfiguration,
+        samples: I,
+    ) -> BitVec<usize, Msb0> {
+        let mut can_sampler = Self::new(configuration);
+
+        samples
+            .into_iter()
+            .filter_map(|sample| can_sampler.push(sample))
+            .collect()
+    }
+
+    pub fn new_fd(
+        nominal_configuration: SamplerConfiguration,
+        data_configuration: SamplerConfiguration,
+        transmitter_delay_compensation: Option<TransmitterDelayCompensation>,
+    ) -> Result<Self, SamplerConfigurationError> {
+        if nominal_configuration.sample_rate != data_configuration.sample_rate {
+            return Err(SamplerConfigurationError::SampleRateMismatch);
+        }
+
+        Ok(Self {
+            data_configuration: Some(data_configuration),
+            transmitter_delay_compensation,
+            ..Self::new(nominal_configuration)
+        })
+    }
+
+    pub fn sample_fd<I: IntoIterator<Item = bool>>(
+        nominal_configuration: SamplerConfiguration,
+        data_configuration: SamplerConfiguration,
+        transmitter_delay_compensation: Option<TransmitterDelayCompensation>,
+        samples: I,
+    ) -> Result<BitVec<usize, Msb0>, SamplerConfigurationError> {
+        let mut can_sampler = Self::new_fd(
+            nominal_configuration,
+            data_configuration,
+            transmitter_delay_compensation,
+        )?;
+
+        Ok(samples
+            .into_iter()
+            .filter_map(|sample| can_sampler.push(sample))
+            .collect())
+    }
+
+    pub fn configuration(&self) -> SamplerConfiguration {
+        self.configuration
+    }
+
+    pub fn data_configuration(&self) -> Option<SamplerConfiguration> {
+        self.data_configuration
+    }
+
+    pub fn transmitter_delay_compensation(&self) -> Option<TransmitterDelayCompensation> {
+        self.transmitter_delay_compensation
+    }
+
+    pub fn transmitter_delay(&self) -> Option<f64> {
+        self.transmitter_delay
+    }
+
+    pub fn is_data_phase(&self) -> bool {
+        self.data_phase
+    }
+
+    pub fn sample_index(&self) -> usize {
+        self.sample_index
+    }
+
+    pub fn push(&mut self, sample: bool) -> Option<bool> {
+        let sample_index = self.sample_index as f64;
+        self.sample_index += 1;
+
+        let samples_per_bit = self.bit_configuration().samples_per_bit();
+
+        if self.bit_sampled && sample_index >= self.bit_start + samples_per_bit {
+            self.bit_start += samples_per_bit;
+            self.bit_sampled = false;
+            self.bit_resynchronized = false;
+        }
+
+        if self.previous_sample && !sample {
+            self.synchronize(sample_index);
+        }
+
+        self.previous_sample = sample;
+
+        if self.bit_sampled || sample_index < self.sample_point() {
+            return None;
+        }
+
+        self.bit_sampled = true;
+        self.previous_bit = sample;
+        self.receive(sample);
+        self.recessive_bit_count = if sample {
+            self.recessive_bit_count + 1
+        } else {
+            0
+        };
+
+        Some(sample)
+    }
+
+    fn bit_configuration(&self) -> SamplerConfiguration {
+        match self.data_configuration {
+            Some(data_configuration) if self.data_phase => data_configuration,
+            _ => self.configuration,
+        }
+    }
+
+    fn sample_point(&self) -> f64 {
+        let bit_configuration = self.bit_configuration();
+
+        match (self.transmitter_delay_compensation, self.transmitter_delay) {
+            // A transmitter samples its own data phase bits at the secondary sample point, which
+            // follows the measured delay of the transceiver loop.
+            (Some(transmitter_delay_compensation), Some(transmitter_delay)) if self.data_phase => {
+                self.bit_start
+                    + transmitter_delay
+                    + bit_configuration.samples_per_time_quantum()
+                        * f64::from(transmitter_delay_compensation.secondary_sample_point_offset)
+            }
+            _ => self.bit_start + bit_configuration.samples_to_sample_point(),
+        }
+    }
+
+    fn receive(&mut self, bit: bool) {
+        let Some(frame_receiver) = &mut self.frame_receiver else {
+            if !bit && self.recessive_bit_count >= HARD_SYNCHRONIZATION_RECESSIVE_LENGTH {
+                self.frame_receiver = Some(FrameReceiver::new(0));
+                self.transmitter_delay = None;
+            }
+
+            return;
+        };
+
+        // The bit rate switches at the sample point of the bit rate switch bit and switches back at
+        // the sample point of the CRC delimiter, or of the bit where an error is detected.
+        let data_phase = match frame_receiver.push(bit) {
+            FrameReceiverStatus::Receiving if self.data_phase => {
+                frame_receiver.stuffing_layout().is_none_or(|stuffing_layout| {
+                    frame_receiver.unstuffed_bits().len()
+                        <= stuffing_layout.cyclic_redundancy_check_delimiter_index()
+                })
+            }
+            FrameReceiverStatus::Receiving => {
+                self.data_configuration.is_some()
+                    && is_bit_rate_switch(frame_receiver.unstuffed_bits())
+            }
+            _ => {
+                self.frame_receiver = None;
+                false
+            }
+        };
+
+        if data_phase != self.data_phase {
+            let sample_point =
+                self.bit_start + self.bit_configuration().samples_to_sample_point();
+            self.data_phase = data_phase;
+            self.bit_start = sample_point - self.bit_configuration().samples_to_sample_point();
+        }
+    }
+
+    fn synchronize(&mut self, edge_index: f64) {
+        if self.recessive_bit_count >= HARD_SYNCHRONIZATION_RECESSIVE_LENGTH {
+            self.bit_start = edge_index;
+            self.bit_sampled = false;
+            self.bit_resynchronized = true;
+            return;
+        }
+
+        let transmitting = self.transmitter_delay_compensation.is_some();
+
+        if self
+            .frame_receiver
+            .as_ref()
+            .is_some_and(|frame_receiver| {
+                is_flexible_data_rate_format(frame_receiver.unstuffed_bits())
+            })
+        {
+            if transmitting {
+                // The transmitter measures how late its own edge from the flexible data rate
+                // format bit to the reserved bit arrives back.
+                let bit_start = if self.bit_sampled {
+                    self.bit_start + self.configuration.samples_per_bit()
+                } else {
+                    self.bit_start
+                };
+                self.transmitter_delay = Some(edge_index - bit_start);
+            } else {
+                // Receivers hard synchronize on the edge from the flexible data rate format bit
+                // to the reserved bit.
+                self.bit_start = edge_index;
+                self.bit_sampled = false;
+            }
+
+            self.bit_resynchronized = true;
+            return;
+        }
+
+        // Only one resynchronization is allowed between sample points, and only on edges that
+        // follow a recessive sample point. A transmitter compensating for its delay does not
+        // resynchronize in the data phase.
+        if self.bit_resynchronized || !self.previous_bit || (transmitting && self.data_phase) {
+            return;
+        }
+
+        let bit_configuration = self.bit_configuration();
+        let synchronization_jump_width = bit_configuration.samples_per_synchronization_jump_width();
+
+        if self.bit_sampled {
+            // The edge arrived before the end of phase segment 2, so the bit is shortened.
+            let next_bit_start = self.bit_start + bit_configuration.samples_per_bit();
+            self.bit_start -= (next_bit_start - edge_index).min(synchronization_jump_width);
+        } else if !transmitting {
+            // The edge arrived after the synchronization segment, so phase segment 1 is lengthened.
+            // A transmitter does not do this for the late echoes of its own dominant bits.
+            self.bit_start += (edge_index - self.bit_start).min(synchronization_jump_width);
+        }
+
+        self.bit_resynchronized = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::{u1, u11, u29};
+
+    use crate::can::{
+        can_id::CANID,
+        frame::{CANFrame, splitter::CANFrameSplitter},
+    };
+
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 24_000_000;
+    const BITRATE: u32 = 500_000;
+    const DATA_BITRATE: u32 = 2_000_000;
+
+    fn samples(bits: &BitSlice<usize, Msb0>, bitrate: f64, offset: f64) -> Vec<bool> {
+        let sample_count =
+            ((bits.len() as f64 / bitrate + offset) * f64::from(SAMPLE_RATE)) as usize;
+
+        (0..sample_count)
+            .map(|sample_index| {
+                let time = sample_index as f64 / f64::from(SAMPLE_RATE) - offset;
+
+                if time < 0.0 {
+                    true
+                } else {
+                    bits.get((time * bitrate) as usize).is_none_or(|bit| *bit)
+                }
+            })
+            .collect()
+    }
+
+    fn bits() -> BitVec<usize, Msb0> {
+        let mut bits = bitvec![usize, Msb0; 1; 11];
+        bits.extend(
+            assert_ok!(CANFrame::new(
+                CANID::Standard(u11::new(0x000)),
+                vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+            ))
+            .to_stuffed_bits(),
+        );
+        bits.extend(
+            assert_ok!(CANFrame::new(
+                CANID::Extended(u29::new(0x1FFFFFFF)),
+                vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+            ))
+            .to_stuffed_bits(),
+        );
+
+        bits
+    }
+
+    fn configuration() -> SamplerConfiguration {
+        assert_ok!(SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 87.5, 16, 2))
+    }
+
+    fn data_configuration() -> SamplerConfiguration {
+        assert_ok!(SamplerConfiguration::new(SAMPLE_RATE, DATA_BITRATE, 75.0, 12, 3))
+    }
+
+    fn fd_can_frames() -> [CANFrame; 4] {
+        [
+            assert_ok!(CANFrame::new(
+                CANID::Standard(u11::new(0x7FF)),
+                vec![0xAA, 0x55]
+            )),
+            assert_ok!(CANFrame::new_fd(
+                CANID::Standard(u11::new(0x123)),
+                (0..16).collect(),
+                u1::new(1)
+            )),
+            assert_ok!(CANFrame::new_fd(
+                CANID::Standard(u11::new(0x456)),
+                vec![0xFF; 12],
+                u1::new(0)
+            )),
+            assert_ok!(CANFrame::new_fd(
+                CANID::Extended(u29::new(0x18FEF100)),
+                (0..64).map(|byte| byte * 3).collect(),
+                u1::new(1)
+            )),
+        ]
+    }
+
+    fn fd_bits() -> BitVec<usize, Msb0> {
+        let mut bits = bitvec![usize, Msb0; 1; 11];
+
+        for can_frame in fd_can_frames() {
+            bits.extend(can_frame.to_stuffed_bits());
+        }
+
+        bits
+    }
+
+    // Generates samples in which the data phase of frames with the bit rate switch bit set runs at
+    // the data bitrate. A transmitter delay lengthens each start of frame, as a transmitter sees it
+    // after hard synchronizing to its own start of frame.
+    fn fd_samples(bits: &BitSlice<usize, Msb0>, transmitter_delay: usize) -> Vec<bool> {
+        let samples_per_bit = (SAMPLE_RATE / BITRATE) as usize;
+        let samples_to_sample_point = samples_per_bit * 7 / 8;
+        let data_samples_per_bit = (SAMPLE_RATE / DATA_BITRATE) as usize;
+        let data_samples_to_sample_point = data_samples_per_bit * 3 / 4;
+
+        let mut samples = Vec::new();
+        let mut frame_receiver: Option<FrameReceiver> = None;
+        let mut data_phase = false;
+        let mut recessive_bit_count = 0;
+
+        for bit in bits.iter().by_vals() {
+            let mut length = if data_phase {
+                data_samples_per_bit
+            } else {
+                samples_per_bit
+            };
+
+            match &mut frame_receiver {
+                None if !bit && recessive_bit_count >= 10 => {
+                    frame_receiver = Some(FrameReceiver::new(0));
+                    length += transmitter_delay;
+                }
+                None => {}
+                Some(receiver) => match receiver.push(bit) {
+                    FrameReceiverStatus::Receiving
+                        if is_bit_rate_switch(receiver.unstuffed_bits()) =>
+                    {
+                        data_phase = true;
+                        length = samples_to_sample_point + data_samples_per_bit
+                            - data_samples_to_sample_point;
+                    }
+                    FrameReceiverStatus::Receiving
+                        if receiver.stuffing_layout().is_some_and(|stuffing_layout| {
+                            receiver.unstuffed_bits().len()
+                                == stuffing_layout.cyclic_redundancy_check_delimiter_index() + 1
+                        }) && data_phase =>
+                    {
+                        data_phase = false;
+                        length = data_samples_to_sample_point + samples_per_bit
+                            - samples_to_sample_point;
+                    }
+                    FrameReceiverStatus::Receiving => {}
+                    _ => frame_receiver = None,
+                },
+            }
+
+            recessive_bit_count = if bit { recessive_bit_count + 1 } else { 0 };
+            samples.extend(std::iter::repeat_n(bit, length));
+        }
+
+        samples
+    }
+
+    #[test]
+    fn test_sample() {
+        let bits = bits();
+
+        let sampled_bits =
+            CANSampler::sample(configuration(), samples(&bits, f64::from(BITRATE), 0.0));
+
+        assert_eq!(sampled_bits, bits);
+    }
+
+    #[test]
+    fn test_sample_hard_synchronization() {
+        let bits = bits();
+
+        let sampled_bits = CANSampler::sample(
+            configuration(),
+            samples(&bits, f64::from(BITRATE), 0.3 / f64::from(BITRATE)),
+        );
+
+        assert_eq!(sampled_bits, bits);
+    }
+
+    #[test]
+    fn test_sample_resynchronization() {
+        let bits = bits();
+
+        for bitrate in [f64::from(BITRATE) * 0.995, f64::from(BITRATE) * 1.005] {
+            let sampled_bits = CANSampler::sample(configuration(), samples(&bits, bitrate, 0.0));
+
+            // The sampler's own clock decides how many bits of trailing intermission fit in the
+            // capture.
+            let length = bits.len() - 3;
+            assert_eq!(sampled_bits[..length], bits[..length]);
+            assert_eq!(CANFrameSplitter::split(&sampled_bits).len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_sample_without_enough_synchronization_jump_width() {
+        let bits = bits();
+
+        let configuration =
+            assert_ok!(SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 87.5, 16, 1));
+        let sampled_bits = CANSampler::sample(
+            configuration,
+            samples(&bits, f64::from(BITRATE) * 0.95, 0.0),
+        );
+
+        assert_ne!(sampled_bits, bits);
+    }
+
+    #[test]
+    fn test_sample_fd() {
+        let bits = fd_bits();
+
+        let sampled_bits = assert_ok!(CANSampler::sample_fd(
+            configuration(),
+            data_configuration(),
+            None,
+            fd_samples(&bits, 0)
+        ));
+
+        assert_eq!(sampled_bits, bits);
+        assert_eq!(
+            CANFrameSplitter::split(&sampled_bits)
+                .into_iter()
+                .map(|(_, frame)| frame)
+                .collect::<Vec<_>>(),
+            fd_can_frames()
+        );
+    }
+
+    #[test]
+    fn test_sample_fd_without_data_configuration() {
+        let bits = fd_bits();
+
+        let sampled_bits = CANSampler::sample(configuration(), fd_samples(&bits, 0));
+
+        assert_ne!(sampled_bits, bits);
+    }
+
+    #[test]
+    fn test_sample_fd_transmitter_delay_compensation() {
+        let bits = fd_bits();
+        // The transmitter delay is longer than a data phase bit.
+        let samples = fd_samples(&bits, 15);
+
+        let mut can_sampler = assert_ok!(CANSampler::new_fd(
+            configuration(),
+            data_configuration(),
+            Some(TransmitterDelayCompensation::new(9))
+        ));
+        let sampled_bits = samples
+            .iter()
+            .filter_map(|sample| can_sampler.push(*sample))
+            .collect::<BitVec<usize, Msb0>>();
+
+        assert_eq!(sampled_bits, bits);
+        assert_eq!(can_sampler.transmitter_delay(), Some(15.0));
+
+        // Receivers hard synchronize to the delayed edges instead.
+        let sampled_bits = assert_ok!(CANSampler::sample_fd(
+            configuration(),
+            data_configuration(),
+            None,
+            samples
+        ));
+
+        assert_eq!(sampled_bits, bits);
+    }
+
+    #[test]
+    fn test_configuration_errors() {
+        assert_err_eq!(
+            SamplerConfiguration::new(SAMPLE_RATE, 0, 87.5, 16, 2),
+            SamplerConfigurationError::BitrateMustBeNonZero
+        );
+        assert_err_eq!(
+            SamplerConfiguration::new(1_000_000, 500_000, 87.5, 16, 2),
+            SamplerConfigurationError::SampleRateTooLow
+        );
+        assert_err_eq!(
+            SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 100.0, 16, 2),
+            SamplerConfigurationError::SamplePointOutOfRange
+        );
+        assert_err_eq!(
+            SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 87.5, 0, 2),
+            SamplerConfigurationError::TimeQuantaPerBitMustBeNonZero
+        );
+        assert_err_eq!(
+            SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 87.5, 16, 0),
+            SamplerConfigurationError::SynchronizationJumpWidthMustBeNonZero
+        );
+        assert_err_eq!(
+            SamplerConfiguration::new(SAMPLE_RATE, BITRATE, 87.5, 16, 3),
+            SamplerConfigurationError::SynchronizationJumpWidthTooLong
+        );
+        assert_err_eq!(
+            CANSampler::new_fd(
+                configuration(),
+                assert_ok!(SamplerConfiguration::new(48_000_000, DATA_BITRATE, 75.0, 12, 3)),
+                None
+            )
+            .map(|can_sampler| can_sampler.data_configuration()),
+            SamplerConfigurationError::SampleRateMismatch
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use std::{collections::HashSet, error::Error, time::Duration};
+
+use strum::Display;
+
+use crate::can::{bus_load::worst_case_bit_count, can_id::CANID};
+
+// Times are counted in ticks of a billionth of a bit, so that durations in nanoseconds convert
+// exactly at any bitrate.
+const TICKS_PER_BIT: u128 = 1_000_000_000;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SchedulabilityError {
+    BitrateMustBeNonZero,
+    PeriodMustBeNonZero,
+    DataFieldTooLong,
+    DuplicateIdentifier,
+}
+
+impl Error for SchedulabilityError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ScheduledMessage {
+    identifier: CANID,
+    period: Duration,
+    jitter: Duration,
+    deadline: Duration,
+    data_field_length: usize,
+    worst_case_bit_count: usize,
+}
+
+impl ScheduledMessage {
+    pub fn new(
+        identifier: CANID,
+        period: Duration,
+        jitter: Duration,
+        deadline: Duration,
+        data_field_length: usize,
+    ) -> Result<Self, SchedulabilityError> {
+        if period.is_zero() {
+            return Err(SchedulabilityError::PeriodMustBeNonZero);
+        }
+
+        let worst_case_bit_count = worst_case_bit_count(identifier, data_field_length)
+            .map_err(|_| SchedulabilityError::DataFieldTooLong)?;
+
+        Ok(Self {
+            identifier,
+            period,
+            jitter,
+            deadline,
+            data_field_length,
+            worst_case_bit_count,
+        })
+    }
+
+    pub fn identifier(&self) -> CANID {
+        self.identifier
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    pub fn data_field_length(&self) -> usize {
+        self.data_field_length
+    }
+
+    pub fn worst_case_bit_count(&self) -> usize {
+        self.worst_case_bit_count
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResponseTime {
+    identifier: CANID,
+    deadline: Duration,
+    worst_case_response_time: Option<Duration>,
+}
+
+impl ResponseTime {
+    pub fn identifier(&self) -> CANID {
+        self.identifier
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    // None when the message and those of higher priority need more than the whole bus, so the
+    // response time is unbounded.
+    pub fn worst_case_response_time(&self) -> Option<Duration> {
+        self.worst_case_response_time
+    }
+
+    pub fn meets_deadline(&self) -> bool {
+        self.worst_case_response_time
+            .is_some_and(|worst_case_response_time| worst_case_response_time <= self.deadline)
+    }
+}
+
+// The worst case response time of each message, in the order given, from the fixed priority
+// non-preemptive analysis of Davis, Burns, Bril and Lukkien (2007), which corrects Tindell's.
+pub fn response_times(
+    scheduled_messages: &[ScheduledMessage],
+    bitrate: u32,
+) -> Result<Vec<ResponseTime>, SchedulabilityError> {
+    if bitrate == 0 {
+        return Err(SchedulabilityError::BitrateMustBeNonZero);
+    }
+
+    let mut identifiers = HashSet::new();
+    for scheduled_message in scheduled_messages {
+        if !identifiers.insert(scheduled_message.identifier) {
+            return Err(SchedulabilityError::DuplicateIdentifier);
+        }
+    }
+
+    let analysed_messages: Vec<AnalysedMessage> = scheduled_messages
+        .iter()
+        .map(|scheduled_message| AnalysedMessage::new(scheduled_message, bitrate))
+        .collect();
+
+    Ok(analysed_messages
+        .iter()
+        .map(|analysed_message| ResponseTime {
+            identifier: analysed_message.identifier,
+            deadline: analysed_message.deadline,
+            worst_case_response_time: worst_case_response_time(
+                analysed_message,
+                &analysed_messages,
+            )
+            .map(|ticks| ticks_to_duration(ticks, bitrate)),
+        })
+        .collect())
+}
+
+struct AnalysedMessage {
+    identifier: CANID,
+    deadline: Duration,
+    period: u128,
+    jitter: u128,
+    transmission_time: u128,
+}
+
+impl AnalysedMessage {
+    fn new(scheduled_message: &ScheduledMessage, bitrate: u32) -> Self {
+        Self {
+            identifier: scheduled_message.identifier,
+            deadline: scheduled_message.deadline,
+            period: duration_to_ticks(scheduled_message.period, bitrate),
+            jitter: duration_to_ticks(scheduled_message.jitter, bitrate),
+            transmission_time: scheduled_message.worst_case_bit_count as u128 * TICKS_PER_BIT,
+        }
+    }
+
+    fn is_higher_priority_than(&self, other: &Self) -> bool {
+        self.identifier < other.identifier
+    }
+
+    // The most transmission time that instances released within the window can take.
+    fn interference(&self, window: u128) -> u128 {
+        (window + self.jitter).div_ceil(self.period) * self.transmission_time
+    }
+}
+
+fn worst_case_response_time(
+    analysed_message: &AnalysedMessage,
+    analysed_messages: &[AnalysedMessage],
+) -> Option<u128> {
+    let higher_priority_messages: Vec<&AnalysedMessage> = analysed_messages
+        .iter()
+        .filter(|other| other.is_higher_priority_than(analysed_message))
+        .collect();
+
+    // Once a lower priority frame has started, it cannot be preempted.
+    let blocking_time = analysed_messages
+        .iter()
+        .filter(|other| analysed_message.is_higher_priority_than(other))
+        .map(|other| other.transmission_time)
+        .max()
+        .unwrap_or(0);
+
+    let utilisation: f64 = higher_priority_messages
+        .iter()
+        .chain([&analysed_message])
+        .map(|message| message.transmission_time as f64 / message.period as f64)
+        .sum();
+
+    if utilisation >= 1.0 {
+        return None;
+    }
+
+    // The level-m busy period, which every instance that can be delayed by an earlier one falls
+    // within.
+    let mut busy_period = analysed_message.transmission_time;
+    loop {
+        let next_busy_period = blocking_time
+            + higher_priority_messages
+                .iter()
+                .chain([&analysed_message])
+                .map(|message| message.interference(busy_period))
+                .sum::<u128>();
+
+        if next_busy_period == busy_period {
+            break;
+        }
+
+        busy_period = next_busy_period;
+    }
+
+    let instance_count = (busy_period + analysed_message.jitter).div_ceil(analysed_message.period);
+
+    let mut worst_case_response_time = 0;
+    let mut queuing_delay = blocking_time;
+
+    for instance in 0..instance_count {
+        queuing_delay =
+            queuing_delay.max(blocking_time + instance * analysed_message.transmission_time);
+        loop {
+            // A higher priority frame queued up to a bit after the queuing delay still wins the
+            // arbitration.
+            let next_queuing_delay = blocking_time
+                + instance * analysed_message.transmission_time
+                + higher_priority_messages
+                    .iter()
+                    .map(|message| message.interference(queuing_delay + TICKS_PER_BIT))
+                    .sum::<u128>();
+
+            if next_queuing_delay == queuing_delay {
+                break;
+            }
+
+            queuing_delay = next_queuing_delay;
+        }
+
+        let response_time = analysed_message.jitter + queuing_delay
+            - instance * analysed_message.period
+            + analysed_message.transmission_time;
+        worst_case_response_time = worst_case_response_time.max(response_time);
+    }
+
+    Some(worst_case_response_time)
+}
+
+fn duration_to_ticks(duration: Duration, bitrate: u32) -> u128 {
+    duration.as_nanos() * u128::from(bitrate)
+}
+
+fn ticks_to_duration(ticks: u128, bitrate: u32) -> Duration {
+    Duration::from_nanos(ticks.div_ceil(u128::from(bitrate)) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+    use ux::{u11, u29};
+
+    use super::*;
+
+    fn scheduled_message(identifier: CANID, period: Duration) -> ScheduledMessage {
+        assert_ok!(ScheduledMessage::new(identifier, period, Duration::ZERO, period, 8))
+    }
+
+    #[test]
+    fn test_response_times() {
+        let period = Duration::from_millis(1);
+        let scheduled_messages = [
+            scheduled_message(CANID::Standard(u11::new(0x003)), period),
+            scheduled_message(CANID::Standard(u11::new(0x001)), period),
+            scheduled_message(CANID::Standard(u11::new(0x002)), period),
+        ];
+
+        // Each frame takes 135 us at 1 Mbit/s.
+        let response_times = assert_ok!(response_times(&scheduled_messages, 1_000_000));
+
+        assert_eq!(response_times[0].identifier(), CANID::Standard(u11::new(0x003)));
+        assert_eq!(
+            response_times[0].worst_case_response_time(),
+            Some(Duration::from_micros(405))
+        );
+        assert_eq!(
+            response_times[1].worst_case_response_time(),
+            Some(Duration::from_micros(270))
+        );
+        assert_eq!(
+            response_times[2].worst_case_response_time(),
+            Some(Duration::from_micros(405))
+        );
+        assert!(response_times.iter().all(ResponseTime::meets_deadline));
+    }
+
+    #[test]
+    fn test_response_times_later_instance() {
+        // The example from Davis et al., in which the second instance of the lowest priority
+        // message has the longest response time, which Tindell's analysis missed.
+        let scheduled_messages = [
+            scheduled_message(CANID::Standard(u11::new(0x001)), Duration::from_nanos(337_500)),
+            scheduled_message(CANID::Standard(u11::new(0x002)), Duration::from_nanos(472_500)),
+            scheduled_message(CANID::Standard(u11::new(0x003)), Duration::from_nanos(472_500)),
+        ];
+
+        let response_times = assert_ok!(response_times(&scheduled_messages, 1_000_000));
+
+        assert_eq!(
+            response_times[0].worst_case_response_time(),
+            Some(Duration::from_micros(270))
+        );
+        assert_eq!(
+            response_times[1].worst_case_response_time(),
+            Some(Duration::from_micros(405))
+        );
+        assert_eq!(
+            response_times[2].worst_case_response_time(),
+            Some(Duration::from_nanos(472_500))
+        );
+        assert!(response_times[2].meets_deadline());
+    }
+
+    #[test]
+    fn test_response_times_jitter_and_deadline() {
+        let scheduled_messages = [
+            assert_ok!(ScheduledMessage::new(
+                CANID::Standard(u11::new(0x000)),
+                Duration::from_millis(1),
+                Duration::from_micros(100),
+                Duration::from_millis(1),
+                8
+            )),
+            assert_ok!(ScheduledMessage::new(
+                CANID::Extended(u29::new(0x00000000)),
+                Duration::from_millis(1),
+                Duration::ZERO,
+                Duration::from_micros(250),
+                8
+            )),
+        ];
+
+        let response_times = assert_ok!(response_times(&scheduled_messages, 1_000_000));
+
+        // Blocked by the 160 us extended frame, and delayed by its own jitter.
+        assert_eq!(
+            response_times[0].worst_case_response_time(),
+            Some(Duration::from_micros(395))
+        );
+        assert!(response_times[0].meets_deadline());
+
+        // The extended frame with the same base identifier loses to the standard frame.
+        assert_eq!(
+            response_times[1].worst_case_response_time(),
+            Some(Duration::from_micros(295))
+        );
+        assert!(!response_times[1].meets_deadline());
+    }
+
+    #[test]
+    fn test_response_times_overloaded() {
+        let period = Duration::from_micros(300);
+        let scheduled_messages = [
+            scheduled_message(CANID::Standard(u11::new(0x001)), period),
+            scheduled_message(CANID::Standard(u11::new(0x002)), period),
+            scheduled_message(CANID::Standard(u11::new(0x003)), period),
+        ];
+
+        let response_times = assert_ok!(response_times(&scheduled_messages, 1_000_000));
+
+        assert!(response_times[0].worst_case_response_time().is_some());
+        assert!(response_times[1].worst_case_response_time().is_some());
+        assert_eq!(response_times[2].worst_case_response_time(), None);
+        assert!(!response_times[2].meets_deadline());
+    }
+
+    #[test]
+    fn test_schedulability_errors() {
+        assert_err_eq!(
+            ScheduledMessage::new(
+                CANID::Standard(u11::new(0x001)),
+                Duration::ZERO,
+                Duration::ZERO,
+                Duration::ZERO,
+                8
+            ),
+            SchedulabilityError::PeriodMustBeNonZero
+        );
+        assert_err_eq!(
+            ScheduledMessage::new(
+                CANID::Standard(u11::new(0x001)),
+                Duration::from_millis(1),
+                Duration::ZERO,
+                Duration::from_millis(1),
+                9
+            ),
+            SchedulabilityError::DataFieldTooLong
+        );
+
+        let message = scheduled_message(CANID::Standard(u11::new(0x001)), Duration::from_millis(1));
+
+        assert_err_eq!(response_times(&[message], 0), SchedulabilityError::BitrateMustBeNonZero);
+        assert_err_eq!(
+            response_times(&[message, message], 1_000_000),
+            SchedulabilityError::DuplicateIdentifier
+        );
+    }
+}
@@ -0,0 +1,529 @@
+use std::{
+    error::Error,
+    ops::{Range, RangeInclusive},
+};
+
+use bitvec::prelude::*;
+use strum::Display;
+
+const MAXIMUM_SIGNAL_LENGTH: usize = 64;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SignalError {
+    LengthMustBeNonZero,
+    LengthTooLong,
+    LengthInvalidForValueType,
+    FactorMustBeNonZero,
+    DataFieldTooShort,
+    ValueOutOfRange,
+    RawValueOutOfRange,
+}
+
+impl Error for SignalError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    // Intel
+    LittleEndian,
+    // Motorola
+    BigEndian,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Unsigned,
+    Signed,
+    // IEEE 754 single precision, which must be 32 bits long.
+    Float32,
+    // IEEE 754 double precision, which must be 64 bits long.
+    Float64,
+}
+
+// The physical value of a signal is its raw value times the factor, plus the offset.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Scaling {
+    factor: f64,
+    offset: f64,
+}
+
+impl Scaling {
+    pub fn new(factor: f64, offset: f64) -> Self {
+        Self { factor, offset }
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+}
+
+// Bits are numbered from the least significant bit of the first byte, as in DBC files. The start
+// bit is the least significant bit of a little endian signal, and the most significant bit of a
+// big endian one.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SignalSpec {
+    start_bit: usize,
+    length: usize,
+    byte_order: ByteOrder,
+    value_type: ValueType,
+    factor: f64,
+    offset: f64,
+    minimum: f64,
+    maximum: f64,
+}
+
+impl SignalSpec {
+    pub fn new(
+        start_bit: usize,
+        length: usize,
+        byte_order: ByteOrder,
+        value_type: ValueType,
+        scaling: Scaling,
+        range: RangeInclusive<f64>,
+    ) -> Result<Self, SignalError> {
+        if length == 0 {
+            return Err(SignalError::LengthMustBeNonZero);
+        }
+
+        if length > MAXIMUM_SIGNAL_LENGTH {
+            return Err(SignalError::LengthTooLong);
+        }
+
+        match value_type {
+            ValueType::Float32 if length != 32 => {
+                return Err(SignalError::LengthInvalidForValueType);
+            }
+            ValueType::Float64 if length != 64 => {
+                return Err(SignalError::LengthInvalidForValueType);
+            }
+            _ => {}
+        }
+
+        if scaling.factor() == 0.0 {
+            return Err(SignalError::FactorMustBeNonZero);
+        }
+
+        Ok(Self {
+            start_bit,
+            length,
+            byte_order,
+            value_type,
+            factor: scaling.factor(),
+            offset: scaling.offset(),
+            minimum: *range.start(),
+            maximum: *range.end(),
+        })
+    }
+
+    pub fn start_bit(&self) -> usize {
+        self.start_bit
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    pub fn scaling(&self) -> Scaling {
+        Scaling::new(self.factor, self.offset)
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    pub fn minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    pub fn decode_raw(&self, data_field: &[u8]) -> Result<u64, SignalError> {
+        let bit_range = self.bit_range(data_field)?;
+
+        Ok(match self.byte_order {
+            ByteOrder::LittleEndian => data_field.view_bits::<Lsb0>()[bit_range].load_le(),
+            ByteOrder::BigEndian => data_field.view_bits::<Msb0>()[bit_range].load_be(),
+        })
+    }
+
+    pub fn decode(&self, data_field: &[u8]) -> Result<f64, SignalError> {
+        let raw_value = self.decode_raw(data_field)?;
+
+        let value = match self.value_type {
+            ValueType::Unsigned => raw_value as f64,
+            ValueType::Signed => self.sign_extend(raw_value) as f64,
+            ValueType::Float32 => f64::from(f32::from_bits(raw_value as u32)),
+            ValueType::Float64 => f64::from_bits(raw_value),
+        };
+
+        Ok(value * self.factor + self.offset)
+    }
+
+    pub fn encode_raw(&self, raw_value: u64, data_field: &mut [u8]) -> Result<(), SignalError> {
+        if self.length < MAXIMUM_SIGNAL_LENGTH && raw_value >> self.length != 0 {
+            return Err(SignalError::RawValueOutOfRange);
+        }
+
+        let bit_range = self.bit_range(data_field)?;
+
+        match self.byte_order {
+            ByteOrder::LittleEndian => {
+                data_field.view_bits_mut::<Lsb0>()[bit_range].store_le(raw_value)
+            }
+            ByteOrder::BigEndian => {
+                data_field.view_bits_mut::<Msb0>()[bit_range].store_be(raw_value)
+            }
+        }
+
+        Ok(())
+    }
+
+    // A minimum that is not below the maximum, such as the 0 to 0 that DBC files often give,
+    // leaves the value unbounded.
+    pub fn encode(&self, value: f64, data_field: &mut [u8]) -> Result<(), SignalError> {
+        if self.minimum < self.maximum && !(self.minimum..=self.maximum).contains(&value) {
+            return Err(SignalError::ValueOutOfRange);
+        }
+
+        self.encode_unscaled((value - self.offset) / self.factor, data_field)
+    }
+
+    // Encodes a value in raw units, before the factor and offset are applied, such as the start
+    // values that DBC files give.
+    pub(crate) fn encode_unscaled(
+        &self,
+        unscaled_value: f64,
+        data_field: &mut [u8],
+    ) -> Result<(), SignalError> {
+        let raw_value = unscaled_value.round();
+
+        let raw_value = match self.value_type {
+            ValueType::Unsigned => {
+                if raw_value < 0.0
+                    || raw_value > (u64::MAX >> (MAXIMUM_SIGNAL_LENGTH - self.length)) as f64
+                {
+                    return Err(SignalError::RawValueOutOfRange);
+                }
+
+                raw_value as u64
+            }
+            ValueType::Signed => {
+                let maximum_raw_value = (i64::MAX >> (MAXIMUM_SIGNAL_LENGTH - self.length)) as f64;
+                if raw_value < -maximum_raw_value - 1.0 || raw_value > maximum_raw_value {
+                    return Err(SignalError::RawValueOutOfRange);
+                }
+
+                (raw_value as i64 as u64) & (u64::MAX >> (MAXIMUM_SIGNAL_LENGTH - self.length))
+            }
+            ValueType::Float32 => u64::from((unscaled_value as f32).to_bits()),
+            ValueType::Float64 => unscaled_value.to_bits(),
+        };
+
+        self.encode_raw(raw_value, data_field)
+    }
+
+    // Sign extends from the top bit of the signal.
+    pub(crate) fn sign_extend(&self, raw_value: u64) -> i64 {
+        let unused_bit_count = MAXIMUM_SIGNAL_LENGTH - self.length;
+        ((raw_value << unused_bit_count) as i64) >> unused_bit_count
+    }
+
+    // The range of the signal in a view of the data field with the same bit order as the signal,
+    // where it is contiguous.
+    fn bit_range(&self, data_field: &[u8]) -> Result<Range<usize>, SignalError> {
+        let first_bit_index = match self.byte_order {
+            ByteOrder::LittleEndian => self.start_bit,
+            ByteOrder::BigEndian => msb0_to_dbc_start_bit(self.start_bit),
+        };
+
+        let bit_range = first_bit_index..(first_bit_index + self.length);
+
+        if bit_range.end > 8 * data_field.len() {
+            return Err(SignalError::DataFieldTooShort);
+        }
+
+        Ok(bit_range)
+    }
+}
+
+// DBC files number the bits of each byte from the least significant one, where Msb0 numbers them
+// from the most significant one. The mapping is its own inverse, so it converts either way.
+pub(crate) fn msb0_to_dbc_start_bit(bit_index: usize) -> usize {
+    8 * (bit_index / 8) + 7 - bit_index % 8
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_little_endian() {
+        // Engine speed from the J1939 EEC1 message, 0.125 rpm per bit from byte 4.
+        let signal_spec = assert_ok!(SignalSpec::new(
+            24,
+            16,
+            ByteOrder::LittleEndian,
+            ValueType::Unsigned,
+            Scaling::new(0.125, 0.0),
+            0.0..=8031.875
+        ));
+
+        let data_field = [0xFF, 0xFF, 0xFF, 0x68, 0x13, 0xFF, 0xFF, 0xFF];
+
+        assert_eq!(signal_spec.decode_raw(&data_field), Ok(0x1368));
+        assert_eq!(signal_spec.decode(&data_field), Ok(621.0));
+    }
+
+    #[test]
+    fn test_decode_big_endian() {
+        // A 12-bit signal whose most significant bit is bit 7 of the first byte, so it takes the
+        // whole first byte and the top half of the second.
+        let signal_spec = assert_ok!(SignalSpec::new(
+            7,
+            12,
+            ByteOrder::BigEndian,
+            ValueType::Unsigned,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        assert_eq!(signal_spec.decode_raw(&[0xAB, 0xC0]), Ok(0xABC));
+
+        // An 8-bit signal from the bottom half of the first byte into the top half of the second.
+        let signal_spec = assert_ok!(SignalSpec::new(
+            3,
+            8,
+            ByteOrder::BigEndian,
+            ValueType::Unsigned,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        assert_eq!(signal_spec.decode_raw(&[0x0A, 0xB0]), Ok(0xAB));
+    }
+
+    #[test]
+    fn test_decode_signed() {
+        let signal_spec = assert_ok!(SignalSpec::new(
+            4,
+            8,
+            ByteOrder::LittleEndian,
+            ValueType::Signed,
+            Scaling::new(0.5, -10.0),
+            0.0..=0.0
+        ));
+
+        // The raw value is 0xFE, which is -2.
+        assert_eq!(signal_spec.decode(&[0xE0, 0x0F]), Ok(-11.0));
+        assert_eq!(signal_spec.decode(&[0x10, 0x00]), Ok(-9.5));
+
+        let signal_spec = assert_ok!(SignalSpec::new(
+            0,
+            64,
+            ByteOrder::LittleEndian,
+            ValueType::Signed,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        assert_eq!(signal_spec.decode(&[0xFF; 8]), Ok(-1.0));
+    }
+
+    #[test]
+    fn test_encode() {
+        let signal_spec = assert_ok!(SignalSpec::new(
+            24,
+            16,
+            ByteOrder::LittleEndian,
+            ValueType::Unsigned,
+            Scaling::new(0.125, 0.0),
+            0.0..=8031.875
+        ));
+
+        let mut data_field = [0xFF; 8];
+        assert_ok!(signal_spec.encode(621.0, &mut data_field));
+        assert_eq!(data_field, [0xFF, 0xFF, 0xFF, 0x68, 0x13, 0xFF, 0xFF, 0xFF]);
+
+        let signal_spec = assert_ok!(SignalSpec::new(
+            3,
+            8,
+            ByteOrder::BigEndian,
+            ValueType::Signed,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        let mut data_field = [0x00; 2];
+        assert_ok!(signal_spec.encode(-2.0, &mut data_field));
+        assert_eq!(data_field, [0x0F, 0xE0]);
+        assert_eq!(signal_spec.decode(&data_field), Ok(-2.0));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+            for value_type in [ValueType::Unsigned, ValueType::Signed] {
+                let signal_spec = assert_ok!(SignalSpec::new(
+                    13,
+                    10,
+                    byte_order,
+                    value_type,
+                    Scaling::new(0.1, 5.0),
+                    0.0..=0.0
+                ));
+
+                let mut data_field = [0x00; 4];
+                assert_ok!(signal_spec.encode(42.3, &mut data_field));
+
+                let value = assert_ok!(signal_spec.decode(&data_field));
+                assert!((value - 42.3).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_float() {
+        let signal_spec = assert_ok!(SignalSpec::new(
+            0,
+            32,
+            ByteOrder::LittleEndian,
+            ValueType::Float32,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        let mut data_field = [0x00; 4];
+        assert_ok!(signal_spec.encode(-1.5, &mut data_field));
+        assert_eq!(data_field, (-1.5f32).to_le_bytes());
+        assert_eq!(signal_spec.decode(&data_field), Ok(-1.5));
+
+        let signal_spec = assert_ok!(SignalSpec::new(
+            7,
+            64,
+            ByteOrder::BigEndian,
+            ValueType::Float64,
+            Scaling::new(2.0, 0.0),
+            0.0..=0.0
+        ));
+
+        let data_field = 0.1f64.to_be_bytes();
+        assert_eq!(signal_spec.decode(&data_field), Ok(0.2));
+
+        assert_err_eq!(
+            SignalSpec::new(
+                0,
+                16,
+                ByteOrder::LittleEndian,
+                ValueType::Float32,
+                Scaling::new(1.0, 0.0),
+                0.0..=0.0
+            ),
+            SignalError::LengthInvalidForValueType
+        );
+    }
+
+    #[test]
+    fn test_msb0_to_dbc_start_bit() {
+        assert_eq!(msb0_to_dbc_start_bit(0), 7);
+        assert_eq!(msb0_to_dbc_start_bit(7), 0);
+        assert_eq!(msb0_to_dbc_start_bit(16), 23);
+
+        for bit_index in 0..64 {
+            assert_eq!(msb0_to_dbc_start_bit(msb0_to_dbc_start_bit(bit_index)), bit_index);
+        }
+    }
+
+    #[test]
+    fn test_signal_errors() {
+        assert_err_eq!(
+            SignalSpec::new(
+                0,
+                0,
+                ByteOrder::LittleEndian,
+                ValueType::Unsigned,
+                Scaling::new(1.0, 0.0),
+                0.0..=0.0
+            ),
+            SignalError::LengthMustBeNonZero
+        );
+        assert_err_eq!(
+            SignalSpec::new(
+                0,
+                65,
+                ByteOrder::LittleEndian,
+                ValueType::Unsigned,
+                Scaling::new(1.0, 0.0),
+                0.0..=0.0
+            ),
+            SignalError::LengthTooLong
+        );
+        assert_err_eq!(
+            SignalSpec::new(
+                0,
+                8,
+                ByteOrder::LittleEndian,
+                ValueType::Unsigned,
+                Scaling::new(0.0, 0.0),
+                0.0..=0.0
+            ),
+            SignalError::FactorMustBeNonZero
+        );
+
+        let signal_spec = assert_ok!(SignalSpec::new(
+            4,
+            8,
+            ByteOrder::LittleEndian,
+            ValueType::Signed,
+            Scaling::new(1.0, 0.0),
+            -100.0..=100.0
+        ));
+
+        assert_err_eq!(signal_spec.decode(&[0x00]), SignalError::DataFieldTooShort);
+        assert_err_eq!(
+            signal_spec.encode(101.0, &mut [0x00; 2]),
+            SignalError::ValueOutOfRange
+        );
+        assert_err_eq!(
+            signal_spec.encode_raw(0x100, &mut [0x00; 2]),
+            SignalError::RawValueOutOfRange
+        );
+
+        let signal_spec = assert_ok!(SignalSpec::new(
+            0,
+            8,
+            ByteOrder::LittleEndian,
+            ValueType::Signed,
+            Scaling::new(1.0, 0.0),
+            0.0..=0.0
+        ));
+
+        assert_err_eq!(
+            signal_spec.encode(128.0, &mut [0x00]),
+            SignalError::RawValueOutOfRange
+        );
+        assert_err_eq!(
+            signal_spec.encode(-129.0, &mut [0x00]),
+            SignalError::RawValueOutOfRange
+        );
+        assert_ok!(signal_spec.encode(-128.0, &mut [0x00]));
+    }
+}
@@ -0,0 +1,521 @@
+use std::{error::Error, ops::RangeInclusive};
+
+use strum::Display;
+
+use crate::can::sampler::{SamplerConfiguration, SamplerConfigurationError};
+
+// The synchronization segment is always one time quantum long.
+const SYNCHRONIZATION_SEGMENT_LENGTH: u32 = 1;
+
+#[derive(Display, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BitTimingError {
+    ClockMustBeNonZero,
+    BitrateMustBeNonZero,
+    SamplePointOutOfRange,
+    SynchronizationJumpWidthOutOfRange,
+    DataPhaseUnsupported,
+    NoValidBitTiming,
+}
+
+impl Error for BitTimingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitTimingLimits {
+    prescaler: RangeInclusive<u32>,
+    time_segment_1: RangeInclusive<u32>,
+    time_segment_2: RangeInclusive<u32>,
+    maximum_synchronization_jump_width: u32,
+}
+
+impl BitTimingLimits {
+    // Time segment 1 is the propagation segment and phase segment 1 together, as most controllers
+    // only have a single register field for both.
+    pub fn new(
+        prescaler: RangeInclusive<u32>,
+        time_segment_1: RangeInclusive<u32>,
+        time_segment_2: RangeInclusive<u32>,
+        maximum_synchronization_jump_width: u32,
+    ) -> Self {
+        Self {
+            prescaler,
+            time_segment_1,
+            time_segment_2,
+            maximum_synchronization_jump_width,
+        }
+    }
+
+    pub fn stm32_fdcan_nominal() -> Self {
+        Self::new(1..=512, 2..=256, 1..=128, 128)
+    }
+
+    pub fn stm32_fdcan_data() -> Self {
+        Self::new(1..=32, 1..=32, 1..=16, 16)
+    }
+
+    pub fn mcp2518fd_nominal() -> Self {
+        Self::new(1..=256, 2..=256, 1..=128, 128)
+    }
+
+    pub fn mcp2518fd_data() -> Self {
+        Self::new(1..=256, 1..=32, 1..=16, 16)
+    }
+
+    pub fn prescaler(&self) -> &RangeInclusive<u32> {
+        &self.prescaler
+    }
+
+    pub fn time_segment_1(&self) -> &RangeInclusive<u32> {
+        &self.time_segment_1
+    }
+
+    pub fn time_segment_2(&self) -> &RangeInclusive<u32> {
+        &self.time_segment_2
+    }
+
+    pub fn maximum_synchronization_jump_width(&self) -> u32 {
+        self.maximum_synchronization_jump_width
+    }
+
+    fn time_quanta_per_bit(&self) -> RangeInclusive<u32> {
+        (SYNCHRONIZATION_SEGMENT_LENGTH + self.time_segment_1.start() + self.time_segment_2.start())
+            ..=(SYNCHRONIZATION_SEGMENT_LENGTH
+                + self.time_segment_1.end()
+                + self.time_segment_2.end())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BitTiming {
+    clock: u32,
+    prescaler: u32,
+    propagation_segment: u32,
+    phase_segment_1: u32,
+    phase_segment_2: u32,
+    synchronization_jump_width: u32,
+    bitrate_error: f64,
+    sample_point_error: f64,
+}
+
+impl BitTiming {
+    pub fn prescaler(&self) -> u32 {
+        self.prescaler
+    }
+
+    pub fn propagation_segment(&self) -> u32 {
+        self.propagation_segment
+    }
+
+    pub fn phase_segment_1(&self) -> u32 {
+        self.phase_segment_1
+    }
+
+    pub fn phase_segment_2(&self) -> u32 {
+        self.phase_segment_2
+    }
+
+    pub fn synchronization_jump_width(&self) -> u32 {
+        self.synchronization_jump_width
+    }
+
+    pub fn time_segment_1(&self) -> u32 {
+        self.propagation_segment + self.phase_segment_1
+    }
+
+    pub fn time_quanta_per_bit(&self) -> u32 {
+        SYNCHRONIZATION_SEGMENT_LENGTH + self.time_segment_1() + self.phase_segment_2
+    }
+
+    pub fn bitrate(&self) -> f64 {
+        f64::from(self.clock) / f64::from(self.prescaler * self.time_quanta_per_bit())
+    }
+
+    pub fn sample_point(&self) -> f64 {
+        100.0 * f64::from(SYNCHRONIZATION_SEGMENT_LENGTH + self.time_segment_1())
+            / f64::from(self.time_quanta_per_bit())
+    }
+
+    // The relative bitrate error, in percent.
+    pub fn bitrate_error(&self) -> f64 {
+        self.bitrate_error
+    }
+
+    // The sample point error, in percentage points.
+    pub fn sample_point_error(&self) -> f64 {
+        self.sample_point_error
+    }
+
+    // The synchronization jump width can only be narrowed, as it starts at the largest the
+    // limits allow.
+    pub fn with_synchronization_jump_width(
+        self,
+        synchronization_jump_width: u32,
+    ) -> Result<Self, BitTimingError> {
+        if !(1..=self.synchronization_jump_width).contains(&synchronization_jump_width) {
+            return Err(BitTimingError::SynchronizationJumpWidthOutOfRange);
+        }
+
+        Ok(Self {
+            synchronization_jump_width,
+            ..self
+        })
+    }
+
+    pub fn to_sampler_configuration(
+        &self,
+        sample_rate: u32,
+    ) -> Result<SamplerConfiguration, SamplerConfigurationError> {
+        SamplerConfiguration::new(
+            sample_rate,
+            self.bitrate().round() as u32,
+            self.sample_point(),
+            self.time_quanta_per_bit(),
+            self.synchronization_jump_width,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitTimingCalculator {
+    clock: u32,
+    nominal_limits: BitTimingLimits,
+    data_limits: Option<BitTimingLimits>,
+}
+
+impl BitTimingCalculator {
+    pub fn new(clock: u32, nominal_limits: BitTimingLimits) -> Result<Self, BitTimingError> {
+        if clock == 0 {
+            return Err(BitTimingError::ClockMustBeNonZero);
+        }
+
+        Ok(Self {
+            clock,
+            nominal_limits,
+            data_limits: None,
+        })
+    }
+
+    pub fn new_fd(
+        clock: u32,
+        nominal_limits: BitTimingLimits,
+        data_limits: BitTimingLimits,
+    ) -> Result<Self, BitTimingError> {
+        Ok(Self {
+            data_limits: Some(data_limits),
+            ..Self::new(clock, nominal_limits)?
+        })
+    }
+
+    pub fn stm32_fdcan(clock: u32) -> Result<Self, BitTimingError> {
+        Self::new_fd(
+            clock,
+            BitTimingLimits::stm32_fdcan_nominal(),
+            BitTimingLimits::stm32_fdcan_data(),
+        )
+    }
+
+    pub fn mcp2518fd(clock: u32) -> Result<Self, BitTimingError> {
+        Self::new_fd(
+            clock,
+            BitTimingLimits::mcp2518fd_nominal(),
+            BitTimingLimits::mcp2518fd_data(),
+        )
+    }
+
+    pub fn clock(&self) -> u32 {
+        self.clock
+    }
+
+    pub fn nominal_limits(&self) -> &BitTimingLimits {
+        &self.nominal_limits
+    }
+
+    pub fn data_limits(&self) -> Option<&BitTimingLimits> {
+        self.data_limits.as_ref()
+    }
+
+    pub fn nominal_bit_timings(
+        &self,
+        bitrate: u32,
+        sample_point: f64,
+    ) -> Result<Vec<BitTiming>, BitTimingError> {
+        self.bit_timings(&self.nominal_limits, bitrate, sample_point)
+    }
+
+    pub fn data_bit_timings(
+        &self,
+        bitrate: u32,
+        sample_point: f64,
+    ) -> Result<Vec<BitTiming>, BitTimingError> {
+        let data_limits = self.data_limits.as_ref().ok_or(BitTimingError::DataPhaseUnsupported)?;
+
+        self.bit_timings(data_limits, bitrate, sample_point)
+    }
+
+    fn bit_timings(
+        &self,
+        limits: &BitTimingLimits,
+        bitrate: u32,
+        sample_point: f64,
+    ) -> Result<Vec<BitTiming>, BitTimingError> {
+        if bitrate == 0 {
+            return Err(BitTimingError::BitrateMustBeNonZero);
+        }
+
+        if !(sample_point > 0.0 && sample_point < 100.0) {
+            return Err(BitTimingError::SamplePointOutOfRange);
+        }
+
+        let time_quanta_per_bit_range = limits.time_quanta_per_bit();
+
+        let mut bit_timings = Vec::new();
+
+        for prescaler in limits.prescaler.clone() {
+            let ideal_time_quanta_per_bit =
+                f64::from(self.clock) / (f64::from(prescaler) * f64::from(bitrate));
+
+            // Only the whole numbers of time quanta either side of the ideal can be the closest.
+            let mut time_quanta_per_bit_candidates = vec![
+                ideal_time_quanta_per_bit.floor() as u32,
+                ideal_time_quanta_per_bit.ceil() as u32,
+            ];
+            time_quanta_per_bit_candidates.dedup();
+
+            for time_quanta_per_bit in time_quanta_per_bit_candidates {
+                if !time_quanta_per_bit_range.contains(&time_quanta_per_bit) {
+                    continue;
+                }
+
+                // Every split of the remaining time quanta between the two time segments is a
+                // valid bit timing, with its own sample point.
+                for time_segment_2 in limits.time_segment_2.clone() {
+                    let Some(time_segment_1) = time_quanta_per_bit
+                        .checked_sub(SYNCHRONIZATION_SEGMENT_LENGTH + time_segment_2)
+                    else {
+                        break;
+                    };
+
+                    if !limits.time_segment_1.contains(&time_segment_1) {
+                        continue;
+                    }
+
+                    bit_timings.push(self.bit_timing(
+                        limits,
+                        prescaler,
+                        time_segment_1,
+                        time_segment_2,
+                        bitrate,
+                        sample_point,
+                    ));
+                }
+            }
+        }
+
+        if bit_timings.is_empty() {
+            return Err(BitTimingError::NoValidBitTiming);
+        }
+
+        // Ties go to the lowest prescaler, which gives the finest time quanta.
+        bit_timings.sort_by(|a, b| {
+            a.bitrate_error
+                .total_cmp(&b.bitrate_error)
+                .then(a.sample_point_error.total_cmp(&b.sample_point_error))
+                .then(a.prescaler.cmp(&b.prescaler))
+        });
+
+        Ok(bit_timings)
+    }
+
+    // Controllers only program time segment 1, so the propagation segment is fixed at its smaller
+    // half. The synchronization jump width is the largest the limits allow, and can be narrowed
+    // with BitTiming::with_synchronization_jump_width.
+    fn bit_timing(
+        &self,
+        limits: &BitTimingLimits,
+        prescaler: u32,
+        time_segment_1: u32,
+        time_segment_2: u32,
+        bitrate: u32,
+        sample_point: f64,
+    ) -> BitTiming {
+        let propagation_segment = time_segment_1 / 2;
+        let phase_segment_1 = time_segment_1 - propagation_segment;
+        let synchronization_jump_width = phase_segment_1
+            .min(time_segment_2)
+            .min(limits.maximum_synchronization_jump_width);
+
+        let mut bit_timing = BitTiming {
+            clock: self.clock,
+            prescaler,
+            propagation_segment,
+            phase_segment_1,
+            phase_segment_2: time_segment_2,
+            synchronization_jump_width,
+            bitrate_error: 0.0,
+            sample_point_error: 0.0,
+        };
+
+        bit_timing.bitrate_error =
+            100.0 * (bit_timing.bitrate() - f64::from(bitrate)).abs() / f64::from(bitrate);
+        bit_timing.sample_point_error = (bit_timing.sample_point() - sample_point).abs();
+
+        bit_timing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err_eq, assert_ok, assert_some};
+
+    use super::*;
+
+    #[test]
+    fn test_nominal_bit_timings() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::stm32_fdcan(80_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.nominal_bit_timings(500_000, 87.5));
+        let bit_timing = bit_timings[0];
+
+        assert_eq!(bit_timing.prescaler(), 1);
+        assert_eq!(bit_timing.propagation_segment(), 69);
+        assert_eq!(bit_timing.phase_segment_1(), 70);
+        assert_eq!(bit_timing.phase_segment_2(), 20);
+        assert_eq!(bit_timing.synchronization_jump_width(), 20);
+        assert_eq!(bit_timing.time_quanta_per_bit(), 160);
+        assert_eq!(bit_timing.bitrate(), 500_000.0);
+        assert_eq!(bit_timing.sample_point(), 87.5);
+        assert_eq!(bit_timing.bitrate_error(), 0.0);
+        assert_eq!(bit_timing.sample_point_error(), 0.0);
+
+        assert!(
+            bit_timings
+                .windows(2)
+                .all(|pair| pair[0].bitrate_error() <= pair[1].bitrate_error())
+        );
+    }
+
+    #[test]
+    fn test_alternative_sample_points() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::stm32_fdcan(80_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.nominal_bit_timings(500_000, 87.5));
+
+        // A 16 time quanta bit with a 12/3 split samples at 81.25% instead of 87.5%.
+        let bit_timing = assert_some!(bit_timings.iter().find(|bit_timing| {
+            bit_timing.prescaler() == 10
+                && bit_timing.time_segment_1() == 12
+                && bit_timing.phase_segment_2() == 3
+        }));
+
+        assert_eq!(bit_timing.sample_point(), 81.25);
+        assert_eq!(bit_timing.sample_point_error(), 6.25);
+
+        // Exact bitrates are ranked by how close they sample to the requested point.
+        assert!(
+            bit_timings
+                .iter()
+                .take_while(|bit_timing| bit_timing.bitrate_error() == 0.0)
+                .collect::<Vec<_>>()
+                .windows(2)
+                .all(|pair| pair[0].sample_point_error() <= pair[1].sample_point_error())
+        );
+    }
+
+    #[test]
+    fn test_with_synchronization_jump_width() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::stm32_fdcan(80_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.nominal_bit_timings(500_000, 87.5));
+        let bit_timing = assert_ok!(bit_timings[0].with_synchronization_jump_width(4));
+
+        assert_eq!(bit_timing.synchronization_jump_width(), 4);
+        assert_eq!(bit_timing.sample_point(), 87.5);
+
+        assert_err_eq!(
+            bit_timings[0].with_synchronization_jump_width(0),
+            BitTimingError::SynchronizationJumpWidthOutOfRange
+        );
+        assert_err_eq!(
+            bit_timings[0].with_synchronization_jump_width(21),
+            BitTimingError::SynchronizationJumpWidthOutOfRange
+        );
+    }
+
+    #[test]
+    fn test_data_bit_timings() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::stm32_fdcan(80_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.data_bit_timings(2_000_000, 75.0));
+        let bit_timing = bit_timings[0];
+
+        assert_eq!(bit_timing.prescaler(), 1);
+        assert_eq!(bit_timing.time_segment_1(), 29);
+        assert_eq!(bit_timing.phase_segment_2(), 10);
+        assert_eq!(bit_timing.synchronization_jump_width(), 10);
+        assert_eq!(bit_timing.sample_point(), 75.0);
+
+        // The data phase allows at most 49 time quanta per bit, which rules out a prescaler of one.
+        let bit_timings = assert_ok!(bit_timing_calculator.data_bit_timings(1_000_000, 75.0));
+        let bit_timing = bit_timings[0];
+
+        assert_eq!(bit_timing.prescaler(), 2);
+        assert_eq!(bit_timing.time_quanta_per_bit(), 40);
+        assert_eq!(bit_timing.sample_point(), 75.0);
+    }
+
+    #[test]
+    fn test_inexact_bit_timings() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::mcp2518fd(40_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.nominal_bit_timings(333_333, 80.0));
+        let bit_timing = bit_timings[0];
+
+        assert_eq!(bit_timing.prescaler(), 1);
+        assert_eq!(bit_timing.time_quanta_per_bit(), 120);
+        assert_eq!(bit_timing.sample_point(), 80.0);
+        assert!(bit_timing.bitrate_error() > 0.0 && bit_timing.bitrate_error() < 0.001);
+    }
+
+    #[test]
+    fn test_to_sampler_configuration() {
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::stm32_fdcan(80_000_000));
+
+        let bit_timings = assert_ok!(bit_timing_calculator.nominal_bit_timings(500_000, 87.5));
+        let sampler_configuration =
+            assert_ok!(bit_timings[0].to_sampler_configuration(16_000_000));
+
+        assert_eq!(sampler_configuration.bitrate(), 500_000);
+        assert_eq!(sampler_configuration.sample_point(), 87.5);
+        assert_eq!(sampler_configuration.time_quanta_per_bit(), 160);
+        assert_eq!(sampler_configuration.synchronization_jump_width(), 20);
+    }
+
+    #[test]
+    fn test_bit_timing_errors() {
+        assert_err_eq!(
+            BitTimingCalculator::new(0, BitTimingLimits::stm32_fdcan_nominal()),
+            BitTimingError::ClockMustBeNonZero
+        );
+
+        let bit_timing_calculator = assert_ok!(BitTimingCalculator::new(
+            8_000_000,
+            BitTimingLimits::stm32_fdcan_nominal()
+        ));
+
+        assert_err_eq!(
+            bit_timing_calculator.nominal_bit_timings(0, 87.5),
+            BitTimingError::BitrateMustBeNonZero
+        );
+        assert_err_eq!(
+            bit_timing_calculator.nominal_bit_timings(500_000, 100.0),
+            BitTimingError::SamplePointOutOfRange
+        );
+        assert_err_eq!(
+            bit_timing_calculator.data_bit_timings(2_000_000, 75.0),
+            BitTimingError::DataPhaseUnsupported
+        );
+        assert_err_eq!(
+            bit_timing_calculator.nominal_bit_timings(4_000_000, 87.5),
+            BitTimingError::NoValidBitTiming
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, error::Error, fmt::Display};
 
 use bitvec::prelude::*;
 
@@ -75,6 +75,25 @@ pub fn unstuff<T: BitStore, B: AsRef<BitSlice<T, Msb0>>>(stuffed_bits: B, n: usi
     unstuffed_bits
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BitStuffingError {
+    bit_index: usize,
+}
+
+impl BitStuffingError {
+    pub fn bit_index(&self) -> usize {
+        self.bit_index
+    }
+}
+
+impl Display for BitStuffingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stuff error at bit {}", self.bit_index)
+    }
+}
+
+impl Error for BitStuffingError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;